@@ -0,0 +1,105 @@
+//! 命令行模式
+//!
+//! 带 `--once` 启动时只采集一次指标，打印到标准输出后退出，不创建窗口和托盘，也不初始化
+//! webview，可以在没有图形界面的服务器上用于脚本：
+//!
+//! ```bash
+//! corner-monitor --once --json
+//! corner-monitor --once --format csv
+//! ```
+//!
+//! 不带 `--once` 时忽略其他参数，正常启动悬浮窗。
+
+use std::io::Write;
+
+use crate::export::{export, export_format_from_str, ExportFormat};
+use crate::monitor::get_system_info_once;
+
+const USAGE: &str = "usage: corner-monitor --once [--json | --format json|csv]";
+
+/// 解析命令行参数（不含程序名），返回 `None` 表示正常启动悬浮窗
+///
+/// 未指定格式时输出 JSON。
+pub fn parse_args<I, S>(args: I) -> Result<Option<ExportFormat>, String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let args: Vec<S> = args.into_iter().collect();
+    if !args.iter().any(|arg| arg.as_ref() == "--once") {
+        return Ok(None);
+    }
+    let mut format = None;
+    let mut args = args.iter().map(AsRef::as_ref);
+    while let Some(arg) = args.next() {
+        let next = match arg {
+            "--once" => continue,
+            "--json" => ExportFormat::Json,
+            "--format" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "missing value for --format".to_string())?;
+                export_format_from_str(value)
+                    .ok_or_else(|| format!("invalid format: {}, expected json or csv", value))?
+            }
+            other => return Err(format!("unknown argument: {}", other)),
+        };
+        if format.is_some_and(|format| format != next) {
+            return Err("conflicting output formats".to_string());
+        }
+        format = Some(next);
+    }
+    Ok(Some(format.unwrap_or(ExportFormat::Json)))
+}
+
+/// 按命令行参数执行一次性输出，返回进程退出码；`None` 表示应正常启动悬浮窗
+pub fn run_from_args<I, S>(args: I) -> Option<i32>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let format = match parse_args(args) {
+        Ok(format) => format?,
+        Err(message) => {
+            eprintln!("{}\n{}", message, USAGE);
+            return Some(2);
+        }
+    };
+    let output = export(&get_system_info_once(), format).and_then(|text| {
+        let mut stdout = std::io::stdout().lock();
+        writeln!(stdout, "{}", text.trim_end()).map_err(|error| error.to_string())
+    });
+    match output {
+        Ok(()) => Some(0),
+        Err(message) => {
+            eprintln!("{}", message);
+            Some(1)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse_args(Vec::<String>::new()), Ok(None));
+        // 不带 `--once` 时按正常启动处理，如 macOS 传入的 `-psn_*` 参数
+        assert_eq!(parse_args(["-psn_0_12345"]), Ok(None));
+        assert_eq!(parse_args(["--once"]), Ok(Some(ExportFormat::Json)));
+        assert_eq!(
+            parse_args(["--once", "--json"]),
+            Ok(Some(ExportFormat::Json))
+        );
+        assert_eq!(
+            parse_args(["--format", "CSV", "--once"]),
+            Ok(Some(ExportFormat::Csv))
+        );
+
+        assert!(parse_args(["--once", "--format"]).is_err());
+        assert!(parse_args(["--once", "--format", "xml"]).is_err());
+        assert!(parse_args(["--once", "--json", "--format", "csv"]).is_err());
+        assert!(parse_args(["--once", "--verbose"]).is_err());
+    }
+}
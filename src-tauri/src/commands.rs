@@ -2,19 +2,119 @@ use std::sync::Mutex;
 
 use tauri::{Emitter, Manager};
 
-use crate::monitor::{Monitor, SystemInfo};
+use crate::export::{export, export_format_from_str, snapshot_json, Snapshot};
+use crate::format;
+use crate::hotkey::rebind_hotkey;
+use crate::i18n::{lang_from_str, lang_to_str};
+use crate::logger::MetricLogger;
+use crate::monitor::{
+    BatteryInfo, DiskInfo, HistoryInfo, Monitor, NetworkInfo, ProcessInfo, SystemInfo,
+};
+#[cfg(feature = "prometheus")]
+use crate::prometheus::{prometheus_text, MetricsServer};
 use crate::state::{
-    layout_to_str, Layout, MonitorVisibility, SettingsStore, UiState, WindowPosition, KEY_LAYOUT,
-    KEY_MONITOR_TARGET, SIZE_HORIZONTAL, SIZE_VERTICAL,
+    clamp_font_size, clamp_opacity, clamp_window_size, format_celsius, import_ui_state,
+    layout_from_str, layout_to_str, metric_order_from_strs, metric_order_to_strs,
+    monitor_item_from_str, monitor_position_key, monitor_summaries, monitor_target_for_monitor,
+    monitor_target_from_monitor, monitor_target_to_str, net_unit_from_str, net_unit_to_str,
+    normalize_hex_color, position_from_str, position_to_str, profile_from_state,
+    profiles_from_value, snap_mode_from_str, snap_mode_to_str, temp_unit_from_str,
+    temp_unit_to_str, valid_disk_alert_threshold, window_size_to_value, Layout, MetricColors,
+    MonitorIntervals, MonitorSummary, MonitorVisibility, NetworkUnit, SettingsStore, SnapMode,
+    UiState, WindowPosition, DEFAULT_FONT_SIZE, DEFAULT_HOTKEY, DEFAULT_METRIC_ORDER,
+    DEFAULT_SNAP_HOTKEY, KEY_DISK_ALERT_THRESHOLD, KEY_FONT_SIZE, KEY_HOTKEY, KEY_LANG, KEY_LAYOUT,
+    KEY_LOCK_TO_CORNER, KEY_MARGIN, KEY_METRICS_PORT, KEY_METRIC_COLORS, KEY_METRIC_ORDER,
+    KEY_MONITOR_INTERVALS, KEY_MONITOR_TARGET, KEY_NET_UNIT, KEY_OPACITY, KEY_POSITION,
+    KEY_PROFILES, KEY_REFRESH_INTERVAL, KEY_SIZE_HORIZONTAL, KEY_SIZE_VERTICAL, KEY_SNAP_HOTKEY,
+    KEY_SNAP_MODE, KEY_TEMP_UNIT, KEY_WINDOW_VISIBLE, KEY_WS_PORT, MAX_MARGIN,
+};
+use crate::tray::{
+    apply_ui_state, begin_window_drag, emit_metric_colors, finish_window_drag, format_net_speed,
+    reset_ui_state, select_next_monitor, set_global_text_color, set_monitor_item_visibility,
+    snap_window_to_nearest_corner, update_font_size, update_lang, update_layout, update_margin,
+    update_monitor_target, update_monitoring_paused, update_net_unit, update_opacity,
+    update_position, update_refresh_interval, update_temp_unit, update_text_color,
+    update_window_visible, TrayMenuItems,
+};
+#[cfg(feature = "websocket")]
+use crate::websocket::StreamServer;
+use crate::window::{
+    apply_layout_and_position, apply_window_position, calculate_window_position_on_monitor,
+    layout_size, monitor_for_window, remember_monitor_position, selected_monitor,
+    show_settings_window,
 };
-use crate::tray::{snap_window_to_nearest_corner, update_layout, TrayMenuItems};
-use crate::window::{apply_window_position, calculate_window_position_on_monitor, monitor_for_window};
 
 #[tauri::command]
 pub fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
 }
 
+/// 解析 `binary`/`decimal`，省略时与托盘提示一致按 1024 进位
+fn unit_base(base: Option<String>) -> Result<format::UnitBase, String> {
+    match base {
+        Some(base) => {
+            format::unit_base_from_str(&base).ok_or_else(|| format!("invalid unit base: {}", base))
+        }
+        None => Ok(format::UnitBase::Binary),
+    }
+}
+
+/// 与托盘提示相同的字节格式，如 `1.5 MiB`
+#[tauri::command]
+pub fn format_bytes(bytes: u64, base: Option<String>) -> Result<String, String> {
+    Ok(format::format_bytes(bytes, unit_base(base)?))
+}
+
+/// 与托盘提示相同的速率格式，如 `1.5 MiB/s`
+///
+/// `unit` 为 `bits` 时按比特显示，如 `12.0 Mb/s`；省略 `base` 时与托盘提示一致，
+/// 字节按 1024 进位、比特按 1000 进位。
+#[tauri::command]
+pub fn format_speed(
+    bytes_per_sec: u64,
+    base: Option<String>,
+    unit: Option<String>,
+) -> Result<String, String> {
+    let unit = match unit {
+        Some(unit) => {
+            net_unit_from_str(&unit).ok_or_else(|| format!("invalid network unit: {}", unit))?
+        }
+        None => NetworkUnit::Bytes,
+    };
+    if base.is_none() {
+        return Ok(format_net_speed(bytes_per_sec, unit));
+    }
+    let base = unit_base(base)?;
+    Ok(match unit {
+        NetworkUnit::Bytes => format::format_speed(bytes_per_sec, base),
+        NetworkUnit::Bits => format::format_bit_speed(bytes_per_sec, base),
+    })
+}
+
+/// 格式化摄氏温度，如 `45.0°C`，省略 `unit` 时使用当前的温度单位设置
+#[tauri::command]
+pub fn format_temperature(
+    state: tauri::State<'_, Mutex<UiState>>,
+    celsius: f32,
+    unit: Option<String>,
+) -> Result<String, String> {
+    let unit = match unit {
+        Some(unit) => temp_unit_from_str(&unit)
+            .ok_or_else(|| format!("invalid temperature unit: {}", unit))?,
+        None => state
+            .lock()
+            .map(|ui_state| ui_state.temp_unit)
+            .map_err(|_| "ui state lock poisoned".to_string())?,
+    };
+    Ok(format_celsius(celsius, unit))
+}
+
+/// 与托盘提示相同的百分比格式，如 `12.50%`
+#[tauri::command]
+pub fn format_percent(value: f32) -> String {
+    format::format_percent(value)
+}
+
 #[tauri::command]
 pub fn get_system_info(monitor: tauri::State<'_, Mutex<Monitor>>) -> Result<SystemInfo, String> {
     monitor
@@ -23,6 +123,409 @@ pub fn get_system_info(monitor: tauri::State<'_, Mutex<Monitor>>) -> Result<Syst
         .map_err(|_| "monitor lock poisoned".to_string())
 }
 
+/// 立即刷新全部数据并返回最新的 `SystemInfo`，如休眠唤醒后强制更新
+///
+/// 刷新会阻塞 200ms 以上，放到后台线程执行，期间不持有 `Monitor` 的锁，不会卡住界面。
+/// 连续点击等并发请求会合并为一次刷新。
+#[tauri::command]
+pub async fn refresh_now(app: tauri::AppHandle) -> Result<SystemInfo, String> {
+    let refresh = app
+        .state::<Mutex<Monitor>>()
+        .lock()
+        .map(|monitor| monitor.refresher())
+        .map_err(|_| "monitor lock poisoned".to_string())?;
+    tauri::async_runtime::spawn_blocking(refresh)
+        .await
+        .map_err(|error| error.to_string())
+}
+
+#[tauri::command]
+pub fn get_disk_info(monitor: tauri::State<'_, Mutex<Monitor>>) -> Result<DiskInfo, String> {
+    monitor
+        .lock()
+        .map(|state| state.get_disk_info())
+        .map_err(|_| "monitor lock poisoned".to_string())
+}
+
+#[tauri::command]
+pub fn get_network_info(
+    monitor: tauri::State<'_, Mutex<Monitor>>,
+) -> Result<NetworkInfo, String> {
+    monitor
+        .lock()
+        .map(|state| state.get_network_info())
+        .map_err(|_| "monitor lock poisoned".to_string())
+}
+
+/// 把本次运行记录的网络速率峰值重置为当前速率
+#[tauri::command]
+pub fn reset_network_peaks(monitor: tauri::State<'_, Mutex<Monitor>>) -> Result<(), String> {
+    monitor
+        .lock()
+        .map(|state| state.reset_network_peaks())
+        .map_err(|_| "monitor lock poisoned".to_string())
+}
+
+#[tauri::command]
+pub fn get_top_processes(
+    n: usize,
+    monitor: tauri::State<'_, Mutex<Monitor>>,
+) -> Result<Vec<ProcessInfo>, String> {
+    monitor
+        .lock()
+        .map(|state| state.get_top_processes(n))
+        .map_err(|_| "monitor lock poisoned".to_string())
+}
+
+#[tauri::command]
+pub fn get_battery_info(
+    monitor: tauri::State<'_, Mutex<Monitor>>,
+) -> Result<Option<BatteryInfo>, String> {
+    monitor
+        .lock()
+        .map(|state| state.get_battery_info())
+        .map_err(|_| "monitor lock poisoned".to_string())
+}
+
+#[tauri::command]
+pub fn get_history(
+    n: usize,
+    monitor: tauri::State<'_, Mutex<Monitor>>,
+) -> Result<HistoryInfo, String> {
+    monitor
+        .lock()
+        .map(|state| state.get_history(n))
+        .map_err(|_| "monitor lock poisoned".to_string())
+}
+
+#[tauri::command]
+pub fn export_metrics(
+    format: String,
+    monitor: tauri::State<'_, Mutex<Monitor>>,
+) -> Result<String, String> {
+    let format = export_format_from_str(&format)
+        .ok_or_else(|| format!("invalid export format: {}, expected json or csv", format))?;
+    let info = monitor
+        .lock()
+        .map(|state| state.get_system_info())
+        .map_err(|_| "monitor lock poisoned".to_string())?;
+    export(&info, format)
+}
+
+#[tauri::command]
+pub fn get_hotkey(state: tauri::State<'_, Mutex<UiState>>) -> String {
+    state
+        .lock()
+        .map(|ui_state| ui_state.hotkey.clone())
+        .unwrap_or_else(|_| DEFAULT_HOTKEY.to_string())
+}
+
+/// 重新绑定切换布局的全局快捷键，新快捷键已被占用时保留原来的绑定并返回错误
+#[tauri::command]
+pub fn set_hotkey(app: tauri::AppHandle, combo: String) -> Result<(), String> {
+    let combo = combo.trim().to_string();
+    let previous = get_hotkey(app.state::<Mutex<UiState>>());
+    rebind_hotkey(&app, &previous, &combo)?;
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.hotkey = combo.clone();
+    }
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_HOTKEY, combo);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_snap_hotkey(state: tauri::State<'_, Mutex<UiState>>) -> String {
+    state
+        .lock()
+        .map(|ui_state| ui_state.snap_hotkey.clone())
+        .unwrap_or_else(|_| DEFAULT_SNAP_HOTKEY.to_string())
+}
+
+/// 重新绑定吸附到最近角落的全局快捷键，新快捷键已被占用时保留原来的绑定并返回错误
+#[tauri::command]
+pub fn set_snap_hotkey(app: tauri::AppHandle, combo: String) -> Result<(), String> {
+    let combo = combo.trim().to_string();
+    let previous = get_snap_hotkey(app.state::<Mutex<UiState>>());
+    rebind_hotkey(&app, &previous, &combo)?;
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.snap_hotkey = combo.clone();
+    }
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_SNAP_HOTKEY, combo);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_metrics_port(state: tauri::State<'_, Mutex<UiState>>) -> Option<u16> {
+    state.lock().ok().and_then(|ui_state| ui_state.metrics_port)
+}
+
+/// 设置 Prometheus 端点的端口并立即生效，`None` 关闭服务
+#[tauri::command]
+pub fn set_metrics_port(app: tauri::AppHandle, port: Option<u16>) -> Result<(), String> {
+    if port == Some(0) {
+        return Err("invalid port: 0".to_string());
+    }
+    apply_metrics_port(&app, port)?;
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.metrics_port = port;
+    }
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_METRICS_PORT, port);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_disk_alert_threshold(state: tauri::State<'_, Mutex<UiState>>) -> Option<f64> {
+    state
+        .lock()
+        .ok()
+        .and_then(|ui_state| ui_state.disk_alert_threshold)
+}
+
+/// 设置单个磁盘使用率的告警阈值并立即生效，`None` 关闭磁盘告警
+///
+/// 在下一次磁盘采集时按新阈值检查，同一磁盘只有回落到阈值以下后再次越过才会重复告警。
+#[tauri::command]
+pub fn set_disk_alert_threshold(
+    app: tauri::AppHandle,
+    threshold: Option<f64>,
+) -> Result<(), String> {
+    let threshold = threshold
+        .map(|value| {
+            valid_disk_alert_threshold(value)
+                .ok_or_else(|| format!("invalid disk alert threshold: {}, expected 0-100", value))
+        })
+        .transpose()?;
+    {
+        let monitor = app.state::<Mutex<Monitor>>();
+        let monitor = monitor
+            .lock()
+            .map_err(|_| "monitor lock poisoned".to_string())?;
+        let mut config = monitor.config();
+        config.thresholds.disk_full = threshold;
+        monitor.set_config(config);
+    }
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.disk_alert_threshold = threshold;
+    }
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_DISK_ALERT_THRESHOLD, threshold);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_ws_port(state: tauri::State<'_, Mutex<UiState>>) -> Option<u16> {
+    state.lock().ok().and_then(|ui_state| ui_state.ws_port)
+}
+
+/// 设置 WebSocket 推送服务的端口并立即生效，`None` 关闭服务
+#[tauri::command]
+pub fn set_ws_port(app: tauri::AppHandle, port: Option<u16>) -> Result<(), String> {
+    if port == Some(0) {
+        return Err("invalid port: 0".to_string());
+    }
+    apply_ws_port(&app, port)?;
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.ws_port = port;
+    }
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_WS_PORT, port);
+    Ok(())
+}
+
+/// 按端口启动、重启或关闭 Prometheus 端点
+#[cfg(feature = "prometheus")]
+pub fn apply_metrics_port(app: &tauri::AppHandle, port: Option<u16>) -> Result<(), String> {
+    let Some(server) = app.try_state::<Mutex<Option<MetricsServer>>>() else {
+        return Ok(());
+    };
+    let mut server = server.lock().map_err(|error| error.to_string())?;
+    if server.as_ref().map(MetricsServer::port) == port {
+        return Ok(());
+    }
+    if let Some(previous) = server.take() {
+        previous.stop();
+    }
+    if let Some(port) = port {
+        let handle = app.clone();
+        *server = Some(MetricsServer::start(port, move || {
+            handle
+                .state::<Mutex<Monitor>>()
+                .lock()
+                .map(|monitor| prometheus_text(&monitor.get_system_info()))
+                .unwrap_or_default()
+        })?);
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "prometheus"))]
+pub fn apply_metrics_port(_app: &tauri::AppHandle, port: Option<u16>) -> Result<(), String> {
+    match port {
+        Some(_) => Err("built without the prometheus feature".to_string()),
+        None => Ok(()),
+    }
+}
+
+/// 按端口启动、重启或关闭 WebSocket 推送服务，关闭时断开所有客户端
+#[cfg(feature = "websocket")]
+pub fn apply_ws_port(app: &tauri::AppHandle, port: Option<u16>) -> Result<(), String> {
+    let Some(server) = app.try_state::<Mutex<Option<StreamServer>>>() else {
+        return Ok(());
+    };
+    let mut server = server.lock().map_err(|error| error.to_string())?;
+    if server.as_ref().map(StreamServer::port) == port {
+        return Ok(());
+    }
+    if let Some(previous) = server.take() {
+        previous.stop();
+    }
+    if let Some(port) = port {
+        *server = Some(StreamServer::start(port)?);
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "websocket"))]
+pub fn apply_ws_port(_app: &tauri::AppHandle, port: Option<u16>) -> Result<(), String> {
+    match port {
+        Some(_) => Err("built without the websocket feature".to_string()),
+        None => Ok(()),
+    }
+}
+
+/// 把最新快照推送给 WebSocket 客户端，服务未启动时不做任何事
+#[cfg(feature = "websocket")]
+pub fn broadcast_system_info(app: &tauri::AppHandle, info: &SystemInfo) {
+    if let Some(server) = app.try_state::<Mutex<Option<StreamServer>>>() {
+        if let Ok(server) = server.lock() {
+            if let Some(server) = server.as_ref() {
+                server.broadcast(info);
+            }
+        }
+    }
+}
+
+/// 将当前指标快照写入 `path`，返回写入的字节数
+#[tauri::command]
+pub fn export_snapshot(app: tauri::AppHandle, path: String) -> Result<usize, String> {
+    let (info, enabled) = app
+        .state::<Mutex<Monitor>>()
+        .lock()
+        .map(|state| (state.get_system_info(), state.config().enabled))
+        .map_err(|_| "monitor lock poisoned".to_string())?;
+    let content = snapshot_json(&Snapshot {
+        app_version: app.package_info().version.to_string(),
+        enabled,
+        system: &info,
+    })?;
+    std::fs::write(&path, &content)
+        .map_err(|error| format!("failed to write {}: {}", path, error))?;
+    Ok(content.len())
+}
+
+/// 开始把指标按 CSV 追加到 `path`，`fields` 为空时记录默认的几列
+///
+/// `path` 已有内容且表头与 `fields` 不同时返回错误。
+#[tauri::command]
+pub fn start_metric_logging(
+    logger: tauri::State<'_, MetricLogger>,
+    path: String,
+    fields: Vec<String>,
+) -> Result<(), String> {
+    logger.start(&path, fields)
+}
+
+/// 停止记录指标，返回之前是否正在记录
+#[tauri::command]
+pub fn stop_metric_logging(logger: tauri::State<'_, MetricLogger>) -> Result<bool, String> {
+    logger.stop()
+}
+
+#[tauri::command]
+pub fn get_monitor_intervals(state: tauri::State<'_, Mutex<UiState>>) -> MonitorIntervals {
+    state
+        .lock()
+        .map(|ui_state| ui_state.intervals)
+        .unwrap_or_default()
+}
+
+#[tauri::command]
+pub fn set_monitor_intervals(
+    app: tauri::AppHandle,
+    cpu_ms: u64,
+    memory_ms: u64,
+    disk_ms: u64,
+    network_ms: u64,
+) -> Result<(), String> {
+    let intervals = MonitorIntervals {
+        cpu_ms,
+        memory_ms,
+        disk_ms,
+        network_ms,
+    };
+    if !intervals.is_valid() {
+        return Err("intervals must be greater than 0".to_string());
+    }
+    {
+        let monitor = app.state::<Mutex<Monitor>>();
+        let monitor = monitor
+            .lock()
+            .map_err(|_| "monitor lock poisoned".to_string())?;
+        monitor.set_config(intervals.apply(monitor.config()));
+    }
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.intervals = intervals;
+    }
+    // 自定义间隔覆盖托盘预设，避免下次启动时被旧预设改回
+    let preset = (cpu_ms == network_ms).then_some(cpu_ms);
+    if let Some(tray) = app.try_state::<TrayMenuItems>() {
+        tray.set_refresh_interval(preset.unwrap_or(0));
+    }
+    let store = app.state::<SettingsStore>();
+    let value = serde_json::to_value(intervals).map_err(|error| error.to_string())?;
+    store.set(KEY_MONITOR_INTERVALS, value);
+    match preset {
+        Some(interval_ms) => store.set(KEY_REFRESH_INTERVAL, interval_ms),
+        None => {
+            store.delete(KEY_REFRESH_INTERVAL);
+        }
+    }
+    Ok(())
+}
+
+/// 同时调整 CPU 和网络的采集间隔，对应托盘“刷新间隔”菜单
+#[tauri::command]
+pub fn set_refresh_interval(app: tauri::AppHandle, interval_ms: u64) -> Result<(), String> {
+    if interval_ms == 0 {
+        return Err("interval must be greater than 0".to_string());
+    }
+    if let Some(tray) = app.try_state::<TrayMenuItems>() {
+        update_refresh_interval(&app, interval_ms, &tray);
+        return Ok(());
+    }
+    let mut intervals = MonitorIntervals::default();
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.intervals.cpu_ms = interval_ms;
+        state.intervals.network_ms = interval_ms;
+        intervals = state.intervals;
+    }
+    {
+        let monitor = app.state::<Mutex<Monitor>>();
+        let monitor = monitor
+            .lock()
+            .map_err(|_| "monitor lock poisoned".to_string())?;
+        monitor.set_config(intervals.apply(monitor.config()));
+    }
+    let store = app.state::<SettingsStore>();
+    let value = serde_json::to_value(intervals).map_err(|error| error.to_string())?;
+    store.set(KEY_MONITOR_INTERVALS, value);
+    store.set(KEY_REFRESH_INTERVAL, interval_ms);
+    Ok(())
+}
+
 #[tauri::command]
 pub fn get_layout(state: tauri::State<'_, Mutex<UiState>>) -> String {
     state
@@ -31,6 +534,112 @@ pub fn get_layout(state: tauri::State<'_, Mutex<UiState>>) -> String {
         .unwrap_or_else(|_| "vertical".to_string())
 }
 
+#[tauri::command]
+pub fn get_net_unit(state: tauri::State<'_, Mutex<UiState>>) -> String {
+    state
+        .lock()
+        .map(|ui_state| net_unit_to_str(ui_state.net_unit).to_string())
+        .unwrap_or_else(|_| "bytes".to_string())
+}
+
+#[tauri::command]
+pub fn set_net_unit(app: tauri::AppHandle, unit: String) -> Result<(), String> {
+    let unit = net_unit_from_str(&unit).ok_or_else(|| format!("invalid network unit: {}", unit))?;
+    if let Some(tray) = app.try_state::<TrayMenuItems>() {
+        update_net_unit(&app, unit, &tray);
+        return Ok(());
+    }
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.net_unit = unit;
+    }
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_NET_UNIT, net_unit_to_str(unit));
+    let _ = app.emit("net-unit-changed", net_unit_to_str(unit));
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_temperature_unit(state: tauri::State<'_, Mutex<UiState>>) -> String {
+    state
+        .lock()
+        .map(|ui_state| temp_unit_to_str(ui_state.temp_unit).to_string())
+        .unwrap_or_else(|_| "celsius".to_string())
+}
+
+/// `get_temperature_unit` 的简写
+#[tauri::command]
+pub fn get_temp_unit(state: tauri::State<'_, Mutex<UiState>>) -> String {
+    get_temperature_unit(state)
+}
+
+#[tauri::command]
+pub fn set_temperature_unit(app: tauri::AppHandle, unit: String) -> Result<(), String> {
+    let unit =
+        temp_unit_from_str(&unit).ok_or_else(|| format!("invalid temperature unit: {}", unit))?;
+    if let Some(tray) = app.try_state::<TrayMenuItems>() {
+        update_temp_unit(&app, unit, &tray);
+        return Ok(());
+    }
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.temp_unit = unit;
+    }
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_TEMP_UNIT, temp_unit_to_str(unit));
+    let _ = app.emit("temp-unit-changed", temp_unit_to_str(unit));
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_language(state: tauri::State<'_, Mutex<UiState>>) -> String {
+    state
+        .lock()
+        .map(|ui_state| lang_to_str(ui_state.lang).to_string())
+        .unwrap_or_else(|_| "zh".to_string())
+}
+
+/// 切换托盘菜单语言，立即重设现有菜单项的文案，无需重启
+#[tauri::command]
+pub fn set_language(app: tauri::AppHandle, language: String) -> Result<(), String> {
+    let lang =
+        lang_from_str(&language).ok_or_else(|| format!("invalid language: {}", language))?;
+    if let Some(tray) = app.try_state::<TrayMenuItems>() {
+        update_lang(&app, lang, &tray);
+        return Ok(());
+    }
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.lang = lang;
+    }
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_LANG, lang_to_str(lang));
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_metric_order(state: tauri::State<'_, Mutex<UiState>>) -> Vec<&'static str> {
+    state
+        .lock()
+        .map(|ui_state| metric_order_to_strs(&ui_state.metric_order))
+        .unwrap_or_else(|_| metric_order_to_strs(&DEFAULT_METRIC_ORDER))
+}
+
+/// 设置指标显示顺序，返回实际生效的顺序
+///
+/// 提交的顺序可以包含 `cpu`、`temp`、`gpu`、`mem`、`net`、`battery`，不能重复。
+/// 缺少 `temp` 或 `gpu` 时按默认顺序补在前一项之后，缺少其他指标、出现未知或重复的
+/// 指标时恢复默认顺序。
+#[tauri::command]
+pub fn set_metric_order(app: tauri::AppHandle, order: Vec<String>) -> Vec<&'static str> {
+    let order = metric_order_from_strs(&order).unwrap_or_else(|| DEFAULT_METRIC_ORDER.to_vec());
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.metric_order = order.clone();
+    }
+    let payload = metric_order_to_strs(&order);
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_METRIC_ORDER, payload.clone());
+    let _ = app.emit("metric-order-changed", payload.clone());
+    payload
+}
+
 #[tauri::command]
 pub fn get_monitor_visibility(state: tauri::State<'_, Mutex<UiState>>) -> MonitorVisibility {
     state
@@ -40,9 +649,29 @@ pub fn get_monitor_visibility(state: tauri::State<'_, Mutex<UiState>>) -> Monito
             cpu: true,
             mem: true,
             net: true,
+            battery: false,
+            temp: false,
+            gpu: false,
         })
 }
 
+/// 按名称（`cpu`/`mem`/`net`/`battery`/`temp`/`gpu`）显示或隐藏单个指标
+#[tauri::command]
+pub fn set_monitor_visibility(
+    app: tauri::AppHandle,
+    item: String,
+    visible: bool,
+) -> Result<(), String> {
+    let item =
+        monitor_item_from_str(&item).ok_or_else(|| format!("invalid monitor item: {}", item))?;
+    let tray = app.try_state::<TrayMenuItems>();
+    if set_monitor_item_visibility(&app, item, visible, tray.as_deref()) {
+        Ok(())
+    } else {
+        Err("at least one of cpu, mem, net, temp and gpu must stay visible".to_string())
+    }
+}
+
 #[tauri::command]
 pub fn get_text_color(state: tauri::State<'_, Mutex<UiState>>) -> String {
     state
@@ -51,6 +680,189 @@ pub fn get_text_color(state: tauri::State<'_, Mutex<UiState>>) -> String {
         .unwrap_or_else(|_| "#ffffff".to_string())
 }
 
+#[tauri::command]
+pub fn set_text_color(app: tauri::AppHandle, color: String) -> Result<(), String> {
+    let color = normalize_hex_color(&color).ok_or_else(|| {
+        format!(
+            "invalid color: {}, expected #rgb, #rrggbb or #rrggbbaa",
+            color
+        )
+    })?;
+    if let Some(tray) = app.try_state::<TrayMenuItems>() {
+        update_text_color(&app, &color, &tray);
+    } else {
+        set_global_text_color(&app, &color);
+    }
+    Ok(())
+}
+
+/// 各指标实际使用的文字颜色，未单独设置的指标为全局颜色
+#[tauri::command]
+pub fn get_metric_colors(state: tauri::State<'_, Mutex<UiState>>) -> MetricColors {
+    state
+        .lock()
+        .map(|ui_state| ui_state.metric_colors.resolved(&ui_state.text_color))
+        .unwrap_or_else(|_| MetricColors::seeded("#ffffff"))
+}
+
+/// 单独设置某个指标的文字颜色，`metric` 为 `cpu`、`mem`、`net` 或 `disk`
+#[tauri::command]
+pub fn set_metric_color(app: tauri::AppHandle, metric: String, hex: String) -> Result<(), String> {
+    let color = normalize_hex_color(&hex).ok_or_else(|| {
+        format!(
+            "invalid color: {}, expected #rgb, #rrggbb or #rrggbbaa",
+            hex
+        )
+    })?;
+    let state = app.state::<Mutex<UiState>>();
+    let mut ui_state = state.lock().map_err(|error| error.to_string())?;
+    let slot = ui_state
+        .metric_colors
+        .slot_mut(&metric)
+        .ok_or_else(|| format!("invalid metric: {}", metric))?;
+    *slot = Some(color);
+    let snapshot = ui_state.clone();
+    drop(ui_state);
+
+    if let Ok(value) = serde_json::to_value(&snapshot.metric_colors) {
+        app.state::<SettingsStore>().set(KEY_METRIC_COLORS, value);
+    }
+    emit_metric_colors(&app, &snapshot);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_opacity(state: tauri::State<'_, Mutex<UiState>>) -> f64 {
+    state.lock().map(|ui_state| ui_state.opacity).unwrap_or(1.0)
+}
+
+#[tauri::command]
+pub fn set_opacity(app: tauri::AppHandle, opacity: f64) -> Result<(), String> {
+    let opacity = clamp_opacity(opacity);
+    if let Some(tray) = app.try_state::<TrayMenuItems>() {
+        update_opacity(&app, opacity, &tray);
+        return Ok(());
+    }
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.opacity = opacity;
+    }
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_OPACITY, opacity);
+    let _ = app.emit("opacity-changed", opacity);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn set_custom_position(app: tauri::AppHandle, x: i32, y: i32) -> Result<(), String> {
+    let position = WindowPosition::Custom { x, y };
+    if let Some(tray) = app.try_state::<TrayMenuItems>() {
+        update_position(&app, position, &tray);
+        return Ok(());
+    }
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.position = position;
+    }
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_POSITION, position_to_str(position));
+    if let Some(window) = app.get_webview_window("main") {
+        apply_window_position(&app, &window, position).map_err(|error| error.to_string())?;
+        if let Some(monitor) = monitor_for_window(&app, &window) {
+            remember_monitor_position(&app, &monitor, position);
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_margin(state: tauri::State<'_, Mutex<UiState>>) -> u32 {
+    state.lock().map(|ui_state| ui_state.margin).unwrap_or(0)
+}
+
+#[tauri::command]
+pub fn set_margin(app: tauri::AppHandle, margin: u32) -> Result<(), String> {
+    let margin = margin.min(MAX_MARGIN);
+    if let Some(tray) = app.try_state::<TrayMenuItems>() {
+        update_margin(&app, margin, &tray);
+        return Ok(());
+    }
+    let position = match app.state::<Mutex<UiState>>().lock() {
+        Ok(mut state) => {
+            state.margin = margin;
+            state.position
+        }
+        Err(_) => WindowPosition::TopLeft,
+    };
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_MARGIN, margin);
+    if let Some(window) = app.get_webview_window("main") {
+        apply_window_position(&app, &window, position).map_err(|error| error.to_string())?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_font_size(state: tauri::State<'_, Mutex<UiState>>) -> u32 {
+    state
+        .lock()
+        .map(|ui_state| ui_state.font_size)
+        .unwrap_or(DEFAULT_FONT_SIZE)
+}
+
+/// 设置文字大小（像素），超出范围时自动限制；自动尺寸随之缩放，窗口会重新调整并贴回角落
+#[tauri::command]
+pub fn set_font_size(app: tauri::AppHandle, size: u32) -> u32 {
+    let size = clamp_font_size(size);
+    if let Some(tray) = app.try_state::<TrayMenuItems>() {
+        update_font_size(&app, size, &tray);
+        return size;
+    }
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.font_size = size;
+    }
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_FONT_SIZE, size);
+    let _ = app.emit("font-size-changed", size);
+    if let Some(window) = app.get_webview_window("main") {
+        apply_layout_and_position(&app, &window);
+    }
+    size
+}
+
+#[tauri::command]
+pub fn get_monitoring_paused(monitor: tauri::State<'_, Mutex<Monitor>>) -> Result<bool, String> {
+    monitor
+        .lock()
+        .map(|state| state.is_paused())
+        .map_err(|_| "monitor lock poisoned".to_string())
+}
+
+#[tauri::command]
+pub fn set_monitoring_paused(app: tauri::AppHandle, paused: bool) -> Result<(), String> {
+    if let Some(tray) = app.try_state::<TrayMenuItems>() {
+        update_monitoring_paused(&app, paused, &tray);
+        return Ok(());
+    }
+    let monitor = app.state::<Mutex<Monitor>>();
+    let monitor = monitor
+        .lock()
+        .map_err(|_| "monitor lock poisoned".to_string())?;
+    if paused {
+        monitor.pause();
+    } else {
+        monitor.resume();
+    }
+    let _ = app.emit("monitoring-paused-changed", paused);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_click_through(state: tauri::State<'_, Mutex<UiState>>) -> bool {
+    state
+        .lock()
+        .map(|ui_state| ui_state.click_through)
+        .unwrap_or(false)
+}
+
 #[tauri::command]
 pub fn snap_window(app: tauri::AppHandle) -> Result<(), String> {
     if let Some(window) = app.get_webview_window("main") {
@@ -59,6 +871,298 @@ pub fn snap_window(app: tauri::AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+/// 由前端在鼠标按下时调用，交给系统开始拖动窗口
+#[tauri::command]
+pub fn enable_drag(app: tauri::AppHandle) -> Result<(), String> {
+    if let Some(window) = app.get_webview_window("main") {
+        begin_window_drag(&window).map_err(|error| error.to_string())?;
+    }
+    Ok(())
+}
+
+/// 由前端在拖动结束（鼠标松开）时调用，按 `lock_to_corner` 吸附或保存当前位置
+#[tauri::command]
+pub fn finish_drag(app: tauri::AppHandle) -> Result<(), String> {
+    if let Some(window) = app.get_webview_window("main") {
+        finish_window_drag(&app, &window).map_err(|error| error.to_string())?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_lock_to_corner(state: tauri::State<'_, Mutex<UiState>>) -> bool {
+    state
+        .lock()
+        .map(|ui_state| ui_state.lock_to_corner)
+        .unwrap_or(true)
+}
+
+#[tauri::command]
+pub fn set_lock_to_corner(app: tauri::AppHandle, locked: bool) {
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.lock_to_corner = locked;
+    }
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_LOCK_TO_CORNER, locked);
+}
+
+#[tauri::command]
+pub fn get_snap_mode(state: tauri::State<'_, Mutex<UiState>>) -> String {
+    let mode = state
+        .lock()
+        .map(|ui_state| ui_state.snap_mode)
+        .unwrap_or(SnapMode::Both);
+    snap_mode_to_str(mode).to_string()
+}
+
+/// 设置吸附时考虑的停靠点：`corners`、`edges` 或 `both`
+#[tauri::command]
+pub fn set_snap_mode(app: tauri::AppHandle, mode: String) -> Result<(), String> {
+    let mode = snap_mode_from_str(&mode).ok_or_else(|| format!("invalid snap mode: {}", mode))?;
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.snap_mode = mode;
+    }
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_SNAP_MODE, snap_mode_to_str(mode));
+    Ok(())
+}
+
+#[tauri::command]
+pub fn move_to_next_monitor(app: tauri::AppHandle) -> Result<(), String> {
+    if let Some(tray) = app.try_state::<TrayMenuItems>() {
+        select_next_monitor(&app, &tray);
+        return Ok(());
+    }
+    let Some(window) = app.get_webview_window("main") else {
+        return Ok(());
+    };
+    let monitors = app.available_monitors().map_err(|error| error.to_string())?;
+    if monitors.len() < 2 {
+        return Ok(());
+    }
+    let current_index = selected_monitor(&app)
+        .or_else(|| monitor_for_window(&app, &window))
+        .and_then(|monitor| monitor_target_from_monitor(&app, &monitor))
+        .map(|target| target.index)
+        .unwrap_or(0);
+    let next_index = (current_index + 1) % monitors.len();
+    move_to_monitor(&app, &window, next_index, &monitors[next_index])
+}
+
+/// 列出所有显示器，供前端选择目标显示器
+#[tauri::command]
+pub fn list_monitors(app: tauri::AppHandle) -> Result<Vec<MonitorSummary>, String> {
+    monitor_summaries(&app).map_err(|error| error.to_string())
+}
+
+/// 切换到 `list_monitors` 中序号为 `index` 的显示器，窗口按当前位置设置移过去
+#[tauri::command]
+pub fn set_monitor_target(app: tauri::AppHandle, index: usize) -> Result<(), String> {
+    let monitors = app
+        .available_monitors()
+        .map_err(|error| error.to_string())?;
+    let Some(monitor) = monitors.get(index) else {
+        return Err(format!(
+            "invalid monitor index: {}, {} monitors available",
+            index,
+            monitors.len()
+        ));
+    };
+    if let Some(tray) = app.try_state::<TrayMenuItems>() {
+        update_monitor_target(&app, index, &tray);
+        return Ok(());
+    }
+    let Some(window) = app.get_webview_window("main") else {
+        return Ok(());
+    };
+    move_to_monitor(&app, &window, index, monitor)
+}
+
+/// 托盘尚未创建时切换显示器：更新并保存目标显示器，再把窗口移过去
+fn move_to_monitor(
+    app: &tauri::AppHandle,
+    window: &tauri::WebviewWindow,
+    index: usize,
+    monitor: &tauri::Monitor,
+) -> Result<(), String> {
+    let target = monitor_target_for_monitor(index, monitor);
+    let position = match app.state::<Mutex<UiState>>().lock() {
+        Ok(mut state) => {
+            state.monitor_target = Some(target.clone());
+            state.position
+        }
+        Err(_) => WindowPosition::TopLeft,
+    };
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_MONITOR_TARGET, monitor_target_to_str(&target));
+
+    let target_pos = calculate_window_position_on_monitor(app, window, position, monitor)
+        .map_err(|error| error.to_string())?;
+    window
+        .set_position(target_pos)
+        .map_err(|error| error.to_string())
+}
+
+/// 恢复所有界面设置的默认值
+#[tauri::command]
+pub fn reset_settings(app: tauri::AppHandle) {
+    reset_ui_state(&app);
+}
+
+/// 导出当前设置，内容与 `ui-settings.json` 一致
+#[tauri::command]
+pub fn export_settings(store: tauri::State<'_, SettingsStore>) -> Result<String, String> {
+    let settings: serde_json::Map<String, serde_json::Value> =
+        store.entries().into_iter().collect();
+    serde_json::to_string_pretty(&settings).map_err(|error| error.to_string())
+}
+
+/// 导入 `export_settings` 导出的设置，校验失败时不做任何修改
+#[tauri::command]
+pub fn import_settings(app: tauri::AppHandle, json: String) -> Result<(), String> {
+    let value: serde_json::Value =
+        serde_json::from_str(&json).map_err(|error| error.to_string())?;
+    let current = app
+        .state::<Mutex<UiState>>()
+        .lock()
+        .map(|state| state.clone())
+        .map_err(|error| error.to_string())?;
+    let next = import_ui_state(&current, &value)?;
+
+    let store = app.state::<SettingsStore>();
+    if value.get(KEY_REFRESH_INTERVAL).is_some() {
+        store.set(KEY_REFRESH_INTERVAL, next.intervals.cpu_ms);
+    } else if value.get(KEY_MONITOR_INTERVALS).is_some() {
+        store.delete(KEY_REFRESH_INTERVAL);
+    }
+    apply_ui_state(&app, &next);
+    Ok(())
+}
+
+/// 把当前的位置、布局、颜色和指标显示状态保存为名为 `name` 的配置方案，同名时覆盖
+#[tauri::command]
+pub fn save_profile(app: tauri::AppHandle, name: String) -> Result<(), String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("profile name must not be empty".to_string());
+    }
+    let profile = app
+        .state::<Mutex<UiState>>()
+        .lock()
+        .map(|state| profile_from_state(&state))
+        .map_err(|error| error.to_string())?;
+    let store = app.state::<SettingsStore>();
+    let mut profiles = profiles_from_value(store.get(KEY_PROFILES));
+    profiles.insert(name.to_string(), profile);
+    store.set(KEY_PROFILES, serde_json::Value::Object(profiles));
+    Ok(())
+}
+
+/// 应用保存的配置方案，与导入设置一样同步托盘、窗口和设置文件
+#[tauri::command]
+pub fn apply_profile(app: tauri::AppHandle, name: String) -> Result<(), String> {
+    let store = app.state::<SettingsStore>();
+    let profiles = profiles_from_value(store.get(KEY_PROFILES));
+    let profile = profiles
+        .get(name.trim())
+        .ok_or_else(|| format!("profile not found: {}", name))?;
+    let current = app
+        .state::<Mutex<UiState>>()
+        .lock()
+        .map(|state| state.clone())
+        .map_err(|error| error.to_string())?;
+    let mut next = import_ui_state(&current, profile)?;
+    // 当前显示器记录的位置优先于全局位置，一并改为方案中的位置
+    if let Some(target) = app
+        .get_webview_window("main")
+        .and_then(|window| monitor_for_window(&app, &window))
+        .and_then(|monitor| monitor_target_from_monitor(&app, &monitor))
+    {
+        next.monitor_positions
+            .insert(monitor_position_key(&target), next.position);
+    }
+    apply_ui_state(&app, &next);
+    Ok(())
+}
+
+/// 按名称排序的配置方案列表
+#[tauri::command]
+pub fn list_profiles(store: tauri::State<'_, SettingsStore>) -> Vec<String> {
+    let mut names: Vec<String> = profiles_from_value(store.get(KEY_PROFILES))
+        .keys()
+        .cloned()
+        .collect();
+    names.sort();
+    names
+}
+
+/// 切换悬浮窗的显示状态，返回切换后是否可见
+#[tauri::command]
+pub fn toggle_window_visibility(app: tauri::AppHandle) -> Result<bool, String> {
+    let visible = !app
+        .state::<Mutex<UiState>>()
+        .lock()
+        .map(|state| state.visible)
+        .unwrap_or(true);
+    if let Some(tray) = app.try_state::<TrayMenuItems>() {
+        update_window_visible(&app, visible, &tray);
+        return Ok(visible);
+    }
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.visible = visible;
+    }
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_WINDOW_VISIBLE, visible);
+    if let Some(window) = app.get_webview_window("main") {
+        let result = if visible { window.show() } else { window.hide() };
+        result.map_err(|error| error.to_string())?;
+    }
+    Ok(visible)
+}
+
+/// 打开设置窗口，已经打开时聚焦到该窗口；关闭设置窗口不会退出应用
+#[tauri::command]
+pub fn open_settings_window(app: tauri::AppHandle) -> Result<(), String> {
+    show_settings_window(&app).map_err(|error| error.to_string())?;
+    if let Some(tray) = app.try_state::<TrayMenuItems>() {
+        tray.set_settings_window(true);
+    }
+    Ok(())
+}
+
+/// 强制把窗口移回主显示器上配置的位置，窗口跑到屏幕外时用于手动恢复
+#[tauri::command]
+pub fn recenter_window(app: tauri::AppHandle) -> Result<(), String> {
+    let Some(window) = app.get_webview_window("main") else {
+        return Ok(());
+    };
+    let primary = app
+        .primary_monitor()
+        .map_err(|error| error.to_string())?
+        .ok_or_else(|| "no primary monitor".to_string())?;
+    let target = monitor_target_from_monitor(&app, &primary);
+    let position = match app.state::<Mutex<UiState>>().lock() {
+        Ok(mut state) => {
+            state.monitor_target = target.clone();
+            state.position
+        }
+        Err(_) => WindowPosition::TopLeft,
+    };
+    if let Some(target) = target {
+        let store = app.state::<SettingsStore>();
+        store.set(KEY_MONITOR_TARGET, monitor_target_to_str(&target));
+        if let Some(tray) = app.try_state::<TrayMenuItems>() {
+            tray.set_monitor_target(Some(&target));
+        }
+    }
+
+    let target_pos = calculate_window_position_on_monitor(&app, &window, position, &primary)
+        .map_err(|error| error.to_string())?;
+    window
+        .set_position(target_pos)
+        .map_err(|error| error.to_string())
+}
+
 #[tauri::command]
 pub fn toggle_layout(app: tauri::AppHandle) -> Result<(), String> {
     let current_layout = app
@@ -70,8 +1174,70 @@ pub fn toggle_layout(app: tauri::AppHandle) -> Result<(), String> {
         Layout::Horizontal => Layout::Vertical,
         Layout::Vertical => Layout::Horizontal,
     };
+    apply_layout(&app, next_layout)
+}
+
+/// 设置指定布局的窗口尺寸（逻辑像素），超出范围时自动限制
+#[tauri::command]
+pub fn set_window_size(
+    app: tauri::AppHandle,
+    layout: String,
+    width: f64,
+    height: f64,
+) -> Result<(), String> {
+    let layout = layout_from_str(&layout).ok_or_else(|| format!("invalid layout: {}", layout))?;
+    let size = clamp_window_size(width, height);
+    let mut current = None;
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.set_window_size(layout, Some(size));
+        current = Some(state.layout);
+    }
+    let store = app.state::<SettingsStore>();
+    let key = match layout {
+        Layout::Horizontal => KEY_SIZE_HORIZONTAL,
+        Layout::Vertical => KEY_SIZE_VERTICAL,
+    };
+    store.set(key, window_size_to_value(Some(size)));
+    if current == Some(layout) {
+        if let Some(window) = app.get_webview_window("main") {
+            apply_layout_and_position(&app, &window);
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn set_layout(app: tauri::AppHandle, layout: String) -> Result<(), String> {
+    let layout = layout_from_str(&layout).ok_or_else(|| format!("invalid layout: {}", layout))?;
+    apply_layout(&app, layout)
+}
+
+#[tauri::command]
+pub fn set_position(app: tauri::AppHandle, position: String) -> Result<(), String> {
+    let position =
+        position_from_str(&position).ok_or_else(|| format!("invalid position: {}", position))?;
     if let Some(tray) = app.try_state::<TrayMenuItems>() {
-        update_layout(&app, next_layout, &tray);
+        update_position(&app, position, &tray);
+        return Ok(());
+    }
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.position = position;
+    }
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_POSITION, position_to_str(position));
+    if let Some(window) = app.get_webview_window("main") {
+        apply_window_position(&app, &window, position).map_err(|error| error.to_string())?;
+        if let Some(monitor) = monitor_for_window(&app, &window) {
+            remember_monitor_position(&app, &monitor, position);
+        }
+    }
+    Ok(())
+}
+
+/// 切换布局，有托盘时复用托盘的更新逻辑以保持勾选状态同步
+fn apply_layout(app: &tauri::AppHandle, next_layout: Layout) -> Result<(), String> {
+    if let Some(tray) = app.try_state::<TrayMenuItems>() {
+        update_layout(app, next_layout, &tray);
         return Ok(());
     }
     let mut changed = true;
@@ -87,22 +1253,18 @@ pub fn toggle_layout(app: tauri::AppHandle) -> Result<(), String> {
         return Ok(());
     }
     if let Some(window) = app.get_webview_window("main") {
-        let target = match next_layout {
-            Layout::Horizontal => SIZE_HORIZONTAL,
-            Layout::Vertical => SIZE_VERTICAL,
-        };
-        let _ = window.set_size(target);
+        let _ = window.set_size(layout_size(app, next_layout));
         let position = match app.state::<Mutex<UiState>>().lock() {
             Ok(state) => state.position,
             Err(_) => WindowPosition::TopLeft,
         };
-        if let Some(monitor) = monitor_for_window(&app, &window) {
+        if let Some(monitor) = monitor_for_window(app, &window) {
             if let Ok(target_pos) =
-                calculate_window_position_on_monitor(&app, &window, position, &monitor)
+                calculate_window_position_on_monitor(app, &window, position, &monitor)
             {
                 let _ = window.set_position(target_pos);
             }
-            let monitor_target = crate::state::monitor_target_from_monitor(&app, &monitor);
+            let monitor_target = crate::state::monitor_target_from_monitor(app, &monitor);
             if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
                 state.monitor_target = monitor_target.clone();
             }
@@ -110,7 +1272,7 @@ pub fn toggle_layout(app: tauri::AppHandle) -> Result<(), String> {
                 store.set(KEY_MONITOR_TARGET, crate::state::monitor_target_to_str(&target));
             }
         } else {
-            let _ = apply_window_position(&app, &window, position);
+            let _ = apply_window_position(app, &window, position);
         }
     }
     Ok(())
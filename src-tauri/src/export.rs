@@ -0,0 +1,196 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::monitor::{EnabledMetrics, SystemInfo};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Json,
+    Csv,
+}
+
+pub fn export_format_from_str(value: &str) -> Option<ExportFormat> {
+    match value.to_ascii_lowercase().as_str() {
+        "json" => Some(ExportFormat::Json),
+        "csv" => Some(ExportFormat::Csv),
+        _ => None,
+    }
+}
+
+pub fn export<T: Serialize>(value: &T, format: ExportFormat) -> Result<String, String> {
+    match format {
+        ExportFormat::Json => {
+            serde_json::to_string_pretty(value).map_err(|error| error.to_string())
+        }
+        ExportFormat::Csv => {
+            let value = serde_json::to_value(value).map_err(|error| error.to_string())?;
+            let (header, row) = to_csv_row(&value);
+            Ok(format!("{}\n{}\n", header, row))
+        }
+    }
+}
+
+/// 调试和分享用的指标快照，附带足够的上下文以便单独查看
+#[derive(Debug, Serialize)]
+pub struct Snapshot<'a> {
+    /// 应用版本
+    pub app_version: String,
+    /// 导出时启用的指标，未启用的指标保留的是最后一次采集的数据
+    pub enabled: EnabledMetrics,
+    /// 系统信息，其中 `timestamp` 即导出时间
+    pub system: &'a SystemInfo,
+}
+
+/// 将快照序列化为格式化的 JSON
+pub fn snapshot_json(snapshot: &Snapshot) -> Result<String, String> {
+    serde_json::to_string_pretty(snapshot).map_err(|error| error.to_string())
+}
+
+/// 将嵌套结构展开为一行 CSV，返回 (表头, 数据)
+///
+/// 对象字段以 `.` 连接（如 `cpu.total_usage`），数组元素以下标连接（如 `disk.disks.0.name`），
+/// `null` 输出为空值。
+pub fn to_csv_row(value: &Value) -> (String, String) {
+    let mut columns = Vec::new();
+    flatten("", value, &mut columns);
+    let header = columns
+        .iter()
+        .map(|(key, _)| escape_csv(key))
+        .collect::<Vec<_>>()
+        .join(",");
+    let row = columns
+        .iter()
+        .map(|(_, value)| escape_csv(value))
+        .collect::<Vec<_>>()
+        .join(",");
+    (header, row)
+}
+
+/// 按字段路径（与 `to_csv_row` 的表头相同，如 `cpu.total_usage`）取出一行 CSV，
+/// 不存在的字段输出为空值
+pub fn csv_fields<S: AsRef<str>>(value: &Value, fields: &[S]) -> String {
+    fields
+        .iter()
+        .map(|field| {
+            let pointer = format!("/{}", field.as_ref().replace('.', "/"));
+            match value.pointer(&pointer) {
+                None | Some(Value::Null) => String::new(),
+                Some(Value::String(text)) => escape_csv(text),
+                Some(other) => escape_csv(&other.to_string()),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn flatten(prefix: &str, value: &Value, columns: &mut Vec<(String, String)>) {
+    let key = |name: &str| {
+        if prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", prefix, name)
+        }
+    };
+    match value {
+        Value::Object(map) => {
+            for (name, child) in map {
+                flatten(&key(name), child, columns);
+            }
+        }
+        Value::Array(items) => {
+            for (index, child) in items.iter().enumerate() {
+                flatten(&key(&index.to_string()), child, columns);
+            }
+        }
+        Value::Null => columns.push((prefix.to_string(), String::new())),
+        Value::String(text) => columns.push((prefix.to_string(), text.clone())),
+        other => columns.push((prefix.to_string(), other.to_string())),
+    }
+}
+
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_flatten() {
+        let value = json!({
+            "cpu": { "total_usage": 12.5, "temperature": null },
+            "memory": { "used": 1024 },
+            "disks": [{ "name": "sda" }],
+        });
+        let mut columns = Vec::new();
+        flatten("", &value, &mut columns);
+        let columns: BTreeMap<_, _> = columns.into_iter().collect();
+        let expected: BTreeMap<_, _> = [
+            ("cpu.total_usage", "12.5"),
+            ("cpu.temperature", ""),
+            ("memory.used", "1024"),
+            ("disks.0.name", "sda"),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+        assert_eq!(columns, expected);
+    }
+
+    #[test]
+    fn test_to_csv_row_escapes_fields() {
+        let (header, row) = to_csv_row(&json!({ "name": "a,b", "label": "say \"hi\"" }));
+        let mut keys: Vec<_> = header.split(',').collect();
+        keys.sort();
+        assert_eq!(keys, vec!["label", "name"]);
+        assert!(row.contains("\"a,b\""));
+        assert!(row.contains("\"say \"\"hi\"\"\""));
+    }
+
+    #[test]
+    fn test_snapshot_is_self_describing() {
+        let info = SystemInfo::default();
+        let json = snapshot_json(&Snapshot {
+            app_version: "1.2.3".to_string(),
+            enabled: EnabledMetrics::default(),
+            system: &info,
+        })
+        .unwrap();
+        let value: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["app_version"], "1.2.3");
+        assert_eq!(value["enabled"]["cpu"], true);
+        assert!(value["system"]["timestamp"].is_u64());
+    }
+
+    #[test]
+    fn test_csv_fields() {
+        let value = json!({
+            "cpu": { "total_usage": 12.5, "temperature": null },
+            "disks": [{ "name": "a,b" }],
+        });
+        let row = csv_fields(
+            &value,
+            &[
+                "cpu.total_usage",
+                "cpu.temperature",
+                "disks.0.name",
+                "missing",
+            ],
+        );
+        assert_eq!(row, "12.5,,\"a,b\",");
+    }
+
+    #[test]
+    fn test_export_format_from_str() {
+        assert_eq!(export_format_from_str("JSON"), Some(ExportFormat::Json));
+        assert_eq!(export_format_from_str("csv"), Some(ExportFormat::Csv));
+        assert_eq!(export_format_from_str("xml"), None);
+    }
+}
@@ -0,0 +1,145 @@
+//! 数值格式化
+//!
+//! 托盘提示和前端共用同一套格式，保证两处显示的字符串一致。字节可按 1024 进位
+//! （KiB/MiB/GiB/TiB）或按 1000 进位（KB/MB/GB/TB），保留一位小数；网络速率
+//! 也可以按比特显示（Kb/s、Mb/s 等）。
+
+/// 字节单位的进制
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnitBase {
+    /// 按 1024 进位，单位为 KiB、MiB 等
+    Binary,
+    /// 按 1000 进位，单位为 KB、MB 等
+    Decimal,
+}
+
+impl UnitBase {
+    fn step(self) -> u64 {
+        match self {
+            UnitBase::Binary => 1024,
+            UnitBase::Decimal => 1000,
+        }
+    }
+
+    fn units(self) -> [&'static str; 4] {
+        match self {
+            UnitBase::Binary => ["KiB", "MiB", "GiB", "TiB"],
+            UnitBase::Decimal => ["KB", "MB", "GB", "TB"],
+        }
+    }
+
+    fn bit_units(self) -> [&'static str; 4] {
+        match self {
+            UnitBase::Binary => ["Kib", "Mib", "Gib", "Tib"],
+            UnitBase::Decimal => ["Kb", "Mb", "Gb", "Tb"],
+        }
+    }
+}
+
+pub fn unit_base_from_str(value: &str) -> Option<UnitBase> {
+    match value.to_ascii_lowercase().as_str() {
+        "binary" => Some(UnitBase::Binary),
+        "decimal" => Some(UnitBase::Decimal),
+        _ => None,
+    }
+}
+
+/// 格式化字节数，如 `512 B`、`1.5 MiB`、`1.2 MB`
+pub fn format_bytes(bytes: u64, base: UnitBase) -> String {
+    format_scaled(bytes, base.step(), "B", base.units())
+}
+
+/// 按 `step` 进位并选择单位，不足一个 `step` 时以 `base_unit` 显示整数
+fn format_scaled(amount: u64, step: u64, base_unit: &str, units: [&str; 4]) -> String {
+    if amount < step {
+        return format!("{} {}", amount, base_unit);
+    }
+    let step = step as f64;
+    let mut value = amount as f64 / step;
+    let mut unit = 0;
+    // 按保留一位小数后的值进位，避免出现 `1024.0 KiB`，前端 `formatScaled` 与此一致
+    while (value * 10.0).round() >= step * 10.0 && unit < units.len() - 1 {
+        value /= step;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, units[unit])
+}
+
+/// 格式化每秒字节数，如 `850.0 KB/s`、`1.5 MiB/s`
+pub fn format_speed(bytes_per_sec: u64, base: UnitBase) -> String {
+    format!("{}/s", format_bytes(bytes_per_sec, base))
+}
+
+/// 按比特格式化每秒字节数，如 `800 b/s`、`12.0 Mb/s`
+///
+/// 比特速率习惯按 1000 进位，托盘和前端都使用 [`UnitBase::Decimal`]。
+pub fn format_bit_speed(bytes_per_sec: u64, base: UnitBase) -> String {
+    let bits = bytes_per_sec.saturating_mul(8);
+    let bits = format_scaled(bits, base.step(), "b", base.bit_units());
+    format!("{}/s", bits)
+}
+
+/// 格式化百分比，保留两位小数，如 `12.50%`
+pub fn format_percent(value: f32) -> String {
+    format!("{:.2}%", value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        let binary = |bytes| format_bytes(bytes, UnitBase::Binary);
+        assert_eq!(binary(0), "0 B");
+        assert_eq!(binary(1023), "1023 B");
+        assert_eq!(binary(1024), "1.0 KiB");
+        assert_eq!(binary(1536), "1.5 KiB");
+        assert_eq!(binary(1024 * 1024 - 1), "1.0 MiB");
+        assert_eq!(binary(1024 * 1024), "1.0 MiB");
+        assert_eq!(binary(16 * 1024 * 1024 * 1024), "16.0 GiB");
+        assert_eq!(binary(u64::MAX), "16777216.0 TiB");
+
+        let decimal = |bytes| format_bytes(bytes, UnitBase::Decimal);
+        assert_eq!(decimal(999), "999 B");
+        assert_eq!(decimal(1000), "1.0 KB");
+        assert_eq!(decimal(1024), "1.0 KB");
+        assert_eq!(decimal(999_999), "1.0 MB");
+        assert_eq!(decimal(1_200_000), "1.2 MB");
+    }
+
+    #[test]
+    fn test_format_speed_and_percent() {
+        assert_eq!(format_speed(0, UnitBase::Binary), "0 B/s");
+        assert_eq!(format_speed(0, UnitBase::Decimal), "0 B/s");
+        assert_eq!(format_speed(999, UnitBase::Binary), "999 B/s");
+        assert_eq!(format_speed(999, UnitBase::Decimal), "999 B/s");
+        assert_eq!(format_speed(1024, UnitBase::Binary), "1.0 KiB/s");
+        assert_eq!(format_speed(1024, UnitBase::Decimal), "1.0 KB/s");
+        assert_eq!(format_speed(850_000, UnitBase::Decimal), "850.0 KB/s");
+        assert_eq!(format_speed(2048, UnitBase::Binary), "2.0 KiB/s");
+        assert_eq!(format_percent(0.0), "0.00%");
+        assert_eq!(format_percent(12.5), "12.50%");
+        assert_eq!(format_percent(100.0), "100.00%");
+    }
+
+    #[test]
+    fn test_format_bit_speed() {
+        assert_eq!(format_bit_speed(0, UnitBase::Decimal), "0 b/s");
+        assert_eq!(format_bit_speed(100, UnitBase::Decimal), "800 b/s");
+        assert_eq!(format_bit_speed(125, UnitBase::Decimal), "1.0 Kb/s");
+        assert_eq!(format_bit_speed(1_500_000, UnitBase::Decimal), "12.0 Mb/s");
+        assert_eq!(format_bit_speed(128, UnitBase::Binary), "1.0 Kib/s");
+        assert_eq!(
+            format_bit_speed(u64::MAX, UnitBase::Decimal),
+            "18446744.1 Tb/s"
+        );
+    }
+
+    #[test]
+    fn test_unit_base_from_str() {
+        assert_eq!(unit_base_from_str("binary"), Some(UnitBase::Binary));
+        assert_eq!(unit_base_from_str("Decimal"), Some(UnitBase::Decimal));
+        assert_eq!(unit_base_from_str("bits"), None);
+    }
+}
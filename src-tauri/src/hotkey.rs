@@ -0,0 +1,66 @@
+//! 全局快捷键
+//!
+//! 快捷键格式与 `tauri-plugin-global-shortcut` 一致，如 `ctrl+alt+m`、`CmdOrCtrl+Shift+L`。
+
+use std::sync::Mutex;
+
+use tauri::plugin::TauriPlugin;
+use tauri::{Manager, Wry};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+
+use crate::commands::{snap_window, toggle_layout};
+use crate::state::UiState;
+
+pub fn parse_hotkey(combo: &str) -> Result<Shortcut, String> {
+    combo
+        .parse::<Shortcut>()
+        .map_err(|error| format!("invalid hotkey {}: {}", combo, error))
+}
+
+/// 注册快捷键，已被占用时返回错误
+pub fn register_hotkey(app: &tauri::AppHandle, combo: &str) -> Result<(), String> {
+    let shortcut = parse_hotkey(combo)?;
+    app.global_shortcut()
+        .register(shortcut)
+        .map_err(|error| format!("failed to register hotkey {}: {}", combo, error))
+}
+
+/// 把快捷键从 `previous` 换成 `next`，注册失败时恢复原来的快捷键
+pub fn rebind_hotkey(app: &tauri::AppHandle, previous: &str, next: &str) -> Result<(), String> {
+    let shortcut = parse_hotkey(next)?;
+    if let Ok(previous_shortcut) = parse_hotkey(previous) {
+        if previous_shortcut == shortcut {
+            return Ok(());
+        }
+        let _ = app.global_shortcut().unregister(previous_shortcut);
+    }
+    if let Err(error) = app.global_shortcut().register(shortcut) {
+        let _ = register_hotkey(app, previous);
+        return Err(format!("failed to register hotkey {}: {}", next, error));
+    }
+    Ok(())
+}
+
+/// 快捷键插件，分别处理切换布局和吸附到最近角落的快捷键
+pub fn plugin() -> TauriPlugin<Wry> {
+    tauri_plugin_global_shortcut::Builder::new()
+        .with_handler(handle_shortcut)
+        .build()
+}
+
+fn handle_shortcut(app: &tauri::AppHandle, shortcut: &Shortcut, event: ShortcutEvent) {
+    if event.state() != ShortcutState::Pressed {
+        return;
+    }
+    let (hotkey, snap_hotkey) = app
+        .state::<Mutex<UiState>>()
+        .lock()
+        .map(|state| (state.hotkey.clone(), state.snap_hotkey.clone()))
+        .unwrap_or_default();
+    let matches = |combo: &str| parse_hotkey(combo).is_ok_and(|bound| bound == *shortcut);
+    if matches(&hotkey) {
+        let _ = toggle_layout(app.clone());
+    } else if matches(&snap_hotkey) {
+        let _ = snap_window(app.clone());
+    }
+}
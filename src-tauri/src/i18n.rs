@@ -0,0 +1,179 @@
+//! 托盘菜单文案的多语言支持
+//!
+//! 文案以菜单项 id 为键，子菜单使用 `menu_*` 作为键。
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Lang {
+    Zh,
+    En,
+}
+
+const ZH: &[(&str, &str)] = &[
+    ("menu_position", "位置"),
+    ("menu_margin", "边距"),
+    ("menu_layout", "布局"),
+    ("menu_color", "颜色"),
+    ("menu_opacity", "透明度"),
+    ("menu_font_size", "文字大小"),
+    ("menu_monitor", "监控"),
+    ("menu_net_unit", "网速单位"),
+    ("menu_temp_unit", "温度单位"),
+    ("menu_refresh_interval", "刷新间隔"),
+    ("menu_display", "显示器"),
+    ("display_next", "下一个显示器"),
+    ("autostart", "开机启动"),
+    ("window_visible", "显示/隐藏"),
+    ("settings_window", "设置"),
+    ("always_on_top", "置顶"),
+    ("click_through", "鼠标穿透"),
+    ("pos_top_left", "左上"),
+    ("pos_bottom_left", "左下"),
+    ("pos_top_right", "右上"),
+    ("pos_bottom_right", "右下"),
+    ("pos_top_center", "上中"),
+    ("pos_bottom_center", "下中"),
+    ("pos_left_center", "左中"),
+    ("pos_right_center", "右中"),
+    ("layout_horizontal", "水平"),
+    ("layout_vertical", "垂直"),
+    ("color_white", "白色"),
+    ("color_black", "黑色"),
+    ("color_cyan", "青色"),
+    ("color_green", "绿色"),
+    ("color_orange", "橙色"),
+    ("color_pink", "粉色"),
+    ("color_yellow", "黄色"),
+    ("monitor_battery", "电池"),
+    ("monitor_temp", "温度"),
+    ("monitoring_paused", "暂停监控"),
+    ("tray_dynamic_icon", "图标显示 CPU"),
+    ("notifications", "告警通知"),
+    ("reset_settings", "恢复默认"),
+    ("quit", "退出"),
+];
+
+const EN: &[(&str, &str)] = &[
+    ("menu_position", "Position"),
+    ("menu_margin", "Margin"),
+    ("menu_layout", "Layout"),
+    ("menu_color", "Color"),
+    ("menu_opacity", "Opacity"),
+    ("menu_font_size", "Font Size"),
+    ("menu_monitor", "Metrics"),
+    ("menu_net_unit", "Network Unit"),
+    ("menu_temp_unit", "Temperature Unit"),
+    ("menu_refresh_interval", "Refresh Interval"),
+    ("menu_display", "Display"),
+    ("display_next", "Next Display"),
+    ("autostart", "Launch at Login"),
+    ("window_visible", "Show Widget"),
+    ("settings_window", "Settings"),
+    ("always_on_top", "Always on Top"),
+    ("click_through", "Click Through"),
+    ("pos_top_left", "Top Left"),
+    ("pos_bottom_left", "Bottom Left"),
+    ("pos_top_right", "Top Right"),
+    ("pos_bottom_right", "Bottom Right"),
+    ("pos_top_center", "Top Center"),
+    ("pos_bottom_center", "Bottom Center"),
+    ("pos_left_center", "Left Center"),
+    ("pos_right_center", "Right Center"),
+    ("layout_horizontal", "Horizontal"),
+    ("layout_vertical", "Vertical"),
+    ("color_white", "White"),
+    ("color_black", "Black"),
+    ("color_cyan", "Cyan"),
+    ("color_green", "Green"),
+    ("color_orange", "Orange"),
+    ("color_pink", "Pink"),
+    ("color_yellow", "Yellow"),
+    ("monitor_battery", "Battery"),
+    ("monitor_temp", "Temp"),
+    ("monitoring_paused", "Pause Monitoring"),
+    ("tray_dynamic_icon", "CPU in Tray Icon"),
+    ("notifications", "Alert Notifications"),
+    ("reset_settings", "Reset to Defaults"),
+    ("quit", "Quit"),
+];
+
+pub fn lang_to_str(lang: Lang) -> &'static str {
+    match lang {
+        Lang::Zh => "zh",
+        Lang::En => "en",
+    }
+}
+
+pub fn lang_from_str(value: &str) -> Option<Lang> {
+    match value {
+        "zh" => Some(Lang::Zh),
+        "en" => Some(Lang::En),
+        _ => None,
+    }
+}
+
+/// 根据 locale 字符串（如 `zh_CN.UTF-8`、`en-US`）选择语言，非中文一律使用英文
+pub fn lang_from_locale(locale: &str) -> Lang {
+    if locale.to_ascii_lowercase().starts_with("zh") {
+        Lang::Zh
+    } else {
+        Lang::En
+    }
+}
+
+/// 从 `LC_ALL`、`LC_MESSAGES`、`LANG` 环境变量检测系统语言
+///
+/// 都未设置时（Windows、从 Finder 启动的 macOS 应用）保持原有的中文界面。
+pub fn detect_lang() -> Lang {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty() && value != "C" && value != "POSIX")
+        .map(|value| lang_from_locale(&value))
+        .unwrap_or(Lang::Zh)
+}
+
+/// 查找菜单文案，当前语言缺失时退回中文，仍然缺失时直接使用键
+pub fn label(key: &'static str, lang: Lang) -> &'static str {
+    let table = match lang {
+        Lang::Zh => ZH,
+        Lang::En => EN,
+    };
+    lookup(table, key)
+        .or_else(|| lookup(ZH, key))
+        .unwrap_or(key)
+}
+
+fn lookup(table: &[(&str, &'static str)], key: &str) -> Option<&'static str> {
+    table
+        .iter()
+        .find(|(name, _)| *name == key)
+        .map(|(_, text)| *text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_label() {
+        assert_eq!(label("quit", Lang::Zh), "退出");
+        assert_eq!(label("quit", Lang::En), "Quit");
+        assert_eq!(label("unknown", Lang::En), "unknown");
+    }
+
+    #[test]
+    fn test_tables_cover_same_keys() {
+        for (key, _) in ZH {
+            assert!(lookup(EN, key).is_some(), "missing en label: {}", key);
+        }
+        assert_eq!(ZH.len(), EN.len());
+    }
+
+    #[test]
+    fn test_lang_from_locale() {
+        assert_eq!(lang_from_locale("zh_CN.UTF-8"), Lang::Zh);
+        assert_eq!(lang_from_locale("zh-Hant"), Lang::Zh);
+        assert_eq!(lang_from_locale("en_US.UTF-8"), Lang::En);
+        assert_eq!(lang_from_locale("de_DE"), Lang::En);
+    }
+}
@@ -1,32 +1,82 @@
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
+mod cli;
 mod commands;
+mod export;
+mod format;
+mod hotkey;
+mod i18n;
+mod logger;
 mod monitor;
+mod notify;
+#[cfg(feature = "prometheus")]
+mod prometheus;
 mod state;
 mod tray;
+#[cfg(feature = "websocket")]
+mod websocket;
 mod window;
 
-use std::sync::Mutex;
-use std::time::Duration;
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 
-use monitor::{Monitor, MonitorConfig};
-use tauri::{Emitter, Manager, WindowEvent};
+use hotkey::register_hotkey;
+use i18n::{detect_lang, lang_from_str};
+use logger::MetricLogger;
+use monitor::{AlertKind, Monitor, MonitorConfig, Thresholds};
+use notify::{notify_alert, AlertNotifier};
+use tauri::{Emitter, Manager, RunEvent, WindowEvent};
 use tauri_plugin_autostart::MacosLauncher;
 use tauri_plugin_store::StoreBuilder;
 
 use crate::commands::{
-    get_layout, get_monitor_visibility, get_system_info, get_text_color, greet, snap_window,
-    toggle_layout,
+    apply_metrics_port, apply_profile, apply_ws_port, enable_drag, export_metrics, export_settings,
+    export_snapshot, finish_drag, format_bytes, format_percent, format_speed, format_temperature,
+    get_battery_info, get_click_through, get_disk_alert_threshold, get_disk_info, get_font_size,
+    get_history, get_hotkey, get_language, get_layout, get_lock_to_corner, get_margin,
+    get_metric_colors, get_metric_order, get_metrics_port, get_monitor_intervals,
+    get_monitor_visibility, get_monitoring_paused, get_net_unit, get_network_info, get_opacity,
+    get_snap_hotkey, get_snap_mode, get_system_info, get_temp_unit, get_temperature_unit,
+    get_text_color, get_top_processes, get_ws_port, greet, import_settings, list_monitors,
+    list_profiles, move_to_next_monitor, open_settings_window, recenter_window, refresh_now,
+    reset_network_peaks, reset_settings, save_profile, set_custom_position,
+    set_disk_alert_threshold, set_font_size, set_hotkey, set_language, set_layout,
+    set_lock_to_corner, set_margin, set_metric_color, set_metric_order, set_metrics_port,
+    set_monitor_intervals, set_monitor_target, set_monitor_visibility, set_monitoring_paused,
+    set_net_unit, set_opacity, set_position, set_refresh_interval, set_snap_hotkey, set_snap_mode,
+    set_temperature_unit, set_text_color, set_window_size, set_ws_port, snap_window,
+    start_metric_logging, stop_metric_logging, toggle_layout, toggle_window_visibility,
 };
 use crate::state::{
-    layout_from_str, layout_to_str, position_from_str, position_to_str, primary_monitor_target,
-    visibility_from_state, UiState, KEY_LAYOUT, KEY_MONITOR_CPU, KEY_MONITOR_MEM, KEY_MONITOR_NET,
-    KEY_MONITOR_TARGET, KEY_POSITION, KEY_TEXT_COLOR, SETTINGS_PATH,
+    clamp_font_size, clamp_opacity, enabled_metrics, has_core_metric, layout_from_str,
+    metric_colors_from_value, metric_order_from_strs, monitor_positions_from_value,
+    net_unit_from_str, normalize_hex_color, position_from_str, primary_monitor_target,
+    save_ui_state, snap_mode_from_str, temp_unit_from_str, valid_disk_alert_threshold,
+    visibility_from_state, window_size_from_value, MetricColors, MonitorIntervals, UiState,
+    KEY_ALWAYS_ON_TOP, KEY_CLICK_THROUGH, KEY_DISK_ALERT_THRESHOLD, KEY_FONT_SIZE, KEY_HOTKEY,
+    KEY_LANG, KEY_LAYOUT, KEY_LOCK_TO_CORNER, KEY_MARGIN, KEY_METRICS_PORT, KEY_METRIC_COLORS,
+    KEY_METRIC_ORDER, KEY_MONITOR_BATTERY, KEY_MONITOR_CPU, KEY_MONITOR_GPU, KEY_MONITOR_INTERVALS,
+    KEY_MONITOR_MEM, KEY_MONITOR_NET, KEY_MONITOR_POSITIONS, KEY_MONITOR_TARGET, KEY_MONITOR_TEMP,
+    KEY_NET_UNIT, KEY_NOTIFICATIONS, KEY_OPACITY, KEY_POSITION, KEY_REFRESH_INTERVAL,
+    KEY_SIZE_HORIZONTAL, KEY_SIZE_VERTICAL, KEY_SNAP_HOTKEY, KEY_SNAP_MODE, KEY_TEMP_UNIT,
+    KEY_TEXT_COLOR, KEY_TRAY_DYNAMIC_ICON, KEY_WINDOW_VISIBLE, KEY_WS_PORT, MAX_MARGIN,
+    SETTINGS_PATH,
+};
+use crate::tray::{
+    emit_ui_state, setup_tray, update_tray_icon, update_tray_tooltip, window_moved, TrayIconCache,
+    TrayMenuItems, WindowDrag,
+};
+use crate::window::{
+    apply_layout_and_position, schedule_reposition, selected_monitor_lost, RepositionDebounce,
+    SETTINGS_WINDOW,
 };
-use crate::tray::setup_tray;
-use crate::window::apply_layout_and_position;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // `--once` 只打印一次指标，不创建窗口，见 `cli` 模块
+    if let Some(code) = cli::run_from_args(std::env::args().skip(1)) {
+        std::process::exit(code);
+    }
     tauri::Builder::default()
         .plugin(tauri_plugin_store::Builder::default().build())
         .plugin(tauri_plugin_autostart::init(
@@ -34,12 +84,15 @@ pub fn run() {
             None,
         ))
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(hotkey::plugin())
         .setup(|app| {
             #[cfg(target_os = "macos")]
             {
                 let _ = app.handle().set_dock_visibility(false);
             }
 
+            app.manage(MetricLogger::default());
             let store = StoreBuilder::new(app, SETTINGS_PATH).build()?;
             let mut ui_state = UiState::default();
             if let Some(value) = store.get(KEY_POSITION) {
@@ -57,9 +110,18 @@ pub fn run() {
                 }
             }
             if let Some(value) = store.get(KEY_TEXT_COLOR) {
-                if let Some(value) = value.as_str() {
-                    ui_state.text_color = value.to_string();
+                if let Some(color) = value.as_str().and_then(normalize_hex_color) {
+                    ui_state.text_color = color;
+                }
+            }
+            match store.get(KEY_METRIC_COLORS) {
+                Some(value) => {
+                    if let Some(colors) = metric_colors_from_value(&value) {
+                        ui_state.metric_colors = colors;
+                    }
                 }
+                // 旧版本只保存了全局颜色，各指标先沿用它
+                None => ui_state.metric_colors = MetricColors::seeded(&ui_state.text_color),
             }
             if let Some(value) = store.get(KEY_MONITOR_TARGET) {
                 if let Some(value) = value.as_str() {
@@ -84,70 +146,373 @@ pub fn run() {
                     ui_state.show_net = value;
                 }
             }
-            if !(ui_state.show_cpu || ui_state.show_mem || ui_state.show_net) {
-                ui_state.show_cpu = true;
+            if let Some(value) = store.get(KEY_MONITOR_BATTERY) {
+                if let Some(value) = value.as_bool() {
+                    ui_state.show_battery = value;
+                }
+            }
+            if let Some(value) = store.get(KEY_MONITOR_TEMP) {
+                if let Some(value) = value.as_bool() {
+                    ui_state.show_temp = value;
+                }
+            }
+            if let Some(value) = store.get(KEY_MONITOR_GPU) {
+                if let Some(value) = value.as_bool() {
+                    ui_state.show_gpu = value;
+                }
+            }
+            if let Some(value) = store.get(KEY_MONITOR_INTERVALS) {
+                if let Ok(intervals) = serde_json::from_value::<MonitorIntervals>(value) {
+                    if intervals.is_valid() {
+                        ui_state.intervals = intervals;
+                    }
+                }
+            }
+            // 选择过托盘预设时，以预设覆盖 CPU 和网络间隔
+            if let Some(value) = store.get(KEY_REFRESH_INTERVAL) {
+                if let Some(value) = value.as_u64().filter(|value| *value > 0) {
+                    ui_state.intervals.cpu_ms = value;
+                    ui_state.intervals.network_ms = value;
+                }
+            }
+            if let Some(value) = store.get(KEY_MONITOR_POSITIONS) {
+                ui_state.monitor_positions = monitor_positions_from_value(&value);
+            }
+            if let Some(value) = store.get(KEY_OPACITY) {
+                if let Some(value) = value.as_f64() {
+                    ui_state.opacity = clamp_opacity(value);
+                }
             }
-            store.set(KEY_POSITION, position_to_str(ui_state.position).to_string());
-            store.set(KEY_LAYOUT, layout_to_str(ui_state.layout).to_string());
-            store.set(KEY_TEXT_COLOR, ui_state.text_color.clone());
-            if let Some(target) = &ui_state.monitor_target {
-                store.set(KEY_MONITOR_TARGET, crate::state::monitor_target_to_str(target));
+            if let Some(value) = store.get(KEY_ALWAYS_ON_TOP) {
+                if let Some(value) = value.as_bool() {
+                    ui_state.always_on_top = value;
+                }
+            }
+            if let Some(value) = store.get(KEY_CLICK_THROUGH) {
+                if let Some(value) = value.as_bool() {
+                    ui_state.click_through = value;
+                }
+            }
+            if let Some(value) = store.get(KEY_TRAY_DYNAMIC_ICON) {
+                if let Some(value) = value.as_bool() {
+                    ui_state.tray_dynamic_icon = value;
+                }
+            }
+            if let Some(value) = store.get(KEY_NOTIFICATIONS) {
+                if let Some(value) = value.as_bool() {
+                    ui_state.notifications = value;
+                }
+            }
+            if let Some(value) = store.get(KEY_DISK_ALERT_THRESHOLD) {
+                ui_state.disk_alert_threshold = value.as_f64().and_then(valid_disk_alert_threshold);
             }
-            store.set(KEY_MONITOR_CPU, ui_state.show_cpu);
-            store.set(KEY_MONITOR_MEM, ui_state.show_mem);
-            store.set(KEY_MONITOR_NET, ui_state.show_net);
+            if let Some(value) = store.get(KEY_LOCK_TO_CORNER) {
+                if let Some(value) = value.as_bool() {
+                    ui_state.lock_to_corner = value;
+                }
+            }
+            if let Some(value) = store.get(KEY_SNAP_MODE) {
+                if let Some(value) = value.as_str() {
+                    if let Some(mode) = snap_mode_from_str(value) {
+                        ui_state.snap_mode = mode;
+                    }
+                }
+            }
+            if let Some(value) = store.get(KEY_WINDOW_VISIBLE) {
+                if let Some(value) = value.as_bool() {
+                    ui_state.visible = value;
+                }
+            }
+            if let Some(value) = store.get(KEY_METRIC_ORDER) {
+                if let Some(values) = value.as_array() {
+                    let values: Vec<&str> =
+                        values.iter().filter_map(|item| item.as_str()).collect();
+                    if let Some(order) = metric_order_from_strs(&values) {
+                        ui_state.metric_order = order;
+                    }
+                }
+            }
+            if let Some(value) = store.get(KEY_MARGIN) {
+                if let Some(value) = value.as_u64() {
+                    ui_state.margin = value.min(MAX_MARGIN as u64) as u32;
+                }
+            }
+            if let Some(value) = store.get(KEY_FONT_SIZE) {
+                if let Some(value) = value.as_u64() {
+                    ui_state.font_size = clamp_font_size(value.min(u32::MAX as u64) as u32);
+                }
+            }
+            if let Some(value) = store.get(KEY_NET_UNIT) {
+                if let Some(value) = value.as_str() {
+                    if let Some(unit) = net_unit_from_str(value) {
+                        ui_state.net_unit = unit;
+                    }
+                }
+            }
+            if let Some(value) = store.get(KEY_TEMP_UNIT) {
+                if let Some(value) = value.as_str() {
+                    if let Some(unit) = temp_unit_from_str(value) {
+                        ui_state.temp_unit = unit;
+                    }
+                }
+            }
+            // 未保存过语言时按系统 locale 选择
+            ui_state.lang = detect_lang();
+            if let Some(value) = store.get(KEY_LANG) {
+                if let Some(value) = value.as_str() {
+                    if let Some(lang) = lang_from_str(value) {
+                        ui_state.lang = lang;
+                    }
+                }
+            }
+            if let Some(value) = store.get(KEY_HOTKEY) {
+                if let Some(value) = value.as_str().filter(|value| !value.trim().is_empty()) {
+                    ui_state.hotkey = value.to_string();
+                }
+            }
+            if let Some(value) = store.get(KEY_SNAP_HOTKEY) {
+                if let Some(value) = value.as_str().filter(|value| !value.trim().is_empty()) {
+                    ui_state.snap_hotkey = value.to_string();
+                }
+            }
+            if let Some(value) = store.get(KEY_SIZE_HORIZONTAL) {
+                if let Some(size) = window_size_from_value(&value) {
+                    ui_state.size_horizontal = Some(size);
+                }
+            }
+            if let Some(value) = store.get(KEY_SIZE_VERTICAL) {
+                if let Some(size) = window_size_from_value(&value) {
+                    ui_state.size_vertical = Some(size);
+                }
+            }
+            if let Some(value) = store.get(KEY_METRICS_PORT) {
+                if let Some(port) = value.as_u64().and_then(|port| u16::try_from(port).ok()) {
+                    ui_state.metrics_port = Some(port).filter(|port| *port > 0);
+                }
+            }
+            if let Some(value) = store.get(KEY_WS_PORT) {
+                if let Some(port) = value.as_u64().and_then(|port| u16::try_from(port).ok()) {
+                    ui_state.ws_port = Some(port).filter(|port| *port > 0);
+                }
+            }
+            if !has_core_metric(&visibility_from_state(&ui_state)) {
+                ui_state.show_cpu = true;
+            }
+            save_ui_state(&store, &ui_state);
             app.manage(store);
             app.manage(Mutex::new(ui_state.clone()));
+            app.manage(RepositionDebounce::default());
+            app.manage(WindowDrag::default());
 
+            // 数据变化时推送 `system-info` 事件，载荷为完整的 `SystemInfo`
+            let handle = app.handle().clone();
             let monitor = Monitor::new(
-                MonitorConfig::new()
-                    .cpu_interval(Duration::from_secs(1))
-                    .memory_interval(Duration::from_secs(1))
-                    .disk_interval(Duration::from_secs(30))
-                    .network_interval(Duration::from_secs(1)),
-            );
+                ui_state.intervals.apply(
+                    MonitorConfig::new()
+                        .process_interval(Duration::from_secs(2))
+                        .enabled(enabled_metrics(&ui_state))
+                        .thresholds(Thresholds {
+                            cpu: Some(90.0),
+                            swap: Some(50.0),
+                            disk_full: ui_state.disk_alert_threshold,
+                            ..Thresholds::default()
+                        }),
+                ),
+            )
+            .with_emitter(move |info| {
+                let _ = handle.emit("system-info", info);
+                update_tray_icon(&handle, info.cpu.total_usage);
+                update_tray_tooltip(&handle, info);
+                #[cfg(feature = "websocket")]
+                broadcast_system_info(&handle, info);
+                // 写入失败时日志已自动停止，推送 `metric-logging-error` 事件，载荷为错误信息
+                if let Err(message) = handle.state::<MetricLogger>().log(info) {
+                    let _ = handle.emit("metric-logging-error", message);
+                }
+            });
+
+            // 越过阈值时推送 `monitor-alert` 事件，交换分区告警单独推送 `swap-pressure` 事件，
+            // 单个磁盘将满推送 `disk-alert` 事件（`mount_point` 为挂载点），
+            // 载荷均为 `MonitorAlert`。
+            // 开启通知时，新越过阈值的告警同时发送系统通知
+            let (alert_tx, alert_rx) = mpsc::channel();
+            monitor.set_alert_sender(alert_tx);
+            let handle = app.handle().clone();
+            thread::spawn(move || {
+                let mut notifier = AlertNotifier::default();
+                for alert in alert_rx {
+                    let settings = handle
+                        .state::<Mutex<UiState>>()
+                        .lock()
+                        .map(|state| (state.notifications, state.lang))
+                        .ok();
+                    if let Some((true, lang)) = settings {
+                        if notifier.should_notify(&alert, Instant::now()) {
+                            notify_alert(&handle, &alert, lang);
+                        }
+                    }
+                    let event = match alert.kind {
+                        AlertKind::Swap => "swap-pressure",
+                        AlertKind::DiskFull => "disk-alert",
+                        _ => "monitor-alert",
+                    };
+                    let _ = handle.emit(event, alert);
+                }
+            });
             monitor.refresh_all();
             monitor.start();
             app.manage(Mutex::new(monitor));
+            #[cfg(feature = "prometheus")]
+            app.manage(Mutex::new(None::<prometheus::MetricsServer>));
+            let _ = apply_metrics_port(&app.handle(), ui_state.metrics_port);
+            #[cfg(feature = "websocket")]
+            app.manage(Mutex::new(None::<websocket::StreamServer>));
+            let _ = apply_ws_port(&app.handle(), ui_state.ws_port);
+            // 快捷键被其他程序占用时不影响启动，推送 `hotkey-error` 事件，载荷为错误信息
+            for combo in [&ui_state.hotkey, &ui_state.snap_hotkey] {
+                if let Err(message) = register_hotkey(&app.handle(), combo) {
+                    let _ = app.emit("hotkey-error", message);
+                }
+            }
 
             if let Some(window) = app.get_webview_window("main") {
                 let handle = app.handle();
                 apply_layout_and_position(&handle, &window);
                 let _ = window.set_shadow(true);
-                let _ = window.unminimize();
-                let _ = window.show();
-                let _ = window.set_focus();
+                let _ = window.set_always_on_top(ui_state.always_on_top);
+                let _ = window.set_ignore_cursor_events(ui_state.click_through);
+                // 上次退出前隐藏了窗口时保持隐藏，可从托盘重新显示
+                if ui_state.visible {
+                    let _ = window.unminimize();
+                    let _ = window.show();
+                    let _ = window.set_focus();
+                } else {
+                    let _ = window.hide();
+                }
             }
 
             let tray_items = setup_tray(&app.handle(), &ui_state)?;
             app.manage(tray_items.clone());
+            app.manage(TrayIconCache::default());
 
-            let _ = app.emit("layout-changed", layout_to_str(ui_state.layout));
-            let _ = app.emit("text-color-changed", ui_state.text_color.clone());
-            let _ = app.emit(
-                "monitor-visibility-changed",
-                visibility_from_state(&ui_state),
-            );
+            emit_ui_state(&app.handle(), &ui_state);
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             greet,
+            format_bytes,
+            format_speed,
+            format_percent,
+            format_temperature,
             get_system_info,
+            refresh_now,
+            get_disk_info,
+            get_network_info,
+            reset_network_peaks,
+            get_top_processes,
+            get_battery_info,
+            get_history,
+            export_metrics,
+            export_snapshot,
+            get_metrics_port,
+            set_metrics_port,
+            get_ws_port,
+            set_ws_port,
+            get_disk_alert_threshold,
+            set_disk_alert_threshold,
+            get_hotkey,
+            set_hotkey,
+            get_snap_hotkey,
+            set_snap_hotkey,
+            set_window_size,
+            start_metric_logging,
+            stop_metric_logging,
+            get_monitoring_paused,
+            set_monitoring_paused,
+            get_monitor_intervals,
+            set_monitor_intervals,
+            set_refresh_interval,
             get_layout,
+            get_net_unit,
+            set_net_unit,
+            get_temperature_unit,
+            get_temp_unit,
+            set_temperature_unit,
+            get_language,
+            set_language,
             get_monitor_visibility,
+            set_monitor_visibility,
+            get_metric_order,
+            set_metric_order,
             get_text_color,
+            set_text_color,
+            get_metric_colors,
+            set_metric_color,
+            get_opacity,
+            set_opacity,
+            set_position,
+            set_layout,
+            set_custom_position,
+            get_click_through,
+            get_margin,
+            set_margin,
+            get_font_size,
+            set_font_size,
             snap_window,
+            enable_drag,
+            finish_drag,
+            get_lock_to_corner,
+            set_lock_to_corner,
+            get_snap_mode,
+            set_snap_mode,
+            move_to_next_monitor,
+            list_monitors,
+            set_monitor_target,
+            recenter_window,
+            reset_settings,
+            export_settings,
+            import_settings,
+            save_profile,
+            apply_profile,
+            list_profiles,
+            toggle_window_visibility,
+            open_settings_window,
             toggle_layout
         ])
         .on_window_event(|window, event| match event {
+            // 设置窗口关闭后取消托盘勾选，其余事件只处理悬浮窗
+            WindowEvent::Destroyed if window.label() == SETTINGS_WINDOW => {
+                if let Some(tray) = window.app_handle().try_state::<TrayMenuItems>() {
+                    tray.set_settings_window(false);
+                }
+            }
+            _ if window.label() != "main" => {}
             WindowEvent::Resized(_) | WindowEvent::ScaleFactorChanged { .. } => {
+                schedule_reposition(window.app_handle());
+            }
+            WindowEvent::Moved(_) => {
+                // 所在显示器断开后系统会移动窗口，此时回退到主显示器，避免窗口落在屏幕外
                 let app = window.app_handle().clone();
-                if let Some(webview) = app.get_webview_window("main") {
-                    apply_layout_and_position(&app, &webview);
+                window_moved(&app);
+                if selected_monitor_lost(&app) {
+                    if let Some(webview) = app.get_webview_window("main") {
+                        apply_layout_and_position(&app, &webview);
+                    }
+                    if let Some(tray) = app.try_state::<TrayMenuItems>() {
+                        tray.refresh_displays(&app);
+                    }
                 }
             }
             _ => {}
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let RunEvent::Exit = event {
+                let _ = apply_metrics_port(app, None);
+                let _ = apply_ws_port(app, None);
+                // 写入缓冲区中尚未落盘的日志
+                let _ = app.state::<MetricLogger>().stop();
+            }
+        });
 }
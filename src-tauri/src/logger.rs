@@ -0,0 +1,215 @@
+//! 指标 CSV 日志
+//!
+//! 由采集线程的推送回调驱动，每次推送追加一行，不单独轮询。
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::export::csv_fields;
+use crate::monitor::SystemInfo;
+
+/// 未指定字段时记录的列
+pub const DEFAULT_LOG_FIELDS: [&str; 5] = [
+    "timestamp",
+    "cpu.total_usage",
+    "memory.usage_percent",
+    "network.total_upload_speed",
+    "network.total_download_speed",
+];
+
+/// 缓冲区写入磁盘的间隔
+const FLUSH_INTERVAL: Duration = Duration::from_secs(10);
+
+struct LogSession {
+    path: String,
+    fields: Vec<String>,
+    writer: BufWriter<File>,
+    last_flush: Instant,
+}
+
+/// 指标日志，未启动时 `log` 不做任何事
+#[derive(Default)]
+pub struct MetricLogger {
+    session: Mutex<Option<LogSession>>,
+}
+
+impl MetricLogger {
+    /// 开始记录到 `path`，已在记录时先结束之前的文件
+    ///
+    /// 以追加方式打开文件，文件为空时先写入表头。已有内容的表头与 `fields` 不同时返回错误，
+    /// 不会把不同列的数据追加到同一个文件。`fields` 为空时使用 [`DEFAULT_LOG_FIELDS`]。
+    pub fn start(&self, path: &str, fields: Vec<String>) -> Result<(), String> {
+        let fields = if fields.is_empty() {
+            DEFAULT_LOG_FIELDS
+                .iter()
+                .map(|field| field.to_string())
+                .collect()
+        } else {
+            fields
+        };
+        let header = fields.join(",");
+
+        // 先结束之前的记录，缓冲中的表头和数据写入后再判断文件是否为空
+        let mut session = self.session.lock().map_err(|error| error.to_string())?;
+        if let Some(mut previous) = session.take() {
+            let _ = previous.writer.flush();
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|error| format!("failed to open {}: {}", path, error))?;
+        let is_empty = file
+            .metadata()
+            .map(|metadata| metadata.len() == 0)
+            .unwrap_or(true);
+        if !is_empty {
+            let existing = read_header(path)?;
+            if existing != header {
+                return Err(format!(
+                    "{} already logs different fields: {}",
+                    path, existing
+                ));
+            }
+        }
+        let mut writer = BufWriter::new(file);
+        if is_empty {
+            writeln!(writer, "{}", header)
+                .map_err(|error| format!("failed to write {}: {}", path, error))?;
+        }
+        *session = Some(LogSession {
+            path: path.to_string(),
+            fields,
+            writer,
+            last_flush: Instant::now(),
+        });
+        Ok(())
+    }
+
+    /// 结束记录并写入剩余数据，返回是否之前正在记录
+    pub fn stop(&self) -> Result<bool, String> {
+        let mut session = self.session.lock().map_err(|error| error.to_string())?;
+        match session.take() {
+            Some(mut previous) => {
+                previous
+                    .writer
+                    .flush()
+                    .map_err(|error| format!("failed to write {}: {}", previous.path, error))?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// 追加一行数据
+    ///
+    /// 写入失败（如磁盘已满）时结束记录并返回错误，之后的推送不再写入。
+    pub fn log(&self, info: &SystemInfo) -> Result<(), String> {
+        let Ok(mut session) = self.session.lock() else {
+            return Ok(());
+        };
+        let Some(current) = session.as_mut() else {
+            return Ok(());
+        };
+        let value = serde_json::to_value(info).map_err(|error| error.to_string())?;
+        let row = csv_fields(&value, &current.fields);
+        let mut result = writeln!(current.writer, "{}", row);
+        if result.is_ok() && current.last_flush.elapsed() >= FLUSH_INTERVAL {
+            result = current.writer.flush();
+            current.last_flush = Instant::now();
+        }
+        if let Err(error) = result {
+            let message = format!("failed to write {}: {}", current.path, error);
+            *session = None;
+            return Err(message);
+        }
+        Ok(())
+    }
+}
+
+/// 读取已有日志文件的表头（第一行）
+fn read_header(path: &str) -> Result<String, String> {
+    let file = File::open(path).map_err(|error| format!("failed to open {}: {}", path, error))?;
+    let mut header = String::new();
+    BufReader::new(file)
+        .read_line(&mut header)
+        .map_err(|error| format!("failed to read {}: {}", path, error))?;
+    Ok(header.trim_end().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_written_once() {
+        let path =
+            std::env::temp_dir().join(format!("corner-monitor-log-{}.csv", std::process::id()));
+        let path = path.to_str().unwrap().to_string();
+        let _ = std::fs::remove_file(&path);
+
+        let logger = MetricLogger::default();
+        let fields = vec!["timestamp".to_string(), "cpu.total_usage".to_string()];
+        logger.start(&path, fields.clone()).unwrap();
+        logger.log(&SystemInfo::default()).unwrap();
+        assert!(logger.stop().unwrap());
+        logger.start(&path, fields).unwrap();
+        logger.log(&SystemInfo::default()).unwrap();
+        logger.stop().unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(content, "timestamp,cpu.total_usage\n0,0.0\n0,0.0\n");
+        assert!(!logger.stop().unwrap());
+    }
+
+    #[test]
+    fn test_restart_without_stop() {
+        let path = std::env::temp_dir().join(format!(
+            "corner-monitor-log-restart-{}.csv",
+            std::process::id()
+        ));
+        let path = path.to_str().unwrap().to_string();
+        let _ = std::fs::remove_file(&path);
+
+        let logger = MetricLogger::default();
+        let fields = vec!["timestamp".to_string(), "cpu.total_usage".to_string()];
+        logger.start(&path, fields.clone()).unwrap();
+        logger.start(&path, fields).unwrap();
+        logger.log(&SystemInfo::default()).unwrap();
+        logger.stop().unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(content, "timestamp,cpu.total_usage\n0,0.0\n");
+    }
+
+    #[test]
+    fn test_rejects_different_fields() {
+        let path = std::env::temp_dir().join(format!(
+            "corner-monitor-log-fields-{}.csv",
+            std::process::id()
+        ));
+        let path = path.to_str().unwrap().to_string();
+        let _ = std::fs::remove_file(&path);
+
+        let logger = MetricLogger::default();
+        logger.start(&path, Vec::new()).unwrap();
+        logger.log(&SystemInfo::default()).unwrap();
+        logger.stop().unwrap();
+
+        let fields = vec!["timestamp".to_string(), "cpu.total_usage".to_string()];
+        let error = logger.start(&path, fields).unwrap_err();
+        assert!(error.contains("different fields"), "{}", error);
+        assert!(!logger.stop().unwrap());
+
+        // 相同的字段可以继续追加
+        logger.start(&path, Vec::new()).unwrap();
+        logger.stop().unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(content.lines().count(), 2);
+    }
+}
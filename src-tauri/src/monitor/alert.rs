@@ -0,0 +1,238 @@
+//! 阈值告警模块
+//!
+//! 只在指标越过阈值（进入或离开告警状态）时产生告警，避免每次采集都重复触发。
+
+use std::collections::HashMap;
+
+use crate::monitor::types::{AlertKind, DiskDetail, MonitorAlert};
+
+/// 单个指标的告警状态
+#[derive(Debug, Default)]
+struct AlertSlot {
+    active: bool,
+    /// 连续超过阈值的采样次数
+    streak: u32,
+    /// 是否有过未超过阈值的采样，见 [`MonitorAlert::fresh`]
+    seen_below: bool,
+}
+
+impl AlertSlot {
+    /// 记录一次采样，连续 `samples` 次超过阈值进入告警状态，回落到阈值以下立即解除
+    ///
+    /// 状态发生切换时返回切换后的状态。
+    fn update(&mut self, value: f64, threshold: Option<f64>, samples: u32) -> Option<bool> {
+        if threshold.is_some_and(|limit| value > limit) {
+            self.streak = self.streak.saturating_add(1);
+        } else {
+            self.streak = 0;
+            self.seen_below = true;
+        }
+        let active = self.streak >= samples.max(1);
+        if self.active == active {
+            return None;
+        }
+        self.active = active;
+        Some(active)
+    }
+}
+
+/// 告警状态跟踪器
+#[derive(Debug, Default)]
+pub struct AlertTracker {
+    cpu: AlertSlot,
+    memory: AlertSlot,
+    disk: AlertSlot,
+    network: AlertSlot,
+    swap: AlertSlot,
+    /// 各挂载点的磁盘使用率告警
+    disks: HashMap<String, AlertSlot>,
+}
+
+impl AlertTracker {
+    /// 创建新的跟踪器，所有指标初始均未处于告警状态
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 用最新数值检查指标，状态发生切换时返回对应的告警
+    ///
+    /// 阈值被移除时，如果之前处于告警状态，会产生一次解除告警。
+    pub fn check(
+        &mut self,
+        kind: AlertKind,
+        value: f64,
+        threshold: Option<f64>,
+    ) -> Option<MonitorAlert> {
+        self.check_sustained(kind, value, threshold, 1)
+    }
+
+    /// 与 `check` 相同，但需要连续 `samples` 次超过阈值才进入告警状态
+    ///
+    /// 任意一次回落到阈值以下都会清零计数并立即解除告警。
+    /// 按挂载点的 `DiskFull` 告警由 `check_disks` 检查，这里直接忽略。
+    pub fn check_sustained(
+        &mut self,
+        kind: AlertKind,
+        value: f64,
+        threshold: Option<f64>,
+        samples: u32,
+    ) -> Option<MonitorAlert> {
+        let slot = match kind {
+            AlertKind::Cpu => &mut self.cpu,
+            AlertKind::Memory => &mut self.memory,
+            AlertKind::Disk => &mut self.disk,
+            AlertKind::Network => &mut self.network,
+            AlertKind::Swap => &mut self.swap,
+            AlertKind::DiskFull => return None,
+        };
+        let active = slot.update(value, threshold, samples)?;
+
+        Some(MonitorAlert {
+            kind,
+            value,
+            threshold: threshold.unwrap_or(0.0),
+            active,
+            mount_point: None,
+            fresh: slot.seen_below,
+        })
+    }
+
+    /// 按挂载点检查各磁盘的使用率，返回状态发生切换的 `DiskFull` 告警
+    ///
+    /// 每个挂载点分别跟踪，越过阈值时各告警一次。处于告警状态的磁盘不再出现
+    /// （如被卸载）时产生一次解除告警。
+    pub fn check_disks(
+        &mut self,
+        disks: &[DiskDetail],
+        threshold: Option<f64>,
+    ) -> Vec<MonitorAlert> {
+        let alert = |mount_point: &str, value, active, fresh| MonitorAlert {
+            kind: AlertKind::DiskFull,
+            value,
+            threshold: threshold.unwrap_or(0.0),
+            active,
+            mount_point: Some(mount_point.to_string()),
+            fresh,
+        };
+        let mut alerts = Vec::new();
+        for disk in disks {
+            let value = disk.usage_percent as f64;
+            let slot = self.disks.entry(disk.mount_point.clone()).or_default();
+            if let Some(active) = slot.update(value, threshold, 1) {
+                alerts.push(alert(&disk.mount_point, value, active, slot.seen_below));
+            }
+        }
+        self.disks.retain(|mount_point, slot| {
+            let present = disks.iter().any(|disk| &disk.mount_point == mount_point);
+            if !present && slot.active {
+                alerts.push(alert(mount_point, 0.0, false, true));
+            }
+            present
+        });
+        alerts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alert_fires_on_enter_and_exit() {
+        let mut tracker = AlertTracker::new();
+        let limit = Some(90.0);
+
+        assert!(tracker.check(AlertKind::Cpu, 50.0, limit).is_none());
+        let alert = tracker.check(AlertKind::Cpu, 95.0, limit).unwrap();
+        assert!(alert.active);
+        assert!(alert.fresh);
+        assert_eq!(alert.threshold, 90.0);
+        assert!(tracker.check(AlertKind::Cpu, 97.0, limit).is_none());
+        // 其他指标互不影响
+        assert!(tracker.check(AlertKind::Memory, 95.0, None).is_none());
+
+        let alert = tracker.check(AlertKind::Cpu, 80.0, limit).unwrap();
+        assert!(!alert.active);
+        assert!(tracker.check(AlertKind::Cpu, 70.0, limit).is_none());
+
+        // 移除阈值时解除已有告警
+        tracker.check(AlertKind::Cpu, 95.0, limit).unwrap();
+        let alert = tracker.check(AlertKind::Cpu, 95.0, None).unwrap();
+        assert!(!alert.active);
+    }
+
+    #[test]
+    fn test_alert_already_high_at_start_is_not_fresh() {
+        let mut tracker = AlertTracker::new();
+        let limit = Some(90.0);
+
+        // 第一次采样就超过阈值
+        let alert = tracker.check(AlertKind::Memory, 95.0, limit).unwrap();
+        assert!(alert.active);
+        assert!(!alert.fresh);
+
+        let alert = tracker.check(AlertKind::Memory, 60.0, limit).unwrap();
+        assert!(!alert.active);
+        assert!(alert.fresh);
+        // 回落后再次越过阈值才算新的告警
+        let alert = tracker.check(AlertKind::Memory, 95.0, limit).unwrap();
+        assert!(alert.fresh);
+    }
+
+    #[test]
+    fn test_sustained_alert_needs_consecutive_samples() {
+        let mut tracker = AlertTracker::new();
+        let mut check = |value| tracker.check_sustained(AlertKind::Swap, value, Some(50.0), 3);
+
+        assert!(check(60.0).is_none());
+        assert!(check(60.0).is_none());
+        // 中途回落会重新计数
+        assert!(check(40.0).is_none());
+        assert!(check(60.0).is_none());
+        assert!(check(60.0).is_none());
+        let alert = check(70.0).unwrap();
+        assert!(alert.active);
+        assert_eq!(alert.value, 70.0);
+        assert!(check(70.0).is_none());
+
+        let alert = check(30.0).unwrap();
+        assert!(!alert.active);
+    }
+
+    #[test]
+    fn test_disk_alerts_per_mount() {
+        let disk = |mount_point: &str, usage_percent| DiskDetail {
+            name: String::new(),
+            mount_point: mount_point.to_string(),
+            file_system: "ext4".to_string(),
+            total: 100,
+            used: 0,
+            available: 100,
+            usage_percent,
+            is_removable: false,
+            read_speed: 0,
+            write_speed: 0,
+        };
+        let mut tracker = AlertTracker::new();
+        let limit = Some(90.0);
+
+        // 总体使用率不高，但 /data 已满
+        let alerts = tracker.check_disks(&[disk("/", 40.0), disk("/data", 95.0)], limit);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].kind, AlertKind::DiskFull);
+        assert_eq!(alerts[0].mount_point.as_deref(), Some("/data"));
+        assert!(alerts[0].active);
+        assert!(!alerts[0].fresh);
+        // 持续超过阈值不重复告警，另一块磁盘越过阈值时单独告警
+        let alerts = tracker.check_disks(&[disk("/", 92.0), disk("/data", 96.0)], limit);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].mount_point.as_deref(), Some("/"));
+        assert!(alerts[0].fresh);
+
+        // 回落和卸载都会解除告警
+        let alerts = tracker.check_disks(&[disk("/", 50.0)], limit);
+        assert_eq!(alerts.len(), 2);
+        assert!(alerts.iter().all(|alert| !alert.active));
+        assert!(tracker.check_disks(&[disk("/", 50.0)], limit).is_empty());
+    }
+}
@@ -0,0 +1,127 @@
+//! 电池信息采集模块
+
+use crate::monitor::types::{BatteryInfo, BatteryState, MonitorError};
+use battery::units::power::watt;
+use battery::units::ratio::percent;
+use battery::units::time::second;
+use battery::{Battery, Manager, State};
+use std::time::Duration;
+
+/// 电池采集器
+pub struct BatteryCollector {
+    /// 电池管理器，平台不支持时为 `None`
+    manager: Option<Manager>,
+    /// 已发现的电池，台式机上为空
+    batteries: Vec<Battery>,
+}
+
+impl BatteryCollector {
+    /// 创建新的电池采集器
+    pub fn new() -> Self {
+        let manager = Manager::new().ok();
+        let batteries = manager
+            .as_ref()
+            .and_then(|manager| manager.batteries().ok())
+            .map(|batteries| batteries.filter_map(Result::ok).collect())
+            .unwrap_or_default();
+
+        Self { manager, batteries }
+    }
+
+    /// 采集电池信息，没有电池时返回 `Ok(None)`，读取失败时返回错误
+    pub fn collect(&mut self) -> Result<Option<BatteryInfo>, MonitorError> {
+        let Some(manager) = self.manager.as_ref() else {
+            return Ok(None);
+        };
+
+        // 只取第一块电池，绝大多数笔记本只有一块
+        let Some(battery) = self.batteries.first_mut() else {
+            return Ok(None);
+        };
+        manager
+            .refresh(battery)
+            .map_err(|error| MonitorError::Battery(error.to_string()))?;
+
+        Ok(Some(battery_info(
+            battery.state(),
+            battery.state_of_charge().get::<percent>(),
+            battery.time_to_empty().map(|time| time.get::<second>()),
+            battery.energy_rate().get::<watt>(),
+        )))
+    }
+}
+
+/// 把电池库的原始读数转换为 [`BatteryInfo`]
+///
+/// `time_to_empty_secs` 为负数或非有限值时视为未知；部分平台不上报功率，
+/// `watts` 为 0 时视为不可用。
+fn battery_info(
+    state: State,
+    percentage: f32,
+    time_to_empty_secs: Option<f32>,
+    watts: f32,
+) -> BatteryInfo {
+    let state = match state {
+        State::Charging => BatteryState::Charging,
+        State::Discharging => BatteryState::Discharging,
+        State::Full => BatteryState::Full,
+        _ => BatteryState::Unknown,
+    };
+    let time_to_empty = time_to_empty_secs
+        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+        .map(Duration::from_secs_f32);
+    let power_draw_watts = Some(watts).filter(|watts| watts.is_finite() && *watts > 0.0);
+
+    BatteryInfo {
+        percentage: percentage.clamp(0.0, 100.0),
+        state,
+        time_to_empty,
+        power_draw_watts,
+    }
+}
+
+impl Default for BatteryCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_battery_info() {
+        let info = battery_info(State::Discharging, 64.5, Some(5400.0), 11.2);
+        assert_eq!(info.state, BatteryState::Discharging);
+        assert_eq!(info.percentage, 64.5);
+        assert_eq!(info.time_to_empty, Some(Duration::from_secs(5400)));
+        assert_eq!(info.power_draw_watts, Some(11.2));
+
+        let info = battery_info(State::Charging, 100.4, None, 0.0);
+        assert_eq!(info.state, BatteryState::Charging);
+        assert_eq!(info.percentage, 100.0);
+        assert_eq!(info.time_to_empty, None);
+        assert_eq!(info.power_draw_watts, None);
+
+        assert_eq!(
+            battery_info(State::Full, 100.0, None, 0.0).state,
+            BatteryState::Full
+        );
+        assert_eq!(
+            battery_info(State::Empty, 0.0, None, 0.0).state,
+            BatteryState::Unknown
+        );
+
+        // 平台返回的无效读数视为不可用
+        let info = battery_info(State::Unknown, -1.0, Some(-60.0), f32::NAN);
+        assert_eq!(info.state, BatteryState::Unknown);
+        assert_eq!(info.percentage, 0.0);
+        assert_eq!(info.time_to_empty, None);
+        assert_eq!(info.power_draw_watts, None);
+        assert_eq!(
+            battery_info(State::Discharging, 50.0, Some(f32::INFINITY), 5.0).time_to_empty,
+            None
+        );
+    }
+}
@@ -1,33 +1,61 @@
 //! CPU 信息采集模块
 
-use crate::monitor::types::{CpuCoreInfo, CpuInfo};
+use crate::monitor::types::{CpuCoreInfo, CpuInfo, MonitorError};
+use parking_lot::Mutex;
+use std::sync::Arc;
 use sysinfo::{Components, CpuRefreshKind, RefreshKind, System};
 
 /// CPU 采集器
 pub struct CpuCollector {
-    system: System,
+    system: Arc<Mutex<System>>,
     components: Components,
+    /// 总使用率的指数平滑系数
+    smoothing: Option<f32>,
+    /// 上一次平滑后的总使用率
+    smoothed_usage: Option<f32>,
 }
 
 impl CpuCollector {
-    /// 创建新的 CPU 采集器
+    /// 创建新的 CPU 采集器，使用独立的 `System`
     pub fn new() -> Self {
         // 只刷新 CPU 相关信息，减少不必要的开销
-        let mut system = System::new_with_specifics(
+        let system = System::new_with_specifics(
             RefreshKind::nothing().with_cpu(CpuRefreshKind::everything()),
         );
-        system.refresh_cpu_all();
+        Self::with_system(Arc::new(Mutex::new(system)))
+    }
+
+    /// 使用共享的 `System` 创建 CPU 采集器，只刷新其中的 CPU 数据
+    pub fn with_system(system: Arc<Mutex<System>>) -> Self {
+        system.lock().refresh_cpu_all();
         let components = Components::new_with_refreshed_list();
-        
-        Self { system, components }
+
+        Self {
+            system,
+            components,
+            smoothing: None,
+            smoothed_usage: None,
+        }
     }
 
-    /// 采集 CPU 信息
-    pub fn collect(&mut self) -> CpuInfo {
+    /// 调整总使用率的指数平滑系数，`None` 表示使用原始值
+    pub fn set_smoothing(&mut self, alpha: Option<f32>) {
+        if alpha.is_none() {
+            self.smoothed_usage = None;
+        }
+        self.smoothing = alpha;
+    }
+
+    /// 采集 CPU 信息，系统没有返回任何 CPU 时返回错误
+    pub fn collect(&mut self) -> Result<CpuInfo, MonitorError> {
         // 刷新 CPU 数据
-        self.system.refresh_cpu_all();
+        let mut system = self.system.lock();
+        system.refresh_cpu_all();
         
-        let cpus = self.system.cpus();
+        let cpus = system.cpus();
+        if cpus.is_empty() {
+            return Err(MonitorError::NoCpu);
+        }
         
         // 获取品牌名称
         let brand = cpus.first()
@@ -35,56 +63,313 @@ impl CpuCollector {
             .unwrap_or_default();
 
         // 计算总体使用率
-        let total_usage = if cpus.is_empty() {
-            0.0
-        } else {
-            cpus.iter().map(|cpu| cpu.cpu_usage()).sum::<f32>() / cpus.len() as f32
-        };
+        let total_usage =
+            cpus.iter().map(|cpu| cpu.cpu_usage()).sum::<f32>() / cpus.len() as f32;
 
         // 收集各核心信息
-        let cores: Vec<CpuCoreInfo> = cpus.iter()
+        let mut cores: Vec<CpuCoreInfo> = cpus.iter()
             .map(|cpu| CpuCoreInfo {
                 name: cpu.name().to_string(),
                 usage: cpu.cpu_usage(),
                 frequency: cpu.frequency(),
+                temperature: None,
             })
             .collect();
 
+        let (avg_frequency, max_frequency) =
+            frequency_stats(cores.iter().map(|core| core.frequency));
+
+        // 尽早释放共享的 `System`，读取温度不需要它
+        drop(system);
+
+        // 只平滑总使用率，各核心保持原始值
+        let total_usage = match self.smoothing {
+            Some(alpha) => {
+                let smoothed = smooth(self.smoothed_usage, total_usage, alpha);
+                self.smoothed_usage = Some(smoothed);
+                smoothed
+            }
+            None => total_usage,
+        };
+
         // 获取 CPU 温度
-        let temperature = self.get_cpu_temperature();
+        let temperatures = self.read_temperatures();
+        for (index, core) in cores.iter_mut().enumerate() {
+            core.temperature = temperatures.core(index);
+        }
+        let (temperature, temperature_source) = temperatures.overall.unzip();
 
         // 获取物理核心数
         let physical_core_count = System::physical_core_count();
 
-        CpuInfo {
+        // 获取平均负载，Windows 上 sysinfo 始终返回 0，视为不可用
+        let load_average = if cfg!(windows) {
+            None
+        } else {
+            let load = System::load_average();
+            Some((load.one, load.five, load.fifteen))
+        };
+
+        Ok(CpuInfo {
             brand,
             total_usage,
             cores,
             temperature,
+            temperature_source,
+            package_temperatures: temperatures.packages,
             physical_core_count,
-        }
+            load_average,
+            avg_frequency,
+            max_frequency,
+        })
     }
 
-    /// 获取 CPU 温度
-    fn get_cpu_temperature(&mut self) -> Option<f32> {
+    /// 读取 CPU 温度传感器
+    ///
+    /// 传感器标签因平台和驱动而异，这里按如下启发式规则匹配（忽略大小写）：
+    ///
+    /// - `Package id N`（Intel coretemp）、`Tdie`（AMD k10temp）视为封装温度；
+    ///   只有在两者都不存在时才使用 `Tctl`，因为它在部分 AMD 处理器上带有偏移量。
+    /// - `Core N` 视为物理核心温度，按编号排序后依次分配给逻辑核心；
+    ///   逻辑核心多于传感器时（超线程）循环复用，即第 i 个逻辑核心取第 `i % 传感器数` 个读数。
+    ///
+    /// 总体温度的选取见 [`select_temperature`]。
+    fn read_temperatures(&mut self) -> CpuTemperatures {
         self.components.refresh(true);
-        
-        // 尝试从组件中找到 CPU 温度
-        for component in self.components.iter() {
-            let label = component.label().to_lowercase();
-            // 不同系统的 CPU 温度标签可能不同
-            if label.contains("cpu") || label.contains("core") || label.contains("package") {
-                return component.temperature();
+
+        let sensors: Vec<(String, f32)> = self
+            .components
+            .iter()
+            .filter_map(|component| Some((component.label().to_string(), component.temperature()?)))
+            .collect();
+
+        let mut cores: Vec<(usize, f32)> = Vec::new();
+        let mut packages: Vec<f32> = Vec::new();
+        let mut tctl: Vec<f32> = Vec::new();
+
+        for (label, temperature) in &sensors {
+            let label = label.to_lowercase();
+
+            if label_index(&label, "package id").is_some() || label.contains("tdie") {
+                packages.push(*temperature);
+            } else if label.contains("tctl") {
+                tctl.push(*temperature);
+            } else if let Some(index) = label_index(&label, "core") {
+                cores.push((index, *temperature));
             }
         }
 
-        // 如果没找到明确的 CPU 温度，尝试获取第一个温度传感器
-        self.components.iter().next().and_then(|c| c.temperature())
+        if packages.is_empty() {
+            packages = tctl;
+        }
+        cores.sort_by_key(|(index, _)| *index);
+
+        CpuTemperatures {
+            cores: cores.into_iter().map(|(_, temperature)| temperature).collect(),
+            packages,
+            overall: select_temperature(&sensors),
+        }
+    }
+}
+
+/// 一次采集得到的 CPU 温度读数
+struct CpuTemperatures {
+    /// 按编号排序的物理核心温度
+    cores: Vec<f32>,
+    /// 封装温度
+    packages: Vec<f32>,
+    /// 总体温度及其来源
+    overall: Option<(f32, String)>,
+}
+
+impl CpuTemperatures {
+    /// 第 `index` 个逻辑核心对应的温度
+    fn core(&self, index: usize) -> Option<f32> {
+        if self.cores.is_empty() {
+            return None;
+        }
+        self.cores.get(index % self.cores.len()).copied()
     }
 }
 
+/// 从 `(标签, 温度)` 列表中选出总体温度，返回温度和来源描述
+///
+/// 按以下优先级匹配标签（忽略大小写），命中后不再看后面的规则：
+///
+/// 1. `tctl`/`tdie`（AMD k10temp），两者都有时取 `tdie`，它不带偏移量
+/// 2. `package`（Intel coretemp），多路 CPU 时取最高的一个
+/// 3. `cpu`，取第一个
+/// 4. `Core N`，多个核心时取平均值
+/// 5. 以上都没有时取第一个传感器
+///
+/// 主板芯片组、显卡等传感器的标签通常不含上述关键字，只会在最后一步被选中。
+fn select_temperature(sensors: &[(String, f32)]) -> Option<(f32, String)> {
+    let reading = |(label, temperature): &(String, f32)| (*temperature, label.clone());
+    let matching = |keyword: &'static str| {
+        sensors
+            .iter()
+            .filter(move |(label, _)| label.to_lowercase().contains(keyword))
+    };
+    let hottest = |keyword| {
+        matching(keyword)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(reading)
+    };
+
+    hottest("tdie")
+        .or_else(|| hottest("tctl"))
+        .or_else(|| hottest("package"))
+        .or_else(|| matching("cpu").next().map(reading))
+        .or_else(|| average_core_temperature(sensors))
+        .or_else(|| sensors.first().map(reading))
+}
+
+/// `Core N` 传感器的温度，多个时取平均值
+fn average_core_temperature(sensors: &[(String, f32)]) -> Option<(f32, String)> {
+    let cores: Vec<&(String, f32)> = sensors
+        .iter()
+        .filter(|(label, _)| label_index(&label.to_lowercase(), "core").is_some())
+        .collect();
+    match cores.as_slice() {
+        [] => None,
+        [(label, temperature)] => Some((*temperature, label.clone())),
+        _ => {
+            let sum: f32 = cores.iter().map(|(_, temperature)| temperature).sum();
+            Some((
+                sum / cores.len() as f32,
+                format!("{} core sensors (average)", cores.len()),
+            ))
+        }
+    }
+}
+
+/// 解析形如 `"<prefix> N"` 的标签中的编号，例如 `"coretemp core 3"` 得到 `3`
+///
+/// 从右侧查找前缀，避免 `coretemp` 这类驱动名中的 `core` 被误匹配。
+fn label_index(label: &str, prefix: &str) -> Option<usize> {
+    let start = label.rfind(prefix)? + prefix.len();
+    let digits: String = label[start..]
+        .trim_start()
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    digits.parse().ok()
+}
+
 impl Default for CpuCollector {
     fn default() -> Self {
         Self::new()
     }
 }
+
+/// 计算平均频率和最高频率，没有核心时均为 0
+fn frequency_stats(frequencies: impl Iterator<Item = u64>) -> (u64, u64) {
+    let (count, sum, max) = frequencies.fold((0u64, 0u64, 0u64), |(count, sum, max), value| {
+        (count + 1, sum + value, max.max(value))
+    });
+    if count == 0 {
+        return (0, 0);
+    }
+    (sum / count, max)
+}
+
+/// 指数平滑，没有上一次结果时直接使用新值
+fn smooth(previous: Option<f32>, value: f32, alpha: f32) -> f32 {
+    let alpha = if alpha.is_nan() { 1.0 } else { alpha.clamp(0.0, 1.0) };
+    match previous {
+        Some(previous) => alpha * value + (1.0 - alpha) * previous,
+        None => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_label_index() {
+        assert_eq!(label_index("coretemp core 3", "core"), Some(3));
+        assert_eq!(label_index("coretemp package id 1", "package id"), Some(1));
+        assert_eq!(label_index("k10temp tdie", "core"), None);
+        assert_eq!(label_index("cpu core", "core"), None);
+    }
+
+    #[test]
+    fn test_select_temperature() {
+        let sensors = |list: &[(&str, f32)]| -> Vec<(String, f32)> {
+            list.iter()
+                .map(|(label, temperature)| (label.to_string(), *temperature))
+                .collect()
+        };
+        let select = |list: &[(&str, f32)]| select_temperature(&sensors(list));
+
+        // AMD：芯片组传感器排在前面也不应被选中
+        assert_eq!(
+            select(&[
+                ("nct6798 SYSTIN", 35.0),
+                ("k10temp Tctl", 62.0),
+                ("k10temp Tccd1", 58.0)
+            ]),
+            Some((62.0, "k10temp Tctl".to_string()))
+        );
+        assert_eq!(
+            select(&[("k10temp Tctl", 72.0), ("k10temp Tdie", 62.0)]),
+            Some((62.0, "k10temp Tdie".to_string()))
+        );
+        // Intel 多路：取最高的封装温度
+        assert_eq!(
+            select(&[
+                ("coretemp Core 0", 50.0),
+                ("coretemp Package id 0", 55.0),
+                ("coretemp Package id 1", 60.0),
+            ]),
+            Some((60.0, "coretemp Package id 1".to_string()))
+        );
+        assert_eq!(
+            select(&[("acpitz", 40.0), ("CPU", 48.0)]),
+            Some((48.0, "CPU".to_string()))
+        );
+        // 只有核心温度时取平均值
+        assert_eq!(
+            select(&[("acpitz", 40.0), ("Core 0", 50.0), ("Core 1", 60.0)]),
+            Some((55.0, "2 core sensors (average)".to_string()))
+        );
+        assert_eq!(
+            select(&[("acpitz", 40.0), ("Core 0", 50.0)]),
+            Some((50.0, "Core 0".to_string()))
+        );
+        // 最后才取第一个传感器
+        assert_eq!(
+            select(&[("acpitz", 40.0), ("nvme Composite", 45.0)]),
+            Some((40.0, "acpitz".to_string()))
+        );
+        assert_eq!(select(&[]), None);
+    }
+
+    #[test]
+    fn test_smooth() {
+        assert_eq!(smooth(None, 80.0, 0.5), 80.0);
+        assert_eq!(smooth(Some(20.0), 80.0, 0.5), 50.0);
+        assert_eq!(smooth(Some(20.0), 80.0, 1.0), 80.0);
+        assert_eq!(smooth(Some(20.0), 80.0, 0.0), 20.0);
+        assert_eq!(smooth(Some(20.0), 80.0, 3.0), 80.0);
+        assert_eq!(smooth(Some(20.0), 80.0, f32::NAN), 80.0);
+    }
+
+    #[test]
+    fn test_frequency_stats() {
+        assert_eq!(frequency_stats([].into_iter()), (0, 0));
+        assert_eq!(frequency_stats([2400, 3600, 3000].into_iter()), (3000, 3600));
+    }
+
+    #[test]
+    fn test_shared_system() {
+        let system = Arc::new(Mutex::new(System::new()));
+        let mut cpu = CpuCollector::with_system(Arc::clone(&system));
+        let mut memory = crate::monitor::memory::MemoryCollector::with_system(system);
+
+        assert!(!cpu.collect().unwrap().cores.is_empty());
+        assert!(memory.collect().unwrap().total > 0);
+        assert!(!cpu.collect().unwrap().cores.is_empty());
+    }
+}
@@ -1,30 +1,65 @@
 //! 磁盘信息采集模块
 
-use crate::monitor::types::{DiskDetail, DiskInfo};
+use crate::monitor::rate::{rate_per_sec, SampleTime};
+use crate::monitor::types::{DiskDetail, DiskFilter, DiskInfo};
 use sysinfo::Disks;
+use std::collections::{HashMap, HashSet};
+
+/// 磁盘上一次的读写数据快照
+struct DiskSnapshot {
+    read: u64,
+    written: u64,
+    timestamp: SampleTime,
+}
 
 /// 磁盘采集器
 pub struct DiskCollector {
     disks: Disks,
+    /// 存储上一次各挂载点的读写数据，用于计算速率
+    last_snapshot: HashMap<String, DiskSnapshot>,
+    /// 过滤规则
+    filter: DiskFilter,
 }
 
 impl DiskCollector {
     /// 创建新的磁盘采集器
-    pub fn new() -> Self {
+    pub fn new(filter: DiskFilter) -> Self {
         let disks = Disks::new_with_refreshed_list();
-        Self { disks }
+        Self {
+            disks,
+            last_snapshot: HashMap::new(),
+            filter,
+        }
+    }
+
+    /// 调整过滤规则
+    pub fn set_filter(&mut self, filter: DiskFilter) {
+        self.filter = filter;
+    }
+
+    /// 丢弃各磁盘的上一次读写快照，下一次采集的速率记为 0
+    ///
+    /// 系统休眠唤醒后调用，避免用休眠前的快照计算速率。
+    pub fn reset_baseline(&mut self) {
+        self.last_snapshot.clear();
     }
 
     /// 采集磁盘信息
     pub fn collect(&mut self) -> DiskInfo {
         self.disks.refresh(true);
 
+        let now = SampleTime::now();
         let mut disk_details: Vec<DiskDetail> = Vec::new();
-        let mut total: u64 = 0;
-        let mut total_used: u64 = 0;
-        let mut total_available: u64 = 0;
 
         for disk in self.disks.iter() {
+            let file_system = disk.file_system()
+                .to_string_lossy()
+                .to_string();
+            if self.filter.excludes(&file_system) {
+                continue;
+            }
+
+            let name = disk.name().to_string_lossy().to_string();
             let disk_total = disk.total_space();
             let disk_available = disk.available_space();
             let disk_used = disk_total.saturating_sub(disk_available);
@@ -35,47 +70,169 @@ impl DiskCollector {
                 0.0
             };
 
-            let file_system = disk.file_system()
-                .to_string_lossy()
-                .to_string();
+            let mount_point = disk.mount_point().to_string_lossy().to_string();
+            let usage = disk.usage();
+            let current_read = usage.total_read_bytes;
+            let current_written = usage.total_written_bytes;
+
+            // 计算速率，新出现的磁盘、休眠唤醒或长时间未采样后的第一次为 0
+            let (read_bytes_per_sec, write_bytes_per_sec) =
+                if let Some(last) = self.last_snapshot.get(&mount_point) {
+                    let secs = now.secs_since(&last.timestamp);
+                    (
+                        rate_per_sec(last.read, current_read, secs),
+                        rate_per_sec(last.written, current_written, secs),
+                    )
+                } else {
+                    (0, 0)
+                };
+
+            // 更新快照
+            self.last_snapshot.insert(mount_point.clone(), DiskSnapshot {
+                read: current_read,
+                written: current_written,
+                timestamp: now,
+            });
 
             let detail = DiskDetail {
-                name: disk.name().to_string_lossy().to_string(),
-                mount_point: disk.mount_point().to_string_lossy().to_string(),
+                name,
+                mount_point,
                 file_system,
                 total: disk_total,
                 used: disk_used,
                 available: disk_available,
                 usage_percent,
                 is_removable: disk.is_removable(),
+                read_speed: read_bytes_per_sec,
+                write_speed: write_bytes_per_sec,
             };
 
-            // 累加总量（只计算非可移除磁盘或有意义的磁盘）
-            total += disk_total;
-            total_used += disk_used;
-            total_available += disk_available;
-
             disk_details.push(detail);
         }
 
-        let total_usage_percent = if total > 0 {
-            (total_used as f32 / total as f32) * 100.0
-        } else {
-            0.0
-        };
+        // 清理已移除磁盘的快照
+        self.last_snapshot.retain(|_, snapshot| snapshot.timestamp == now);
 
-        DiskInfo {
-            disks: disk_details,
-            total,
-            total_used,
-            total_available,
-            total_usage_percent,
+        summarize(disk_details, self.filter.dedupe_devices)
+    }
+}
+
+/// 识别同一物理卷的标识，无法可靠识别时返回 `None`，此时不参与合并
+///
+/// - 同一 APFS 容器中的卷共享容量，`total` 相同，按容量合并
+/// - 形如 `/dev/sda1` 的设备路径可以直接识别同一设备（如 bind mount）
+/// - Windows 上的名称是卷标，可能重复，不参与合并
+fn volume_key(disk: &DiskDetail) -> Option<String> {
+    if disk.file_system.eq_ignore_ascii_case("apfs") {
+        Some(format!("apfs:{}", disk.total))
+    } else if disk.name.starts_with('/') {
+        Some(disk.name.clone())
+    } else {
+        None
+    }
+}
+
+/// 汇总磁盘总量
+///
+/// 所有挂载点都会保留在结果中；`dedupe` 为 `true` 时，同一物理卷只计入一次总量和速率。
+fn summarize(disks: Vec<DiskDetail>, dedupe: bool) -> DiskInfo {
+    let mut total: u64 = 0;
+    let mut total_used: u64 = 0;
+    let mut total_available: u64 = 0;
+    let mut total_read_speed: u64 = 0;
+    let mut total_write_speed: u64 = 0;
+    // 已统计过的物理卷
+    let mut seen: HashSet<String> = HashSet::new();
+
+    for disk in &disks {
+        if dedupe {
+            if let Some(key) = volume_key(disk) {
+                if !seen.insert(key) {
+                    continue;
+                }
+            }
         }
+        total += disk.total;
+        total_used += disk.used;
+        total_available += disk.available;
+        total_read_speed += disk.read_speed;
+        total_write_speed += disk.write_speed;
+    }
+
+    let total_usage_percent = if total > 0 {
+        (total_used as f32 / total as f32) * 100.0
+    } else {
+        0.0
+    };
+
+    DiskInfo {
+        disks,
+        total,
+        total_used,
+        total_available,
+        total_usage_percent,
+        total_read_speed,
+        total_write_speed,
     }
 }
 
 impl Default for DiskCollector {
     fn default() -> Self {
-        Self::new()
+        Self::new(DiskFilter::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_excludes_pseudo_file_systems() {
+        let filter = DiskFilter::default();
+        assert!(filter.excludes("tmpfs"));
+        assert!(filter.excludes("Overlay"));
+        assert!(!filter.excludes("ext4"));
+        assert!(!DiskFilter::none().excludes("tmpfs"));
+
+        let info = DiskCollector::new(filter).collect();
+        assert!(info.disks.iter().all(|disk| disk.file_system != "tmpfs"));
+        let listed_total: u64 = info.disks.iter().map(|disk| disk.total).sum();
+        assert!(info.total <= listed_total);
+    }
+
+    fn detail(name: &str, mount_point: &str, file_system: &str, total: u64) -> DiskDetail {
+        DiskDetail {
+            name: name.to_string(),
+            mount_point: mount_point.to_string(),
+            file_system: file_system.to_string(),
+            total,
+            used: total / 2,
+            available: total - total / 2,
+            usage_percent: 50.0,
+            is_removable: false,
+            read_speed: 10,
+            write_speed: 20,
+        }
+    }
+
+    #[test]
+    fn test_summarize_dedupes_shared_volumes() {
+        let disks = vec![
+            detail("/dev/sda1", "/", "ext4", 100),
+            detail("/dev/sda1", "/mnt/bind", "ext4", 100),
+            detail("Macintosh HD", "/", "apfs", 500),
+            detail("Macintosh HD - Data", "/System/Volumes/Data", "apfs", 500),
+            detail("Local Disk", "C:\\", "NTFS", 300),
+            detail("Local Disk", "D:\\", "NTFS", 300),
+        ];
+
+        let info = summarize(disks.clone(), true);
+        assert_eq!(info.disks.len(), 6);
+        assert_eq!(info.total, 100 + 500 + 300 + 300);
+        assert_eq!(info.total_read_speed, 40);
+
+        let raw = summarize(disks, false);
+        assert_eq!(raw.total, 1800);
+        assert_eq!(raw.total_write_speed, 120);
     }
 }
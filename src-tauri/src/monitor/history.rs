@@ -0,0 +1,109 @@
+//! 历史数据模块
+//!
+//! 保存最近若干次采集结果，供前端绘制迷你折线图。
+
+use crate::monitor::types::HistoryInfo;
+use std::collections::VecDeque;
+
+/// 固定容量的环形缓冲区，写满后丢弃最旧的数据
+#[derive(Debug, Clone)]
+pub struct RingBuffer<T> {
+    samples: VecDeque<T>,
+    capacity: usize,
+}
+
+impl<T: Clone> RingBuffer<T> {
+    /// 创建指定容量的缓冲区
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// 追加一个样本
+    pub fn push(&mut self, sample: T) {
+        if self.capacity == 0 {
+            return;
+        }
+        while self.samples.len() >= self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    /// 调整容量，缩小时丢弃最旧的数据
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.samples.len() > capacity {
+            self.samples.pop_front();
+        }
+    }
+
+    /// 获取最近的 `n` 个样本，按时间从旧到新排列
+    pub fn latest(&self, n: usize) -> Vec<T> {
+        let skip = self.samples.len().saturating_sub(n);
+        self.samples.iter().skip(skip).cloned().collect()
+    }
+}
+
+/// 各项指标的历史数据
+#[derive(Debug, Clone)]
+pub struct History {
+    pub cpu: RingBuffer<f32>,
+    pub memory: RingBuffer<f32>,
+    pub upload: RingBuffer<u64>,
+    pub download: RingBuffer<u64>,
+}
+
+impl History {
+    /// 创建指定容量的历史数据
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            cpu: RingBuffer::new(capacity),
+            memory: RingBuffer::new(capacity),
+            upload: RingBuffer::new(capacity),
+            download: RingBuffer::new(capacity),
+        }
+    }
+
+    /// 调整所有序列的容量
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.cpu.set_capacity(capacity);
+        self.memory.set_capacity(capacity);
+        self.upload.set_capacity(capacity);
+        self.download.set_capacity(capacity);
+    }
+
+    /// 获取各序列最近的 `n` 个样本
+    pub fn latest(&self, n: usize) -> HistoryInfo {
+        HistoryInfo {
+            cpu: self.cpu.latest(n),
+            memory: self.memory.latest(n),
+            upload: self.upload.latest(n),
+            download: self.download.latest(n),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_buffer_drops_oldest() {
+        let mut buffer = RingBuffer::new(3);
+        for sample in 1..=5 {
+            buffer.push(sample);
+        }
+        assert_eq!(buffer.latest(10), vec![3, 4, 5]);
+        assert_eq!(buffer.latest(2), vec![4, 5]);
+
+        buffer.set_capacity(1);
+        assert_eq!(buffer.latest(10), vec![5]);
+
+        buffer.set_capacity(0);
+        buffer.push(6);
+        assert!(buffer.latest(10).is_empty());
+    }
+}
@@ -1,39 +1,60 @@
 //! 内存信息采集模块
+//!
+//! `used` 统一按“总量减去可用内存”计算，不把页缓存、缓冲区等可回收的内存算作已使用，
+//! `usage_percent` 与之保持一致。Linux 上另外从 `/proc/meminfo` 读取缓存和缓冲区的大小，
+//! 其他平台不提供这两项，始终为 0。
 
-use crate::monitor::types::MemoryInfo;
+use crate::monitor::types::{MemoryInfo, MemoryPressureThresholds, MonitorError};
+use parking_lot::Mutex;
+use std::sync::Arc;
 use sysinfo::{MemoryRefreshKind, RefreshKind, System};
 
 /// 内存采集器
 pub struct MemoryCollector {
-    system: System,
+    system: Arc<Mutex<System>>,
+    pressure_thresholds: MemoryPressureThresholds,
 }
 
 impl MemoryCollector {
-    /// 创建新的内存采集器
+    /// 创建新的内存采集器，使用独立的 `System`
     pub fn new() -> Self {
         // 只刷新内存相关信息，减少不必要的开销
         let system = System::new_with_specifics(
             RefreshKind::nothing().with_memory(MemoryRefreshKind::everything()),
         );
-        Self { system }
+        Self::with_system(Arc::new(Mutex::new(system)))
     }
 
-    /// 采集内存信息
-    pub fn collect(&mut self) -> MemoryInfo {
-        self.system.refresh_memory();
+    /// 使用共享的 `System` 创建内存采集器，只刷新其中的内存数据
+    pub fn with_system(system: Arc<Mutex<System>>) -> Self {
+        Self {
+            system,
+            pressure_thresholds: MemoryPressureThresholds::default(),
+        }
+    }
 
-        let total = self.system.total_memory();
-        let used = self.system.used_memory();
-        let available = self.system.available_memory();
-        
-        let usage_percent = if total > 0 {
-            (used as f32 / total as f32) * 100.0
-        } else {
-            0.0
-        };
+    /// 调整内存压力等级的判定阈值
+    pub fn set_pressure_thresholds(&mut self, thresholds: MemoryPressureThresholds) {
+        self.pressure_thresholds = thresholds;
+    }
 
-        let swap_total = self.system.total_swap();
-        let swap_used = self.system.used_swap();
+    /// 采集内存信息，读取不到内存总量时返回错误
+    pub fn collect(&mut self) -> Result<MemoryInfo, MonitorError> {
+        let mut system = self.system.lock();
+        system.refresh_memory();
+
+        let total = system.total_memory();
+        if total == 0 {
+            return Err(MonitorError::NoMemory);
+        }
+        let available = system.available_memory();
+        let used = total.saturating_sub(available);
+        let (cached, buffers) = read_cache_and_buffers();
+
+        let usage_percent = (used as f32 / total as f32) * 100.0;
+
+        let swap_total = system.total_swap();
+        let swap_used = system.used_swap();
         
         let swap_usage_percent = if swap_total > 0 {
             (swap_used as f32 / swap_total as f32) * 100.0
@@ -41,16 +62,62 @@ impl MemoryCollector {
             0.0
         };
 
-        MemoryInfo {
+        let available_percent = (available as f32 / total as f32) * 100.0;
+        let pressure = self
+            .pressure_thresholds
+            .classify(available_percent, swap_usage_percent);
+
+        Ok(MemoryInfo {
             total,
             used,
             available,
+            cached,
+            buffers,
             usage_percent,
             swap_total,
             swap_used,
             swap_usage_percent,
+            pressure,
+        })
+    }
+}
+
+/// 读取页缓存和缓冲区的大小 (字节)，读取失败时均为 0
+#[cfg(target_os = "linux")]
+fn read_cache_and_buffers() -> (u64, u64) {
+    std::fs::read_to_string("/proc/meminfo")
+        .map(|content| parse_meminfo(&content))
+        .unwrap_or((0, 0))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_cache_and_buffers() -> (u64, u64) {
+    (0, 0)
+}
+
+/// 从 `/proc/meminfo` 的内容中解析 (缓存, 缓冲区)，缓存包含 `Cached` 与 `SReclaimable`
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_meminfo(content: &str) -> (u64, u64) {
+    let mut cached = 0;
+    let mut buffers = 0;
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let Some(kib) = value
+            .split_whitespace()
+            .next()
+            .and_then(|value| value.parse::<u64>().ok())
+        else {
+            continue;
+        };
+        match key {
+            "Cached" | "SReclaimable" => cached += kib * 1024,
+            "Buffers" => buffers += kib * 1024,
+            _ => {}
         }
     }
+    (cached, buffers)
 }
 
 impl Default for MemoryCollector {
@@ -58,3 +125,31 @@ impl Default for MemoryCollector {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::types::MemoryPressure;
+
+    #[test]
+    fn test_parse_meminfo() {
+        let content = "MemTotal:       16315148 kB\n\
+                       MemAvailable:    9123456 kB\n\
+                       Buffers:          204800 kB\n\
+                       Cached:          4096000 kB\n\
+                       SwapCached:          100 kB\n\
+                       SReclaimable:     512000 kB\n";
+        assert_eq!(parse_meminfo(content), (4_608_000 * 1024, 204_800 * 1024));
+        assert_eq!(parse_meminfo(""), (0, 0));
+    }
+
+    #[test]
+    fn test_pressure_classify() {
+        let thresholds = MemoryPressureThresholds::default();
+        assert_eq!(thresholds.classify(40.0, 0.0), MemoryPressure::Normal);
+        assert_eq!(thresholds.classify(10.0, 0.0), MemoryPressure::Warning);
+        assert_eq!(thresholds.classify(40.0, 30.0), MemoryPressure::Warning);
+        assert_eq!(thresholds.classify(4.0, 0.0), MemoryPressure::Critical);
+        assert_eq!(thresholds.classify(40.0, 60.0), MemoryPressure::Critical);
+    }
+}
@@ -1,6 +1,6 @@
 //! 系统监控模块
 //!
-//! 提供 CPU、内存、磁盘、网络的监控功能，支持多线程后台采集。
+//! 提供 CPU、内存、磁盘、网络、进程、电池的监控功能，支持多线程后台采集。
 //!
 //! # 使用示例
 //!
@@ -13,7 +13,9 @@
 //!     .cpu_interval(Duration::from_secs(1))
 //!     .memory_interval(Duration::from_secs(2))
 //!     .disk_interval(Duration::from_secs(5))
-//!     .network_interval(Duration::from_secs(1));
+//!     .network_interval(Duration::from_secs(1))
+//!     .process_interval(Duration::from_secs(2))
+//!     .battery_interval(Duration::from_secs(30));
 //!
 //! // 创建并启动监控器
 //! let monitor = Monitor::new(config);
@@ -28,16 +30,25 @@
 //! let memory_info = monitor.get_memory_info();
 //! let disk_info = monitor.get_disk_info();
 //! let network_info = monitor.get_network_info();
+//! let top_processes = monitor.get_top_processes(5);
+//! let battery_info = monitor.get_battery_info();
 //!
 //! // 停止监控
 //! monitor.stop();
 //! ```
+//!
+//! 也可以通过 [`Monitor::with_emitter`] 注册回调，在数据变化时主动推送，而不必轮询。
 
 mod types;
 mod cpu;
 mod memory;
 mod disk;
 mod network;
+mod process;
+mod battery;
+mod history;
+mod alert;
+mod rate;
 
 pub use types::*;
 
@@ -45,30 +56,310 @@ use cpu::CpuCollector;
 use memory::MemoryCollector;
 use disk::DiskCollector;
 use network::NetworkCollector;
+use process::ProcessCollector;
+use battery::BatteryCollector;
+use history::History;
+use alert::AlertTracker;
 
-use parking_lot::RwLock;
-use std::sync::atomic::{AtomicBool, Ordering};
+use parking_lot::{Condvar, Mutex, RwLock};
+use sysinfo::System;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::Sender;
 use std::sync::Arc;
 use std::thread;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// 指标重新启用后，预热采集与正式采集之间的间隔
+///
+/// CPU、磁盘、网络的数据都是相对上一次采集计算的，隐藏期间的基准已经过期，
+/// 需要先刷新一次基准，隔一小段时间再正式采集。
+const RESUME_DELAY: Duration = Duration::from_millis(200);
+
+/// 数据变化时的推送回调
+type Emitter = Box<dyn Fn(&SystemInfo) + Send + Sync>;
+
+/// 各采集器及其速率基准，后台采集线程与手动刷新共用
+///
+/// 电池采集器不能跨线程移动，且没有需要保留的基准，不放在这里。
+struct Collectors {
+    cpu: CpuCollector,
+    memory: MemoryCollector,
+    disk: DiskCollector,
+    network: NetworkCollector,
+    process: ProcessCollector,
+    /// CPU、磁盘、网络、进程是否已有可用的基准
+    ///
+    /// 停止或暂停后基准会过期，下一次手动刷新需要先预热。
+    primed: bool,
+}
+
+impl Collectors {
+    fn new(config: &MonitorConfig) -> Self {
+        // CPU 与内存共用一个 `System`，各自只刷新需要的部分
+        let system = Arc::new(Mutex::new(System::new()));
+        Self {
+            cpu: CpuCollector::with_system(Arc::clone(&system)),
+            memory: MemoryCollector::with_system(system),
+            disk: DiskCollector::new(config.disk_filter.clone()),
+            network: NetworkCollector::new(),
+            process: ProcessCollector::new(config.process_top_n),
+            primed: false,
+        }
+    }
+
+    /// 应用最新配置
+    fn configure(&mut self, config: &MonitorConfig) {
+        self.process.set_top_n(config.process_top_n);
+        self.disk.set_filter(config.disk_filter.clone());
+        self.cpu.set_smoothing(config.cpu_smoothing);
+        self.memory.set_pressure_thresholds(config.memory_pressure);
+    }
+}
 
 /// 内部共享状态
 struct MonitorState {
+    /// 当前配置，采集线程每轮都会重新读取
+    config: RwLock<MonitorConfig>,
     cpu: RwLock<CpuInfo>,
     memory: RwLock<MemoryInfo>,
     disk: RwLock<DiskInfo>,
     network: RwLock<NetworkInfo>,
+    processes: RwLock<Vec<ProcessInfo>>,
+    battery: RwLock<Option<BatteryInfo>>,
+    /// 最近若干次采集的历史数据
+    history: RwLock<History>,
+    emitter: RwLock<Option<Emitter>>,
+    /// 最近一次尚未恢复的采集错误
+    last_error: RwLock<Option<MonitorError>>,
+    /// 阈值告警的接收端
+    alert_sender: Mutex<Option<Sender<MonitorAlert>>>,
+    /// 采集线程每轮持有，手动刷新时复用，不会重置网络累计流量和平滑状态
+    collectors: Mutex<Collectors>,
     running: AtomicBool,
+    /// 暂停时采集线程保持运行但跳过采集，保留各采集器的基准数据
+    paused: AtomicBool,
+    /// 用于唤醒休眠中的采集线程
+    wakeup: Condvar,
+    wakeup_lock: Mutex<()>,
+    /// 同一时间只进行一次手动刷新
+    refresh_lock: Mutex<()>,
+    /// 已完成的手动刷新次数
+    refresh_count: AtomicU64,
+    /// 本次运行以来的最高总上传、下载速率
+    peak_upload_speed: AtomicU64,
+    peak_download_speed: AtomicU64,
 }
 
-impl Default for MonitorState {
-    fn default() -> Self {
+impl MonitorState {
+    fn new(config: MonitorConfig) -> Self {
         Self {
+            history: RwLock::new(History::new(config.history_len)),
+            collectors: Mutex::new(Collectors::new(&config)),
+            config: RwLock::new(config),
             cpu: RwLock::new(CpuInfo::default()),
             memory: RwLock::new(MemoryInfo::default()),
             disk: RwLock::new(DiskInfo::default()),
             network: RwLock::new(NetworkInfo::default()),
+            processes: RwLock::new(Vec::new()),
+            battery: RwLock::new(None),
+            emitter: RwLock::new(None),
+            last_error: RwLock::new(None),
+            alert_sender: Mutex::new(None),
             running: AtomicBool::new(false),
+            paused: AtomicBool::new(false),
+            wakeup: Condvar::new(),
+            wakeup_lock: Mutex::new(()),
+            refresh_lock: Mutex::new(()),
+            refresh_count: AtomicU64::new(0),
+            peak_upload_speed: AtomicU64::new(0),
+            peak_download_speed: AtomicU64::new(0),
+        }
+    }
+
+    /// 休眠到指定时间，期间可被 `wake()` 提前唤醒
+    ///
+    /// 返回采集线程是否应继续运行
+    fn sleep_until(&self, deadline: Instant) -> bool {
+        let mut guard = self.wakeup_lock.lock();
+        if !self.running.load(Ordering::SeqCst) {
+            return false;
+        }
+        self.wakeup.wait_until(&mut guard, deadline);
+        self.running.load(Ordering::SeqCst)
+    }
+
+    /// 写入新数据，返回数据是否发生变化
+    fn update<T: PartialEq>(slot: &RwLock<T>, value: T) -> bool {
+        let mut current = slot.write();
+        if *current == value {
+            return false;
+        }
+        *current = value;
+        true
+    }
+
+    /// 检查采集结果
+    ///
+    /// 失败时保存错误并返回 `None`，原有数据保持不变；成功时清除该采集器之前的错误。
+    fn check<T>(&self, collector: &str, result: Result<T, MonitorError>) -> Option<T> {
+        let mut last_error = self.last_error.write();
+        match result {
+            Ok(value) => {
+                if last_error
+                    .as_ref()
+                    .is_some_and(|error| error.collector() == collector)
+                {
+                    *last_error = None;
+                }
+                Some(value)
+            }
+            Err(error) => {
+                *last_error = Some(error);
+                None
+            }
+        }
+    }
+
+    /// 记录一次 CPU 采集结果
+    fn record_cpu(&self, info: &CpuInfo) {
+        self.history.write().cpu.push(info.total_usage);
+    }
+
+    /// 记录一次内存采集结果
+    fn record_memory(&self, info: &MemoryInfo) {
+        self.history.write().memory.push(info.usage_percent);
+    }
+
+    /// 记录一次网络采集结果
+    fn record_network(&self, info: &NetworkInfo) {
+        let mut history = self.history.write();
+        history.upload.push(info.total_upload_speed);
+        history.download.push(info.total_download_speed);
+    }
+
+    /// 用本次速率更新峰值，并把峰值写入 `info`
+    ///
+    /// 休眠唤醒、暂停恢复、重新启用后的第一次采集速率都记为 0，不会产生虚高的峰值。
+    fn track_network_peaks(&self, info: &mut NetworkInfo) {
+        let upload = info.total_upload_speed;
+        let download = info.total_download_speed;
+        info.peak_upload_speed =
+            self.peak_upload_speed.fetch_max(upload, Ordering::SeqCst).max(upload);
+        info.peak_download_speed =
+            self.peak_download_speed.fetch_max(download, Ordering::SeqCst).max(download);
+    }
+
+    /// 把峰值重置为当前速率
+    fn reset_network_peaks(&self) {
+        let mut network = self.network.write();
+        self.peak_upload_speed.store(network.total_upload_speed, Ordering::SeqCst);
+        self.peak_download_speed.store(network.total_download_speed, Ordering::SeqCst);
+        network.peak_upload_speed = network.total_upload_speed;
+        network.peak_download_speed = network.total_download_speed;
+    }
+
+    /// 汇总当前的完整系统信息
+    fn system_info(&self) -> SystemInfo {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+
+        SystemInfo {
+            cpu: self.cpu.read().clone(),
+            memory: self.memory.read().clone(),
+            disk: self.disk.read().clone(),
+            network: self.network.read().clone(),
+            battery: self.battery.read().clone(),
+            uptime_secs: System::uptime(),
+            last_error: self.last_error.read().clone(),
+            timestamp,
+        }
+    }
+
+    /// 调用推送回调（如果已注册）
+    fn emit(&self) {
+        if let Some(emitter) = self.emitter.read().as_ref() {
+            emitter(&self.system_info());
+        }
+    }
+
+    /// 发送告警（如果已注册接收端）
+    fn send_alert(&self, alert: Option<MonitorAlert>) {
+        let Some(alert) = alert else {
+            return;
+        };
+        let mut sender = self.alert_sender.lock();
+        if let Some(tx) = sender.as_ref() {
+            // 接收端已关闭时不再发送
+            if tx.send(alert).is_err() {
+                *sender = None;
+            }
+        }
+    }
+
+    /// 唤醒采集线程，使其立即检查运行状态和最新配置
+    fn wake(&self) {
+        let _guard = self.wakeup_lock.lock();
+        self.wakeup.notify_all();
+    }
+
+    /// 立即刷新所有数据，与并发的刷新请求合并
+    ///
+    /// 已有刷新在进行时等它完成并直接使用其结果，不再重复刷新。
+    /// 返回本次调用是否实际执行了刷新。
+    fn refresh_coalesced(&self) -> bool {
+        let seen = self.refresh_count.load(Ordering::SeqCst);
+        let _guard = self.refresh_lock.lock();
+        if self.refresh_count.load(Ordering::SeqCst) != seen {
+            return false;
+        }
+        self.refresh_all();
+        self.refresh_count.fetch_add(1, Ordering::SeqCst);
+        true
+    }
+
+    /// 立即刷新所有数据（同步操作，会阻塞当前线程）
+    ///
+    /// 复用采集线程的采集器，速率和累计流量与后台采集连续。
+    /// 采集器还没有基准时先预热，等待 [`RESUME_DELAY`] 后再正式采集。
+    fn refresh_all(&self) {
+        let config = self.config.read().clone();
+        let mut collectors = self.collectors.lock();
+        collectors.configure(&config);
+
+        if !collectors.primed {
+            let _ = collectors.cpu.collect();
+            collectors.disk.collect();
+            collectors.network.collect();
+            collectors.process.collect();
+            thread::sleep(RESUME_DELAY);
+        }
+
+        if let Some(info) = self.check("cpu", collectors.cpu.collect()) {
+            self.record_cpu(&info);
+            *self.cpu.write() = info;
+        }
+
+        if let Some(info) = self.check("memory", collectors.memory.collect()) {
+            self.record_memory(&info);
+            *self.memory.write() = info;
+        }
+
+        *self.disk.write() = collectors.disk.collect();
+
+        let mut info = collectors.network.collect();
+        self.record_network(&info);
+        self.track_network_peaks(&mut info);
+        *self.network.write() = info;
+
+        *self.processes.write() = collectors.process.collect();
+
+        collectors.primed = true;
+        drop(collectors);
+
+        if let Some(info) = self.check("battery", BatteryCollector::new().collect()) {
+            *self.battery.write() = info;
         }
     }
 }
@@ -78,17 +369,15 @@ impl Default for MonitorState {
 /// 使用多线程后台采集，各类数据按独立的采集频率更新。
 /// 调用 `get_*` 方法可随时获取最新的监控数据。
 pub struct Monitor {
-    config: MonitorConfig,
     state: Arc<MonitorState>,
     handles: RwLock<Vec<thread::JoinHandle<()>>>,
 }
 
 impl Monitor {
-    /// 使用指定配置创建监控器
+    /// 使用指定配置创建监控器，采集间隔会被限制在下限以上
     pub fn new(config: MonitorConfig) -> Self {
         Self {
-            config,
-            state: Arc::new(MonitorState::default()),
+            state: Arc::new(MonitorState::new(config.clamped())),
             handles: RwLock::new(Vec::new()),
         }
     }
@@ -98,73 +387,238 @@ impl Monitor {
         Self::new(MonitorConfig::default())
     }
 
+    /// 注册推送回调
+    ///
+    /// 后台采集线程每轮采集后，只要有任意数据发生变化，就会以最新的 [`SystemInfo`]
+    /// 调用该回调。回调在采集线程中执行，应尽快返回。
+    pub fn with_emitter<F>(self, emitter: F) -> Self
+    where
+        F: Fn(&SystemInfo) + Send + Sync + 'static,
+    {
+        *self.state.emitter.write() = Some(Box::new(emitter));
+        self
+    }
+
+    /// 注册阈值告警的接收端
+    ///
+    /// 指标越过 [`MonitorConfig::thresholds`] 中的阈值时发送 [`MonitorAlert`]，
+    /// 进入和离开告警状态各发送一次。再次调用会替换之前的接收端。
+    pub fn set_alert_sender(&self, sender: Sender<MonitorAlert>) {
+        *self.state.alert_sender.lock() = Some(sender);
+    }
+
     /// 启动后台采集线程
+    ///
+    /// 可以在 `stop()` 之后再次调用，会重新创建采集线程并从头开始计时。
     pub fn start(&self) {
+        // 先持有句柄锁，保证与 `stop()` 串行执行
+        let mut handles = self.handles.write();
+
         if self.state.running.swap(true, Ordering::SeqCst) {
             // 已经在运行
             return;
         }
 
-        let mut handles = self.handles.write();
+        // 回收上一次运行遗留的线程句柄
+        for handle in handles.drain(..) {
+            let _ = handle.join();
+        }
 
         // 使用单线程轮询所有采集器，减少线程数量
         let state = Arc::clone(&self.state);
-        let cpu_interval = self.config.cpu_interval;
-        let memory_interval = self.config.memory_interval;
-        let disk_interval = self.config.disk_interval;
-        let network_interval = self.config.network_interval;
 
         let handle = thread::spawn(move || {
-            let mut cpu_collector = CpuCollector::new();
-            let mut memory_collector = MemoryCollector::new();
-            let mut disk_collector = DiskCollector::new();
-            let mut network_collector = NetworkCollector::new();
-
-            // 初始采集一次
-            thread::sleep(std::time::Duration::from_millis(100));
-
-            // 使用计时器追踪每个采集器的下次执行时间
-            let tick_interval = std::time::Duration::from_millis(100); // 基础轮询间隔
-            let mut cpu_countdown = std::time::Duration::ZERO;
-            let mut memory_countdown = std::time::Duration::ZERO;
-            let mut disk_countdown = std::time::Duration::ZERO;
-            let mut network_countdown = std::time::Duration::ZERO;
-
-            while state.running.load(Ordering::SeqCst) {
-                // CPU 采集
-                if cpu_countdown <= std::time::Duration::ZERO {
-                    let info = cpu_collector.collect();
-                    *state.cpu.write() = info;
-                    cpu_countdown = cpu_interval;
+            let mut battery_collector = BatteryCollector::new();
+            let mut alerts = AlertTracker::new();
+
+            // 记录每个采集器的下次执行时间
+            let start = Instant::now();
+            let mut cpu_due = start;
+            let mut memory_due = start;
+            let mut disk_due = start;
+            let mut network_due = start;
+            let mut process_due = start;
+            let mut battery_due = start;
+            // 上一轮启用的指标，用于发现重新启用的采集器；
+            // 采集器可能保留着上次运行的基准，启动时按重新启用处理
+            let mut enabled = EnabledMetrics {
+                cpu: false,
+                memory: false,
+                disk: false,
+                network: false,
+                battery: false,
+            };
+
+            loop {
+                // 暂停期间一直休眠，直到 `resume()` 或 `stop()` 唤醒
+                if state.paused.load(Ordering::SeqCst) {
+                    if !state.sleep_until(Instant::now() + Duration::from_secs(60 * 60)) {
+                        break;
+                    }
+                    // 恢复后按重新启用处理：CPU、磁盘、网络先刷新基准，
+                    // 避免把整个暂停期间的增量算进第一次的速率
+                    enabled = EnabledMetrics {
+                        cpu: false,
+                        memory: false,
+                        disk: false,
+                        network: false,
+                        battery: false,
+                    };
+                    continue;
                 }
 
-                // 内存采集
-                if memory_countdown <= std::time::Duration::ZERO {
-                    let info = memory_collector.collect();
-                    *state.memory.write() = info;
-                    memory_countdown = memory_interval;
+                // 每轮读取最新配置；间隔被调小时，下次执行时间不晚于当前时间加新间隔
+                let config = state.config.read().clone();
+                let now = Instant::now();
+                cpu_due = cpu_due.min(now + config.cpu_interval);
+                memory_due = memory_due.min(now + config.memory_interval);
+                disk_due = disk_due.min(now + config.disk_interval);
+                network_due = network_due.min(now + config.network_interval);
+                process_due = process_due.min(now + config.process_interval);
+                battery_due = battery_due.min(now + config.battery_interval);
+                let mut collectors = state.collectors.lock();
+                collectors.configure(&config);
+                let Collectors {
+                    cpu: cpu_collector,
+                    memory: memory_collector,
+                    disk: disk_collector,
+                    network: network_collector,
+                    process: process_collector,
+                    primed,
+                } = &mut *collectors;
+                state.history.write().set_capacity(config.history_len);
+                let mut changed = false;
+
+                // CPU 采集，隐藏时跳过；重新启用时先刷新基准
+                if !config.enabled.cpu {
+                    cpu_due = now + config.cpu_interval;
+                } else if !enabled.cpu {
+                    let _ = cpu_collector.collect();
+                    cpu_due = now + RESUME_DELAY;
+                } else if now >= cpu_due {
+                    if let Some(info) = state.check("cpu", cpu_collector.collect()) {
+                        state.record_cpu(&info);
+                        state.send_alert(alerts.check(
+                            AlertKind::Cpu,
+                            info.total_usage as f64,
+                            config.thresholds.cpu,
+                        ));
+                        changed |= MonitorState::update(&state.cpu, info);
+                    }
+                    cpu_due = now + config.cpu_interval;
+                }
+
+                // 内存采集，重新启用时立即采集
+                if !config.enabled.memory {
+                    memory_due = now + config.memory_interval;
+                } else if !enabled.memory || now >= memory_due {
+                    if let Some(info) = state.check("memory", memory_collector.collect()) {
+                        state.record_memory(&info);
+                        state.send_alert(alerts.check(
+                            AlertKind::Memory,
+                            info.usage_percent as f64,
+                            config.thresholds.memory,
+                        ));
+                        state.send_alert(alerts.check_sustained(
+                            AlertKind::Swap,
+                            info.swap_usage_percent as f64,
+                            config.thresholds.swap,
+                            config.swap_alert_samples,
+                        ));
+                        changed |= MonitorState::update(&state.memory, info);
+                    }
+                    memory_due = now + config.memory_interval;
                 }
 
                 // 磁盘采集
-                if disk_countdown <= std::time::Duration::ZERO {
+                if !config.enabled.disk {
+                    disk_due = now + config.disk_interval;
+                } else if !enabled.disk {
+                    disk_collector.collect();
+                    disk_due = now + RESUME_DELAY;
+                } else if now >= disk_due {
                     let info = disk_collector.collect();
-                    *state.disk.write() = info;
-                    disk_countdown = disk_interval;
+                    let fullest = info
+                        .disks
+                        .iter()
+                        .map(|disk| disk.usage_percent)
+                        .fold(0.0, f32::max);
+                    state.send_alert(alerts.check(
+                        AlertKind::Disk,
+                        fullest as f64,
+                        config.thresholds.disk,
+                    ));
+                    for alert in alerts.check_disks(&info.disks, config.thresholds.disk_full) {
+                        state.send_alert(Some(alert));
+                    }
+                    changed |= MonitorState::update(&state.disk, info);
+                    disk_due = now + config.disk_interval;
                 }
 
                 // 网络采集
-                if network_countdown <= std::time::Duration::ZERO {
-                    let info = network_collector.collect();
-                    *state.network.write() = info;
-                    network_countdown = network_interval;
+                if !config.enabled.network {
+                    network_due = now + config.network_interval;
+                } else if !enabled.network {
+                    network_collector.collect();
+                    network_due = now + RESUME_DELAY;
+                } else if now >= network_due {
+                    let mut info = network_collector.collect();
+                    state.record_network(&info);
+                    state.track_network_peaks(&mut info);
+                    state.send_alert(alerts.check(
+                        AlertKind::Network,
+                        (info.total_upload_speed + info.total_download_speed) as f64,
+                        config.thresholds.network,
+                    ));
+                    changed |= MonitorState::update(&state.network, info);
+                    network_due = now + config.network_interval;
+                }
+
+                // 进程采集（不包含在推送数据中）
+                if now >= process_due {
+                    let info = process_collector.collect();
+                    *state.processes.write() = info;
+                    process_due = now + config.process_interval;
                 }
 
-                // 等待并更新倒计时
-                thread::sleep(tick_interval);
-                cpu_countdown = cpu_countdown.saturating_sub(tick_interval);
-                memory_countdown = memory_countdown.saturating_sub(tick_interval);
-                disk_countdown = disk_countdown.saturating_sub(tick_interval);
-                network_countdown = network_countdown.saturating_sub(tick_interval);
+                // 电池采集
+                if !config.enabled.battery {
+                    battery_due = now + config.battery_interval;
+                } else if !enabled.battery || now >= battery_due {
+                    if let Some(info) = state.check("battery", battery_collector.collect()) {
+                        changed |= MonitorState::update(&state.battery, info);
+                    }
+                    battery_due = now + config.battery_interval;
+                }
+
+                enabled = config.enabled;
+                *primed = enabled.cpu && enabled.disk && enabled.network;
+                drop(collectors);
+
+                // 推送变化
+                if changed {
+                    state.emit();
+                }
+
+                // 休眠到最近的下次执行时间，`stop()` 或 `set_config()` 会提前唤醒
+                let next_due = cpu_due
+                    .min(memory_due)
+                    .min(disk_due)
+                    .min(network_due)
+                    .min(process_due)
+                    .min(battery_due);
+                let expected_wake =
+                    SystemTime::now() + next_due.saturating_duration_since(Instant::now());
+                if !state.sleep_until(next_due) {
+                    break;
+                }
+
+                // 醒来的墙上时间远晚于预期，说明期间系统休眠过，丢弃网络、磁盘的速率基准
+                if rate::overslept(expected_wake, SystemTime::now()) {
+                    let mut collectors = state.collectors.lock();
+                    collectors.network.reset_baseline();
+                    collectors.disk.reset_baseline();
+                }
             }
         });
         handles.push(handle);
@@ -172,13 +626,50 @@ impl Monitor {
 
     /// 停止后台采集线程
     pub fn stop(&self) {
+        let mut handles = self.handles.write();
         self.state.running.store(false, Ordering::SeqCst);
-        
+        self.state.wake();
+
         // 等待所有线程结束
-        let mut handles = self.handles.write();
         for handle in handles.drain(..) {
             let _ = handle.join();
         }
+        self.state.collectors.lock().primed = false;
+    }
+
+    /// 暂停采集
+    ///
+    /// 与 `stop()` 不同，采集线程和各采集器都会保留，恢复时 CPU、网络、磁盘
+    /// 会先刷新基准再计算速率。暂停期间 `get_*` 返回最后一次采集的数据。
+    pub fn pause(&self) {
+        self.state.paused.store(true, Ordering::SeqCst);
+        self.state.collectors.lock().primed = false;
+    }
+
+    /// 恢复采集，到期的采集器会立即执行一次
+    pub fn resume(&self) {
+        self.state.paused.store(false, Ordering::SeqCst);
+        self.state.wake();
+    }
+
+    /// 检查采集是否已暂停
+    pub fn is_paused(&self) -> bool {
+        self.state.paused.load(Ordering::SeqCst)
+    }
+
+    /// 获取当前配置
+    pub fn config(&self) -> MonitorConfig {
+        self.state.config.read().clone()
+    }
+
+    /// 更新配置，运行中的采集线程会在下一轮生效，无需重启
+    ///
+    /// 采集间隔会被限制在下限以上，返回实际生效的配置。
+    pub fn set_config(&self, config: MonitorConfig) -> MonitorConfig {
+        let config = config.clamped();
+        *self.state.config.write() = config.clone();
+        self.state.wake();
+        config
     }
 
     /// 检查监控器是否正在运行
@@ -188,11 +679,13 @@ impl Monitor {
     }
 
     /// 获取 CPU 信息
+    #[allow(dead_code)]
     pub fn get_cpu_info(&self) -> CpuInfo {
         self.state.cpu.read().clone()
     }
 
     /// 获取内存信息
+    #[allow(dead_code)]
     pub fn get_memory_info(&self) -> MemoryInfo {
         self.state.memory.read().clone()
     }
@@ -207,51 +700,75 @@ impl Monitor {
         self.state.network.read().clone()
     }
 
+    /// 把网络速率峰值重置为当前速率，并推送更新后的数据
+    pub fn reset_network_peaks(&self) {
+        self.state.reset_network_peaks();
+        self.state.emit();
+    }
+
+    /// 获取资源占用最高的前 `n` 个进程
+    pub fn get_top_processes(&self, n: usize) -> Vec<ProcessInfo> {
+        self.state.processes.read().iter().take(n).cloned().collect()
+    }
+
+    /// 获取电池信息，没有电池时返回 `None`
+    pub fn get_battery_info(&self) -> Option<BatteryInfo> {
+        self.state.battery.read().clone()
+    }
+
+    /// 获取最近 `n` 次的 CPU 总使用率，按时间从旧到新排列
+    #[allow(dead_code)]
+    pub fn get_cpu_history(&self, n: usize) -> Vec<f32> {
+        self.state.history.read().cpu.latest(n)
+    }
+
+    /// 获取最近 `n` 次的内存使用率，按时间从旧到新排列
+    #[allow(dead_code)]
+    pub fn get_memory_history(&self, n: usize) -> Vec<f32> {
+        self.state.history.read().memory.latest(n)
+    }
+
+    /// 获取最近 `n` 次的总上传、下载速率，按时间从旧到新排列
+    #[allow(dead_code)]
+    pub fn get_network_history(&self, n: usize) -> (Vec<u64>, Vec<u64>) {
+        let history = self.state.history.read();
+        (history.upload.latest(n), history.download.latest(n))
+    }
+
+    /// 获取最近 `n` 次的全部历史数据
+    pub fn get_history(&self, n: usize) -> HistoryInfo {
+        self.state.history.read().latest(n)
+    }
+
+    /// 获取最近一次尚未恢复的采集错误
+    #[allow(dead_code)]
+    pub fn last_error(&self) -> Option<MonitorError> {
+        self.state.last_error.read().clone()
+    }
+
     /// 获取完整的系统信息
     pub fn get_system_info(&self) -> SystemInfo {
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map(|d| d.as_millis() as u64)
-            .unwrap_or(0);
-
-        SystemInfo {
-            cpu: self.get_cpu_info(),
-            memory: self.get_memory_info(),
-            disk: self.get_disk_info(),
-            network: self.get_network_info(),
-            timestamp,
-        }
+        self.state.system_info()
     }
 
     /// 立即刷新所有数据（同步操作，会阻塞当前线程）
     pub fn refresh_all(&self) {
-        // CPU
-        {
-            let mut collector = CpuCollector::new();
-            thread::sleep(std::time::Duration::from_millis(100));
-            let info = collector.collect();
-            *self.state.cpu.write() = info;
-        }
-
-        // Memory
-        {
-            let mut collector = MemoryCollector::new();
-            let info = collector.collect();
-            *self.state.memory.write() = info;
-        }
-
-        // Disk
-        {
-            let mut collector = DiskCollector::new();
-            let info = collector.collect();
-            *self.state.disk.write() = info;
-        }
+        self.state.refresh_all();
+    }
 
-        // Network
-        {
-            let mut collector = NetworkCollector::new();
-            let info = collector.collect();
-            *self.state.network.write() = info;
+    /// 返回一个立即刷新所有数据并返回最新 `SystemInfo` 的函数
+    ///
+    /// 采集器还没有基准时刷新会阻塞 200ms 以上，返回的函数只持有共享状态，可以交给后台线程执行，
+    /// 不必在刷新期间一直持有 `Monitor` 的锁。刷新后会调用推送回调。
+    ///
+    /// 多个函数同时执行时会合并为一次刷新，只推送一次。
+    pub fn refresher(&self) -> impl FnOnce() -> SystemInfo + Send + 'static {
+        let state = Arc::clone(&self.state);
+        move || {
+            if state.refresh_coalesced() {
+                state.emit();
+            }
+            state.system_info()
         }
     }
 }
@@ -269,7 +786,6 @@ impl Default for Monitor {
 }
 
 /// 便捷函数：一次性获取系统信息（不启动后台线程）
-#[allow(dead_code)]
 pub fn get_system_info_once() -> SystemInfo {
     let monitor = Monitor::with_default_config();
     monitor.refresh_all();
@@ -279,7 +795,6 @@ pub fn get_system_info_once() -> SystemInfo {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::time::Duration;
 
     #[test]
     fn test_monitor_basic() {
@@ -305,9 +820,269 @@ mod tests {
         assert!(!monitor.is_running());
     }
 
+    #[test]
+    fn test_monitor_restart() {
+        let config = MonitorConfig::new().memory_interval(Duration::from_millis(200));
+        let monitor = Monitor::new(config);
+
+        monitor.start();
+        thread::sleep(Duration::from_millis(300));
+        monitor.stop();
+        assert!(!monitor.is_running());
+
+        // 清空数据，确认第二次启动后重新采集
+        *monitor.state.memory.write() = MemoryInfo::default();
+        monitor.start();
+        assert!(monitor.is_running());
+        thread::sleep(Duration::from_millis(500));
+        assert!(monitor.get_memory_info().total > 0);
+
+        monitor.stop();
+    }
+
+    #[test]
+    fn test_stop_wakes_immediately() {
+        let monitor = Monitor::with_default_config();
+        monitor.start();
+        thread::sleep(Duration::from_millis(500));
+
+        let begin = Instant::now();
+        monitor.stop();
+        assert!(begin.elapsed() < Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_pause_and_resume() {
+        let config = MonitorConfig::new().memory_interval(Duration::from_millis(100));
+        let monitor = Monitor::new(config);
+        monitor.start();
+        thread::sleep(Duration::from_millis(300));
+
+        monitor.pause();
+        assert!(monitor.is_paused());
+        thread::sleep(Duration::from_millis(150));
+        *monitor.state.memory.write() = MemoryInfo::default();
+        thread::sleep(Duration::from_millis(300));
+        assert_eq!(monitor.get_memory_info(), MemoryInfo::default());
+
+        monitor.resume();
+        thread::sleep(Duration::from_millis(200));
+        assert!(monitor.get_memory_info().total > 0);
+
+        // 暂停期间停止也应立即返回
+        monitor.pause();
+        let begin = Instant::now();
+        monitor.stop();
+        assert!(begin.elapsed() < Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_disabled_metric_stops_updating() {
+        let config = MonitorConfig::new().network_interval(Duration::from_millis(100));
+        let monitor = Monitor::new(config.clone());
+        monitor.start();
+        thread::sleep(Duration::from_millis(300));
+
+        monitor.set_config(config.clone().enabled(EnabledMetrics {
+            network: false,
+            ..EnabledMetrics::default()
+        }));
+        thread::sleep(Duration::from_millis(150));
+        let sentinel = NetworkInfo {
+            total_uploaded: u64::MAX,
+            ..NetworkInfo::default()
+        };
+        *monitor.state.network.write() = sentinel.clone();
+        thread::sleep(Duration::from_millis(400));
+        assert_eq!(monitor.get_network_info(), sentinel);
+
+        // 重新启用后恢复采集
+        monitor.set_config(config);
+        thread::sleep(Duration::from_millis(500));
+        assert_ne!(monitor.get_network_info(), sentinel);
+
+        monitor.stop();
+    }
+
+    #[test]
+    fn test_error_cleared_by_same_collector() {
+        let state = MonitorState::new(MonitorConfig::default());
+
+        assert_eq!(state.check::<()>("cpu", Err(MonitorError::NoCpu)), None);
+        assert_eq!(*state.last_error.read(), Some(MonitorError::NoCpu));
+
+        // 其他采集器成功不影响 CPU 的错误
+        assert_eq!(state.check("memory", Ok(1)), Some(1));
+        assert_eq!(state.system_info().last_error, Some(MonitorError::NoCpu));
+
+        assert_eq!(state.check("cpu", Ok(2)), Some(2));
+        assert_eq!(*state.last_error.read(), None);
+    }
+
+    #[test]
+    fn test_network_peaks() {
+        let state = MonitorState::new(MonitorConfig::default());
+        let sample = |upload, download| {
+            let mut info = NetworkInfo {
+                total_upload_speed: upload,
+                total_download_speed: download,
+                ..NetworkInfo::default()
+            };
+            state.track_network_peaks(&mut info);
+            *state.network.write() = info.clone();
+            (info.peak_upload_speed, info.peak_download_speed)
+        };
+
+        assert_eq!(sample(100, 5_000), (100, 5_000));
+        assert_eq!(sample(300, 1_000), (300, 5_000));
+        // 唤醒后的第一次采集记为 0，不影响峰值
+        assert_eq!(sample(0, 0), (300, 5_000));
+
+        sample(50, 200);
+        state.reset_network_peaks();
+        let network = state.network.read().clone();
+        assert_eq!((network.peak_upload_speed, network.peak_download_speed), (50, 200));
+        assert_eq!(sample(20, 800), (50, 800));
+    }
+
+    #[test]
+    fn test_set_config_while_running() {
+        let config = MonitorConfig::new().memory_interval(Duration::from_secs(60));
+        let monitor = Monitor::new(config);
+        monitor.start();
+        thread::sleep(Duration::from_millis(300));
+
+        // 清空数据，缩短间隔后应在不重启的情况下重新采集
+        *monitor.state.memory.write() = MemoryInfo::default();
+        monitor.set_config(monitor.config().memory_interval(Duration::from_millis(200)));
+        thread::sleep(Duration::from_millis(500));
+        assert!(monitor.get_memory_info().total > 0);
+
+        monitor.stop();
+    }
+
+    #[test]
+    fn test_interval_floor() {
+        let config = MonitorConfig::new()
+            .cpu_interval(Duration::from_millis(5))
+            .disk_interval(Duration::from_millis(5))
+            .network_interval(Duration::from_secs(2));
+        assert_eq!(config.cpu_interval, MIN_FAST_INTERVAL);
+        assert_eq!(config.disk_interval, MIN_SLOW_INTERVAL);
+        assert_eq!(config.network_interval, Duration::from_secs(2));
+
+        // 直接修改字段的配置在 `set_config` 时限制
+        let monitor = Monitor::with_default_config();
+        let mut config = monitor.config();
+        config.memory_interval = Duration::ZERO;
+        config.battery_interval = Duration::from_millis(10);
+        let effective = monitor.set_config(config);
+        assert_eq!(effective.memory_interval, MIN_FAST_INTERVAL);
+        assert_eq!(effective.battery_interval, MIN_SLOW_INTERVAL);
+        assert_eq!(monitor.config().memory_interval, MIN_FAST_INTERVAL);
+    }
+
+    #[test]
+    fn test_with_emitter() {
+        use std::sync::atomic::AtomicUsize;
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let monitor = Monitor::new(MonitorConfig::new().memory_interval(Duration::from_millis(200)))
+            .with_emitter({
+                let count = Arc::clone(&count);
+                move |_| {
+                    count.fetch_add(1, Ordering::SeqCst);
+                }
+            });
+        monitor.start();
+        thread::sleep(Duration::from_millis(500));
+        monitor.stop();
+
+        assert!(count.load(Ordering::SeqCst) > 0);
+    }
+
+    #[test]
+    fn test_get_top_processes() {
+        let monitor = Monitor::new(MonitorConfig::new().process_top_n(5));
+        monitor.refresh_all();
+
+        let processes = monitor.get_top_processes(3);
+        assert!(processes.len() <= 3);
+        assert!(processes
+            .windows(2)
+            .all(|pair| pair[0].cpu_usage >= pair[1].cpu_usage));
+        assert!(processes.iter().all(|p| p.cpu_usage <= 100.0));
+    }
+
     #[test]
     fn test_get_system_info_once() {
         let info = get_system_info_once();
         assert!(info.memory.total > 0);
     }
+
+    #[test]
+    fn test_refresher_on_another_thread() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let monitor = Monitor::with_default_config().with_emitter(move |info| {
+            let _ = tx.send(info.memory.total);
+        });
+        let info = thread::spawn(monitor.refresher()).join().unwrap();
+        assert!(info.memory.total > 0);
+        assert_eq!(monitor.get_memory_info().total, info.memory.total);
+        assert_eq!(rx.try_recv(), Ok(info.memory.total));
+    }
+
+    #[test]
+    fn test_refresh_all_reuses_collectors() {
+        let monitor = Monitor::with_default_config();
+        monitor.start();
+        thread::sleep(Duration::from_millis(500));
+        assert!(monitor.state.collectors.lock().primed);
+
+        // 手动刷新沿用采集线程的累计流量，不会回退
+        let before = monitor.get_network_info();
+        monitor.refresh_all();
+        let after = monitor.get_network_info();
+        assert!(after.total_uploaded >= before.total_uploaded);
+        assert!(after.total_downloaded >= before.total_downloaded);
+
+        // 暂停、停止后基准过期，下一次手动刷新需要重新预热
+        monitor.pause();
+        assert!(!monitor.state.collectors.lock().primed);
+        monitor.stop();
+        monitor.refresh_all();
+        assert!(monitor.state.collectors.lock().primed);
+    }
+
+    #[test]
+    fn test_concurrent_refreshers_coalesce() {
+        use std::sync::atomic::AtomicUsize;
+        use std::sync::Barrier;
+
+        const REQUESTS: usize = 4;
+        let emits = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&emits);
+        let monitor = Monitor::with_default_config().with_emitter(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let barrier = Arc::new(Barrier::new(REQUESTS));
+        let handles: Vec<_> = (0..REQUESTS)
+            .map(|_| {
+                let refresh = monitor.refresher();
+                let barrier = Arc::clone(&barrier);
+                thread::spawn(move || {
+                    barrier.wait();
+                    refresh()
+                })
+            })
+            .collect();
+        for handle in handles {
+            assert!(handle.join().unwrap().memory.total > 0);
+        }
+
+        // 第一次刷新进行期间到达的请求都应复用它的结果
+        let emits = emits.load(Ordering::SeqCst);
+        assert!((1..REQUESTS).contains(&emits), "emits: {}", emits);
+    }
 }
@@ -1,15 +1,15 @@
 //! 网络信息采集模块
 
+use crate::monitor::rate::{rate_per_sec, SampleTime};
 use crate::monitor::types::{NetworkInfo, NetworkInterfaceInfo};
 use sysinfo::Networks;
 use std::collections::HashMap;
-use std::time::Instant;
 
 /// 网络接口上一次的数据快照
 struct NetworkSnapshot {
     received: u64,
     transmitted: u64,
-    timestamp: Instant,
+    timestamp: SampleTime,
 }
 
 /// 网络采集器
@@ -17,6 +17,12 @@ pub struct NetworkCollector {
     networks: Networks,
     /// 存储上一次各接口的数据，用于计算速率
     last_snapshot: HashMap<String, NetworkSnapshot>,
+    /// 累计上传字节数，只按观测到的增量累加，不受接口重建影响
+    accumulated_uploaded: u64,
+    /// 累计下载字节数，只按观测到的增量累加，不受接口重建影响
+    accumulated_downloaded: u64,
+    /// 为 true 时下一次采集不计算速率，见 [`NetworkCollector::reset_baseline`]
+    baseline_reset: bool,
 }
 
 impl NetworkCollector {
@@ -26,37 +32,50 @@ impl NetworkCollector {
         Self {
             networks,
             last_snapshot: HashMap::new(),
+            accumulated_uploaded: 0,
+            accumulated_downloaded: 0,
+            baseline_reset: false,
         }
     }
 
+    /// 作废各接口的上一次快照，下一次采集的速率记为 0
+    ///
+    /// 系统休眠唤醒后调用，避免用休眠前的快照计算速率。快照中的计数器仍用于
+    /// 累加流量，直接清空会把接口的全部计数重复计入累计值。
+    pub fn reset_baseline(&mut self) {
+        self.baseline_reset = true;
+    }
+
     /// 采集网络信息
     pub fn collect(&mut self) -> NetworkInfo {
         self.networks.refresh(true);
         
-        let now = Instant::now();
+        let now = SampleTime::now();
         let mut interfaces: Vec<NetworkInterfaceInfo> = Vec::new();
         let mut total_upload_speed: u64 = 0;
         let mut total_download_speed: u64 = 0;
-        let mut total_uploaded: u64 = 0;
-        let mut total_downloaded: u64 = 0;
 
         for (name, network) in self.networks.iter() {
             let current_received = network.total_received();
             let current_transmitted = network.total_transmitted();
 
-            // 计算速率
-            let (download_speed, upload_speed) = if let Some(last) = self.last_snapshot.get(name) {
-                let elapsed = now.duration_since(last.timestamp).as_secs_f64();
-                if elapsed > 0.0 {
-                    let download = ((current_received.saturating_sub(last.received)) as f64 / elapsed) as u64;
-                    let upload = ((current_transmitted.saturating_sub(last.transmitted)) as f64 / elapsed) as u64;
-                    (download, upload)
+            // 累加计数器增量
+            let last = self.last_snapshot.get(name);
+            self.accumulated_downloaded += counter_delta(last.map(|s| s.received), current_received);
+            self.accumulated_uploaded +=
+                counter_delta(last.map(|s| s.transmitted), current_transmitted);
+
+            // 计算速率，休眠唤醒或长时间未采样后的第一次记为 0
+            let (download_speed, upload_speed) =
+                if let Some(last) = last.filter(|_| !self.baseline_reset) {
+                    let secs = now.secs_since(&last.timestamp);
+                    (
+                        rate_per_sec(last.received, current_received, secs),
+                        rate_per_sec(last.transmitted, current_transmitted, secs),
+                    )
                 } else {
                     (0, 0)
-                }
-            } else {
-                (0, 0)
-            };
+                };
 
             // 更新快照
             self.last_snapshot.insert(name.clone(), NetworkSnapshot {
@@ -75,24 +94,59 @@ impl NetworkCollector {
 
             total_upload_speed += upload_speed;
             total_download_speed += download_speed;
-            total_uploaded += current_transmitted;
-            total_downloaded += current_received;
 
             interfaces.push(interface_info);
         }
 
+        self.baseline_reset = false;
+
         NetworkInfo {
             interfaces,
             total_upload_speed,
             total_download_speed,
-            total_uploaded,
-            total_downloaded,
+            total_uploaded: self.accumulated_uploaded,
+            total_downloaded: self.accumulated_downloaded,
+            // 峰值由 `Monitor` 跨采集器统计
+            peak_upload_speed: 0,
+            peak_download_speed: 0,
         }
     }
 }
 
+/// 计算接口计数器在两次采集之间的增量
+///
+/// 首次出现的接口计入其全部计数；计数器变小说明接口被重建、计数从 0 重新开始，
+/// 此时当前值就是重建后的增量。
+fn counter_delta(last: Option<u64>, current: u64) -> u64 {
+    match last {
+        Some(last) if current >= last => current - last,
+        _ => current,
+    }
+}
+
 impl Default for NetworkCollector {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counter_delta_with_reset() {
+        // 模拟一个接口的计数器序列：正常增长、接口重建后归零再增长
+        let samples = [1_000, 1_500, 2_000, 300, 800];
+        let mut last = None;
+        let mut accumulated = 0;
+        let mut totals = Vec::new();
+        for current in samples {
+            accumulated += counter_delta(last, current);
+            last = Some(current);
+            totals.push(accumulated);
+        }
+        assert_eq!(totals, vec![1_000, 1_500, 2_000, 2_300, 2_800]);
+        assert!(totals.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+}
@@ -0,0 +1,74 @@
+//! 进程信息采集模块
+
+use crate::monitor::types::{MonitorConfig, ProcessInfo};
+use sysinfo::{CpuRefreshKind, ProcessRefreshKind, ProcessesToUpdate, RefreshKind, System};
+
+/// 进程采集器
+pub struct ProcessCollector {
+    system: System,
+    /// 保留的进程数量上限
+    top_n: usize,
+    /// 逻辑核心数，用于归一化进程 CPU 使用率
+    cpu_count: usize,
+}
+
+impl ProcessCollector {
+    /// 创建新的进程采集器
+    pub fn new(top_n: usize) -> Self {
+        // 只刷新进程的 CPU 与内存信息，减少不必要的开销
+        let mut system = System::new_with_specifics(
+            RefreshKind::nothing().with_processes(Self::refresh_kind()),
+        );
+        system.refresh_cpu_list(CpuRefreshKind::nothing());
+        let cpu_count = system.cpus().len().max(1);
+
+        Self {
+            system,
+            top_n,
+            cpu_count,
+        }
+    }
+
+    /// 调整保留的进程数量上限
+    pub fn set_top_n(&mut self, top_n: usize) {
+        self.top_n = top_n;
+    }
+
+    fn refresh_kind() -> ProcessRefreshKind {
+        ProcessRefreshKind::nothing().with_cpu().with_memory()
+    }
+
+    /// 采集进程信息，按 CPU 使用率（其次内存）降序排列，最多返回 `top_n` 个
+    pub fn collect(&mut self) -> Vec<ProcessInfo> {
+        self.system
+            .refresh_processes_specifics(ProcessesToUpdate::All, true, Self::refresh_kind());
+
+        let cpu_count = self.cpu_count as f32;
+        let mut processes: Vec<ProcessInfo> = self.system.processes()
+            .values()
+            .map(|process| ProcessInfo {
+                pid: process.pid().as_u32(),
+                name: process.name().to_string_lossy().to_string(),
+                // sysinfo 的进程使用率按单核计算（可超过 100%），
+                // 与 CpuCollector 的总体使用率保持一致，按核心数归一化到 0.0 - 100.0
+                cpu_usage: (process.cpu_usage() / cpu_count).min(100.0),
+                memory: process.memory(),
+                parent_pid: process.parent().map(|pid| pid.as_u32()),
+            })
+            .collect();
+
+        processes.sort_by(|a, b| {
+            b.cpu_usage
+                .total_cmp(&a.cpu_usage)
+                .then_with(|| b.memory.cmp(&a.memory))
+        });
+        processes.truncate(self.top_n);
+        processes
+    }
+}
+
+impl Default for ProcessCollector {
+    fn default() -> Self {
+        Self::new(MonitorConfig::default().process_top_n)
+    }
+}
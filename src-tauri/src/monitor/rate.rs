@@ -0,0 +1,102 @@
+//! 网络、磁盘速率计算
+//!
+//! 速率由两次快照的计数器差值除以采样间隔得出。下面几种情况得不到可信的速率，
+//! 本次记为 0，下一次采集恢复正常：
+//!
+//! - 间隔超过 [`MAX_SAMPLE_GAP`]：长时间没有采集（如暂停后），平均值已无意义
+//! - 休眠唤醒：单调时钟可能不计入休眠时间，间隔很短而计数器已大幅前进，
+//!   通过墙上时钟比单调时钟多走了超过 [`MAX_CLOCK_DRIFT`] 识别
+//! - 计数器变小：接口或磁盘被重建，计数从 0 重新开始
+
+use std::time::{Duration, Instant, SystemTime};
+
+/// 两次采样之间允许的最长间隔
+pub const MAX_SAMPLE_GAP: Duration = Duration::from_secs(5 * 60);
+/// 墙上时钟比单调时钟多走的时间超过该值时，认为期间系统休眠过
+pub const MAX_CLOCK_DRIFT: Duration = Duration::from_secs(5);
+
+/// 采样时刻，同时记录单调时钟和墙上时钟
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SampleTime {
+    instant: Instant,
+    wall: SystemTime,
+}
+
+impl SampleTime {
+    pub fn now() -> Self {
+        Self {
+            instant: Instant::now(),
+            wall: SystemTime::now(),
+        }
+    }
+
+    /// 距离 `earlier` 的秒数，间隔为 0、过长或期间发生过休眠时返回 `None`
+    pub fn secs_since(&self, earlier: &SampleTime) -> Option<f64> {
+        let elapsed = self.instant.saturating_duration_since(earlier.instant);
+        if elapsed.is_zero() || elapsed > MAX_SAMPLE_GAP {
+            return None;
+        }
+        // 墙上时钟回拨时无法判断，只按单调时钟处理
+        if let Ok(wall_elapsed) = self.wall.duration_since(earlier.wall) {
+            if wall_elapsed.saturating_sub(elapsed) > MAX_CLOCK_DRIFT {
+                return None;
+            }
+        }
+        Some(elapsed.as_secs_f64())
+    }
+}
+
+/// 采集线程醒来的时间比预期晚了超过 [`MAX_CLOCK_DRIFT`]，说明期间系统休眠过
+pub fn overslept(expected: SystemTime, now: SystemTime) -> bool {
+    now.duration_since(expected).is_ok_and(|late| late > MAX_CLOCK_DRIFT)
+}
+
+/// 计数器从 `last` 到 `current` 的每秒速率，间隔不可信或计数器变小时为 0
+pub fn rate_per_sec(last: u64, current: u64, secs: Option<f64>) -> u64 {
+    match secs {
+        Some(secs) if current >= last => ((current - last) as f64 / secs) as u64,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_after_long_gap() {
+        let start = SampleTime::now();
+        let at = |secs: u64, wall_secs: u64| SampleTime {
+            instant: start.instant + Duration::from_secs(secs),
+            wall: start.wall + Duration::from_secs(wall_secs),
+        };
+
+        // 正常间隔
+        let secs = at(2, 2).secs_since(&start);
+        assert_eq!(secs, Some(2.0));
+        assert_eq!(rate_per_sec(1_000, 3_000, secs), 1_000);
+
+        // 两次快照相隔 10 分钟，累计的 6 GB 不应折算成速率
+        let secs = at(600, 600).secs_since(&start);
+        assert_eq!(secs, None);
+        assert_eq!(rate_per_sec(0, 6_000_000_000, secs), 0);
+
+        // 休眠 10 分钟：单调时钟只走了 1 秒，墙上时钟走了 10 分钟
+        let secs = at(1, 600).secs_since(&start);
+        assert_eq!(secs, None);
+        assert_eq!(rate_per_sec(0, 6_000_000_000, secs), 0);
+
+        // 计数器变小
+        assert_eq!(rate_per_sec(5_000, 100, Some(1.0)), 0);
+        // 同一时刻重复采样
+        assert_eq!(start.secs_since(&start), None);
+    }
+
+    #[test]
+    fn test_overslept() {
+        let expected = SystemTime::now();
+        assert!(!overslept(expected, expected + Duration::from_secs(1)));
+        assert!(!overslept(expected, expected - Duration::from_secs(60)));
+        assert!(overslept(expected, expected + Duration::from_secs(600)));
+    }
+}
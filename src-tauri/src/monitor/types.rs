@@ -1,10 +1,11 @@
 //! 系统监控数据类型定义
 
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::time::Duration;
 
 /// CPU 核心信息
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CpuCoreInfo {
     /// 核心名称
     pub name: String,
@@ -12,10 +13,12 @@ pub struct CpuCoreInfo {
     pub usage: f32,
     /// 频率 (MHz)
     pub frequency: u64,
+    /// 核心温度 (摄氏度)，没有对应传感器时为 `None`
+    pub temperature: Option<f32>,
 }
 
 /// CPU 整体信息
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CpuInfo {
     /// 品牌名称
     pub brand: String,
@@ -23,10 +26,20 @@ pub struct CpuInfo {
     pub total_usage: f32,
     /// 各核心信息
     pub cores: Vec<CpuCoreInfo>,
-    /// CPU 温度 (摄氏度)，可能在某些系统上不可用
+    /// CPU 温度 (摄氏度)，取各封装温度的最大值，可能在某些系统上不可用
     pub temperature: Option<f32>,
+    /// `temperature` 取自的传感器标签，多个核心取平均时为描述文字
+    pub temperature_source: Option<String>,
+    /// 各封装温度 (摄氏度)
+    pub package_temperatures: Vec<f32>,
     /// 物理核心数
     pub physical_core_count: Option<usize>,
+    /// 1、5、15 分钟平均负载，Windows 上不可用
+    pub load_average: Option<(f64, f64, f64)>,
+    /// 各核心的平均频率 (MHz)
+    pub avg_frequency: u64,
+    /// 各核心中的最高频率 (MHz)
+    pub max_frequency: u64,
 }
 
 impl Default for CpuInfo {
@@ -36,21 +49,30 @@ impl Default for CpuInfo {
             total_usage: 0.0,
             cores: Vec::new(),
             temperature: None,
+            temperature_source: None,
+            package_temperatures: Vec::new(),
             physical_core_count: None,
+            load_average: None,
+            avg_frequency: 0,
+            max_frequency: 0,
         }
     }
 }
 
 /// 内存信息
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MemoryInfo {
     /// 总内存 (字节)
     pub total: u64,
-    /// 已使用内存 (字节)
+    /// 已使用内存 (字节)，即总量减去可用内存，不含可回收的缓存
     pub used: u64,
     /// 可用内存 (字节)
     pub available: u64,
-    /// 使用率 (0.0 - 100.0)
+    /// 页缓存及可回收的 slab (字节)，平台不提供时为 0
+    pub cached: u64,
+    /// 块设备缓冲区 (字节)，平台不提供时为 0
+    pub buffers: u64,
+    /// 使用率 (0.0 - 100.0)，按 `used` 计算
     pub usage_percent: f32,
     /// 交换分区总量 (字节)
     pub swap_total: u64,
@@ -58,6 +80,59 @@ pub struct MemoryInfo {
     pub swap_used: u64,
     /// 交换分区使用率 (0.0 - 100.0)
     pub swap_usage_percent: f32,
+    /// 内存压力等级
+    pub pressure: MemoryPressure,
+}
+
+/// 内存压力等级
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemoryPressure {
+    #[default]
+    Normal,
+    Warning,
+    Critical,
+}
+
+/// 内存压力等级的判定阈值
+///
+/// 可用内存占比低于、或交换分区使用率高于某一等级的阈值时，即进入该等级。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MemoryPressureThresholds {
+    /// 进入 Warning 的可用内存占比 (0.0 - 100.0)
+    pub warning_available: f32,
+    /// 进入 Critical 的可用内存占比 (0.0 - 100.0)
+    pub critical_available: f32,
+    /// 进入 Warning 的交换分区使用率 (0.0 - 100.0)
+    pub warning_swap: f32,
+    /// 进入 Critical 的交换分区使用率 (0.0 - 100.0)
+    pub critical_swap: f32,
+}
+
+impl Default for MemoryPressureThresholds {
+    fn default() -> Self {
+        Self {
+            warning_available: 15.0,
+            critical_available: 5.0,
+            warning_swap: 25.0,
+            critical_swap: 50.0,
+        }
+    }
+}
+
+impl MemoryPressureThresholds {
+    /// 按可用内存占比和交换分区使用率判定压力等级
+    pub fn classify(&self, available_percent: f32, swap_usage_percent: f32) -> MemoryPressure {
+        if available_percent < self.critical_available || swap_usage_percent > self.critical_swap {
+            MemoryPressure::Critical
+        } else if available_percent < self.warning_available
+            || swap_usage_percent > self.warning_swap
+        {
+            MemoryPressure::Warning
+        } else {
+            MemoryPressure::Normal
+        }
+    }
 }
 
 impl Default for MemoryInfo {
@@ -66,16 +141,19 @@ impl Default for MemoryInfo {
             total: 0,
             used: 0,
             available: 0,
+            cached: 0,
+            buffers: 0,
             usage_percent: 0.0,
             swap_total: 0,
             swap_used: 0,
             swap_usage_percent: 0.0,
+            pressure: MemoryPressure::Normal,
         }
     }
 }
 
 /// 单个磁盘信息
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DiskDetail {
     /// 磁盘名称
     pub name: String,
@@ -93,10 +171,14 @@ pub struct DiskDetail {
     pub usage_percent: f32,
     /// 是否可移除
     pub is_removable: bool,
+    /// 读取速率 (字节/秒)
+    pub read_speed: u64,
+    /// 写入速率 (字节/秒)
+    pub write_speed: u64,
 }
 
 /// 磁盘整体信息
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DiskInfo {
     /// 各磁盘详情
     pub disks: Vec<DiskDetail>,
@@ -108,6 +190,10 @@ pub struct DiskInfo {
     pub total_available: u64,
     /// 总体使用率 (0.0 - 100.0)
     pub total_usage_percent: f32,
+    /// 总读取速率 (字节/秒)
+    pub total_read_speed: u64,
+    /// 总写入速率 (字节/秒)
+    pub total_write_speed: u64,
 }
 
 impl Default for DiskInfo {
@@ -118,12 +204,14 @@ impl Default for DiskInfo {
             total_used: 0,
             total_available: 0,
             total_usage_percent: 0.0,
+            total_read_speed: 0,
+            total_write_speed: 0,
         }
     }
 }
 
 /// 网络接口信息
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct NetworkInterfaceInfo {
     /// 接口名称
     pub name: String,
@@ -131,14 +219,14 @@ pub struct NetworkInterfaceInfo {
     pub upload_speed: u64,
     /// 下载速率 (字节/秒)
     pub download_speed: u64,
-    /// 累计上传字节数
+    /// 接口自身的累计上传字节数，接口重建后从 0 开始
     pub total_uploaded: u64,
-    /// 累计下载字节数
+    /// 接口自身的累计下载字节数，接口重建后从 0 开始
     pub total_downloaded: u64,
 }
 
 /// 网络整体信息
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct NetworkInfo {
     /// 各网络接口信息
     pub interfaces: Vec<NetworkInterfaceInfo>,
@@ -146,10 +234,14 @@ pub struct NetworkInfo {
     pub total_upload_speed: u64,
     /// 总下载速率 (字节/秒)
     pub total_download_speed: u64,
-    /// 总累计上传字节数
+    /// 总累计上传字节数，只增不减，不受接口重建影响
     pub total_uploaded: u64,
-    /// 总累计下载字节数
+    /// 总累计下载字节数，只增不减，不受接口重建影响
     pub total_downloaded: u64,
+    /// 本次运行以来的最高总上传速率 (字节/秒)
+    pub peak_upload_speed: u64,
+    /// 本次运行以来的最高总下载速率 (字节/秒)
+    pub peak_download_speed: u64,
 }
 
 impl Default for NetworkInfo {
@@ -160,12 +252,91 @@ impl Default for NetworkInfo {
             total_download_speed: 0,
             total_uploaded: 0,
             total_downloaded: 0,
+            peak_upload_speed: 0,
+            peak_download_speed: 0,
         }
     }
 }
 
+/// 进程信息
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessInfo {
+    /// 进程 ID
+    pub pid: u32,
+    /// 进程名称
+    pub name: String,
+    /// CPU 使用率 (0.0 - 100.0)，已按核心数归一化
+    pub cpu_usage: f32,
+    /// 内存占用 (字节)
+    pub memory: u64,
+    /// 父进程 ID
+    pub parent_pid: Option<u32>,
+}
+
+/// 电池充放电状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BatteryState {
+    /// 充电中
+    Charging,
+    /// 放电中
+    Discharging,
+    /// 已充满
+    Full,
+    /// 未知
+    Unknown,
+}
+
+/// 电池信息
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatteryInfo {
+    /// 电量 (0.0 - 100.0)
+    pub percentage: f32,
+    /// 充放电状态
+    pub state: BatteryState,
+    /// 预计剩余使用时间，仅在放电时可用
+    pub time_to_empty: Option<Duration>,
+    /// 充放电功率 (瓦)，平台不支持时为 `None`
+    pub power_draw_watts: Option<f32>,
+}
+
+/// 采集失败的原因
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "message", rename_all = "snake_case")]
+pub enum MonitorError {
+    /// 系统没有返回任何 CPU 信息
+    NoCpu,
+    /// 系统没有返回内存总量
+    NoMemory,
+    /// 读取电池信息失败
+    Battery(String),
+}
+
+impl MonitorError {
+    /// 产生该错误的采集器名称
+    pub fn collector(&self) -> &'static str {
+        match self {
+            MonitorError::NoCpu => "cpu",
+            MonitorError::NoMemory => "memory",
+            MonitorError::Battery(_) => "battery",
+        }
+    }
+}
+
+impl fmt::Display for MonitorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MonitorError::NoCpu => write!(f, "no cpu information available"),
+            MonitorError::NoMemory => write!(f, "no memory information available"),
+            MonitorError::Battery(message) => write!(f, "failed to read battery: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for MonitorError {}
+
 /// 系统完整信息
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SystemInfo {
     /// CPU 信息
     pub cpu: CpuInfo,
@@ -175,6 +346,12 @@ pub struct SystemInfo {
     pub disk: DiskInfo,
     /// 网络信息
     pub network: NetworkInfo,
+    /// 电池信息，没有电池的设备上为 `None`
+    pub battery: Option<BatteryInfo>,
+    /// 系统运行时间 (秒)
+    pub uptime_secs: u64,
+    /// 最近一次尚未恢复的采集错误，为 `None` 时所有数据均正常
+    pub last_error: Option<MonitorError>,
     /// 采集时间戳 (毫秒)
     pub timestamp: u64,
 }
@@ -186,12 +363,158 @@ impl Default for SystemInfo {
             memory: MemoryInfo::default(),
             disk: DiskInfo::default(),
             network: NetworkInfo::default(),
+            battery: None,
+            uptime_secs: 0,
+            last_error: None,
             timestamp: 0,
         }
     }
 }
 
+/// 历史数据序列，按时间从旧到新排列
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HistoryInfo {
+    /// CPU 总使用率 (0.0 - 100.0)
+    pub cpu: Vec<f32>,
+    /// 内存使用率 (0.0 - 100.0)
+    pub memory: Vec<f32>,
+    /// 总上传速率 (字节/秒)
+    pub upload: Vec<u64>,
+    /// 总下载速率 (字节/秒)
+    pub download: Vec<u64>,
+}
+
+/// 告警指标类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertKind {
+    /// CPU 总使用率
+    Cpu,
+    /// 内存使用率
+    Memory,
+    /// 磁盘最高使用率
+    Disk,
+    /// 网络总速率（上传加下载）
+    Network,
+    /// 交换分区使用率
+    Swap,
+    /// 单个磁盘的使用率，告警中带有挂载点
+    #[serde(rename = "disk_full")]
+    DiskFull,
+}
+
+/// 阈值告警，指标进入或离开告警状态时各产生一次
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MonitorAlert {
+    /// 告警指标
+    pub kind: AlertKind,
+    /// 触发时的数值
+    pub value: f64,
+    /// 对应的阈值，阈值被移除时为 0
+    pub threshold: f64,
+    /// `true` 表示进入告警状态，`false` 表示恢复正常
+    pub active: bool,
+    /// `DiskFull` 告警对应磁盘的挂载点，其他告警为 `None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mount_point: Option<String>,
+    /// 进入告警前是否观测到过指标低于阈值
+    ///
+    /// 为 `false` 表示开始跟踪时指标就已超过阈值（如启动时负载已经很高），
+    /// 不算新越过的阈值。解除告警时始终为 `true`。
+    #[serde(default)]
+    pub fresh: bool,
+}
+
+/// 告警阈值，为 `None` 的指标不做检查
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Thresholds {
+    /// CPU 总使用率上限 (0.0 - 100.0)
+    pub cpu: Option<f64>,
+    /// 内存使用率上限 (0.0 - 100.0)
+    pub memory: Option<f64>,
+    /// 任一磁盘的使用率上限 (0.0 - 100.0)
+    pub disk: Option<f64>,
+    /// 网络总速率上限 (字节/秒)
+    pub network: Option<f64>,
+    /// 交换分区使用率上限 (0.0 - 100.0)，连续多次超过才告警
+    pub swap: Option<f64>,
+    /// 单个磁盘的使用率上限 (0.0 - 100.0)，每个挂载点分别告警
+    pub disk_full: Option<f64>,
+}
+
+/// 磁盘过滤规则，被过滤的磁盘不出现在结果中，也不计入总量
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiskFilter {
+    /// 排除的文件系统类型（不区分大小写）
+    pub excluded_file_systems: Vec<String>,
+    /// 同一物理卷的多个挂载点（如 bind mount、APFS 卷）是否只计入一次总量，
+    /// 各挂载点仍会列在结果中。关闭后按挂载点直接累加
+    pub dedupe_devices: bool,
+}
+
+impl Default for DiskFilter {
+    fn default() -> Self {
+        Self {
+            excluded_file_systems: ["tmpfs", "overlay", "squashfs", "devtmpfs"]
+                .iter()
+                .map(|fs| fs.to_string())
+                .collect(),
+            dedupe_devices: true,
+        }
+    }
+}
+
+impl DiskFilter {
+    /// 不做任何过滤
+    #[allow(dead_code)]
+    pub fn none() -> Self {
+        Self {
+            excluded_file_systems: Vec::new(),
+            dedupe_devices: false,
+        }
+    }
+
+    /// 检查文件系统类型是否被排除
+    pub fn excludes(&self, file_system: &str) -> bool {
+        self.excluded_file_systems
+            .iter()
+            .any(|excluded| excluded.eq_ignore_ascii_case(file_system))
+    }
+}
+
+/// 需要采集的指标，未启用的采集器会被跳过，保留最后一次的数据
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct EnabledMetrics {
+    pub cpu: bool,
+    pub memory: bool,
+    pub disk: bool,
+    pub network: bool,
+    pub battery: bool,
+}
+
+impl Default for EnabledMetrics {
+    fn default() -> Self {
+        Self {
+            cpu: true,
+            memory: true,
+            disk: true,
+            network: true,
+            battery: true,
+        }
+    }
+}
+
+/// CPU、内存、网络、进程采集间隔的下限
+///
+/// 更短的间隔会让采集线程空转占满 CPU，设置更小的值时按该值处理。
+pub const MIN_FAST_INTERVAL: Duration = Duration::from_millis(100);
+/// 磁盘、电池采集间隔的下限，这两项读取较慢且变化不频繁
+pub const MIN_SLOW_INTERVAL: Duration = Duration::from_secs(1);
+
 /// 监控配置
+///
+/// 各采集间隔不低于 [`MIN_FAST_INTERVAL`] 或 [`MIN_SLOW_INTERVAL`]，构造方法会直接限制；
+/// 直接修改字段得到的配置在传给 `Monitor` 时再限制。
 #[derive(Debug, Clone)]
 pub struct MonitorConfig {
     /// CPU 采集间隔
@@ -202,6 +525,26 @@ pub struct MonitorConfig {
     pub disk_interval: Duration,
     /// 网络采集间隔
     pub network_interval: Duration,
+    /// 进程采集间隔
+    pub process_interval: Duration,
+    /// 电池采集间隔
+    pub battery_interval: Duration,
+    /// 保留的进程数量上限
+    pub process_top_n: usize,
+    /// 每项指标保留的历史样本数量
+    pub history_len: usize,
+    /// 告警阈值
+    pub thresholds: Thresholds,
+    /// 磁盘过滤规则
+    pub disk_filter: DiskFilter,
+    /// 需要采集的指标
+    pub enabled: EnabledMetrics,
+    /// CPU 总使用率的指数平滑系数 (0.0 - 1.0)，越小越平滑；`None` 表示使用原始值
+    pub cpu_smoothing: Option<f32>,
+    /// 内存压力等级的判定阈值
+    pub memory_pressure: MemoryPressureThresholds,
+    /// 交换分区使用率连续超过阈值多少次采样后告警
+    pub swap_alert_samples: u32,
 }
 
 impl Default for MonitorConfig {
@@ -211,6 +554,16 @@ impl Default for MonitorConfig {
             memory_interval: Duration::from_secs(10),
             disk_interval: Duration::from_secs(60 * 5),
             network_interval: Duration::from_secs(3),
+            process_interval: Duration::from_secs(5),
+            process_top_n: 10,
+            battery_interval: Duration::from_secs(30),
+            history_len: 60,
+            thresholds: Thresholds::default(),
+            disk_filter: DiskFilter::default(),
+            enabled: EnabledMetrics::default(),
+            cpu_smoothing: None,
+            memory_pressure: MemoryPressureThresholds::default(),
+            swap_alert_samples: 3,
         }
     }
 }
@@ -221,27 +574,108 @@ impl MonitorConfig {
         Self::default()
     }
 
-    /// 设置 CPU 采集间隔
+    /// 设置 CPU 采集间隔，不低于 [`MIN_FAST_INTERVAL`]
     pub fn cpu_interval(mut self, interval: Duration) -> Self {
-        self.cpu_interval = interval;
+        self.cpu_interval = interval.max(MIN_FAST_INTERVAL);
         self
     }
 
-    /// 设置内存采集间隔
+    /// 设置内存采集间隔，不低于 [`MIN_FAST_INTERVAL`]
     pub fn memory_interval(mut self, interval: Duration) -> Self {
-        self.memory_interval = interval;
+        self.memory_interval = interval.max(MIN_FAST_INTERVAL);
         self
     }
 
-    /// 设置磁盘采集间隔
+    /// 设置磁盘采集间隔，不低于 [`MIN_SLOW_INTERVAL`]
     pub fn disk_interval(mut self, interval: Duration) -> Self {
-        self.disk_interval = interval;
+        self.disk_interval = interval.max(MIN_SLOW_INTERVAL);
         self
     }
 
-    /// 设置网络采集间隔
+    /// 设置网络采集间隔，不低于 [`MIN_FAST_INTERVAL`]
     pub fn network_interval(mut self, interval: Duration) -> Self {
-        self.network_interval = interval;
+        self.network_interval = interval.max(MIN_FAST_INTERVAL);
+        self
+    }
+
+    /// 设置进程采集间隔，不低于 [`MIN_FAST_INTERVAL`]
+    pub fn process_interval(mut self, interval: Duration) -> Self {
+        self.process_interval = interval.max(MIN_FAST_INTERVAL);
+        self
+    }
+
+    /// 设置电池采集间隔，不低于 [`MIN_SLOW_INTERVAL`]
+    #[allow(dead_code)]
+    pub fn battery_interval(mut self, interval: Duration) -> Self {
+        self.battery_interval = interval.max(MIN_SLOW_INTERVAL);
+        self
+    }
+
+    /// 把所有采集间隔限制在各自的下限以上
+    pub fn clamped(mut self) -> Self {
+        self.cpu_interval = self.cpu_interval.max(MIN_FAST_INTERVAL);
+        self.memory_interval = self.memory_interval.max(MIN_FAST_INTERVAL);
+        self.disk_interval = self.disk_interval.max(MIN_SLOW_INTERVAL);
+        self.network_interval = self.network_interval.max(MIN_FAST_INTERVAL);
+        self.process_interval = self.process_interval.max(MIN_FAST_INTERVAL);
+        self.battery_interval = self.battery_interval.max(MIN_SLOW_INTERVAL);
+        self
+    }
+
+    /// 设置保留的进程数量上限
+    #[allow(dead_code)]
+    pub fn process_top_n(mut self, top_n: usize) -> Self {
+        self.process_top_n = top_n;
+        self
+    }
+
+    /// 设置每项指标保留的历史样本数量
+    #[allow(dead_code)]
+    pub fn history_len(mut self, len: usize) -> Self {
+        self.history_len = len;
+        self
+    }
+
+    /// 设置告警阈值
+    pub fn thresholds(mut self, thresholds: Thresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+
+    /// 设置磁盘过滤规则
+    #[allow(dead_code)]
+    pub fn disk_filter(mut self, filter: DiskFilter) -> Self {
+        self.disk_filter = filter;
+        self
+    }
+
+    /// 设置需要采集的指标
+    pub fn enabled(mut self, enabled: EnabledMetrics) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// 设置 CPU 总使用率的指数平滑系数
+    ///
+    /// 每次采集的结果为 `alpha * 新值 + (1 - alpha) * 上次结果`，`alpha` 会被限制在
+    /// 0.0 - 1.0 之间。各核心的使用率不做平滑。传入 `None` 则使用原始值。
+    #[allow(dead_code)]
+    pub fn cpu_smoothing(mut self, alpha: Option<f32>) -> Self {
+        self.cpu_smoothing = alpha;
+        self
+    }
+
+    /// 设置内存压力等级的判定阈值
+    #[allow(dead_code)]
+    pub fn memory_pressure(mut self, thresholds: MemoryPressureThresholds) -> Self {
+        self.memory_pressure = thresholds;
+        self
+    }
+
+    /// 设置交换分区告警所需的连续采样次数，至少为 1
+    #[allow(dead_code)]
+    pub fn swap_alert_samples(mut self, samples: u32) -> Self {
+        self.swap_alert_samples = samples;
         self
     }
 }
@@ -0,0 +1,168 @@
+//! 告警的系统通知
+//!
+//! 只在指标新越过阈值时通知（见 `MonitorAlert::fresh`），启动时就已超过阈值的指标
+//! 需要先回落再越过才会通知。同一告警在 [`NOTIFY_COOLDOWN`] 内只通知一次，避免指标
+//! 在阈值附近反复波动时刷屏。
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use tauri_plugin_notification::NotificationExt;
+
+use crate::format::{format_speed, UnitBase};
+use crate::i18n::Lang;
+use crate::monitor::{AlertKind, MonitorAlert};
+
+/// 同一告警两次通知之间的最短间隔
+pub const NOTIFY_COOLDOWN: Duration = Duration::from_secs(5 * 60);
+
+/// 记录各告警上次通知的时间，按指标和挂载点区分
+#[derive(Debug, Default)]
+pub struct AlertNotifier {
+    last_sent: HashMap<(AlertKind, Option<String>), Instant>,
+}
+
+impl AlertNotifier {
+    /// 判断是否需要为 `alert` 发送通知，需要时记为已通知
+    ///
+    /// 只通知新进入告警状态的告警，解除告警不通知。
+    pub fn should_notify(&mut self, alert: &MonitorAlert, now: Instant) -> bool {
+        if !alert.active || !alert.fresh {
+            return false;
+        }
+        let key = (alert.kind, alert.mount_point.clone());
+        if let Some(last) = self.last_sent.get(&key) {
+            if now.saturating_duration_since(*last) < NOTIFY_COOLDOWN {
+                return false;
+            }
+        }
+        self.last_sent.insert(key, now);
+        true
+    }
+}
+
+/// 通知的标题和正文，如 `CPU 使用率 95.0%，超过 90%`
+pub fn notification_text(alert: &MonitorAlert, lang: Lang) -> (String, String) {
+    let value = alert.value;
+    let limit = alert.threshold;
+    let speed = |bytes_per_sec: f64| format_speed(bytes_per_sec as u64, UnitBase::Binary);
+    let mount_point = alert.mount_point.as_deref().unwrap_or("");
+    let (title, body) = match (alert.kind, lang) {
+        (AlertKind::Cpu, Lang::Zh) => (
+            "CPU 使用率过高",
+            format!("CPU 使用率 {:.1}%，超过 {:.0}%", value, limit),
+        ),
+        (AlertKind::Cpu, Lang::En) => (
+            "High CPU usage",
+            format!("CPU usage {:.1}% is above {:.0}%", value, limit),
+        ),
+        (AlertKind::Memory, Lang::Zh) => (
+            "内存使用率过高",
+            format!("内存使用率 {:.1}%，超过 {:.0}%", value, limit),
+        ),
+        (AlertKind::Memory, Lang::En) => (
+            "High memory usage",
+            format!("Memory usage {:.1}% is above {:.0}%", value, limit),
+        ),
+        (AlertKind::Disk, Lang::Zh) => (
+            "磁盘使用率过高",
+            format!("磁盘使用率 {:.1}%，超过 {:.0}%", value, limit),
+        ),
+        (AlertKind::Disk, Lang::En) => (
+            "High disk usage",
+            format!("Disk usage {:.1}% is above {:.0}%", value, limit),
+        ),
+        (AlertKind::Network, Lang::Zh) => (
+            "网络流量过高",
+            format!("网络速率 {}，超过 {}", speed(value), speed(limit)),
+        ),
+        (AlertKind::Network, Lang::En) => (
+            "High network traffic",
+            format!("Network speed {} is above {}", speed(value), speed(limit)),
+        ),
+        (AlertKind::Swap, Lang::Zh) => (
+            "交换分区使用率过高",
+            format!("交换分区使用率 {:.1}%，超过 {:.0}%", value, limit),
+        ),
+        (AlertKind::Swap, Lang::En) => (
+            "High swap usage",
+            format!("Swap usage {:.1}% is above {:.0}%", value, limit),
+        ),
+        (AlertKind::DiskFull, Lang::Zh) => (
+            "磁盘空间不足",
+            format!("{} 已使用 {:.1}%，超过 {:.0}%", mount_point, value, limit),
+        ),
+        (AlertKind::DiskFull, Lang::En) => (
+            "Disk almost full",
+            format!("{} is {:.1}% full, above {:.0}%", mount_point, value, limit),
+        ),
+    };
+    (title.to_string(), body)
+}
+
+/// 发送系统通知，系统不支持或用户未授权时静默失败
+pub fn notify_alert(app: &tauri::AppHandle, alert: &MonitorAlert, lang: Lang) {
+    let (title, body) = notification_text(alert, lang);
+    let _ = app.notification().builder().title(title).body(body).show();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alert(kind: AlertKind, mount_point: Option<&str>, fresh: bool) -> MonitorAlert {
+        MonitorAlert {
+            kind,
+            value: 95.0,
+            threshold: 90.0,
+            active: true,
+            mount_point: mount_point.map(str::to_string),
+            fresh,
+        }
+    }
+
+    #[test]
+    fn test_should_notify_throttles_fresh_alerts() {
+        let mut notifier = AlertNotifier::default();
+        let start = Instant::now();
+        let cpu = alert(AlertKind::Cpu, None, true);
+
+        // 启动时就已超过阈值的不通知
+        assert!(!notifier.should_notify(&alert(AlertKind::Cpu, None, false), start));
+        assert!(notifier.should_notify(&cpu, start));
+        // 冷却时间内不重复通知，解除告警不通知
+        assert!(!notifier.should_notify(&cpu, start + Duration::from_secs(60)));
+        let cleared = MonitorAlert {
+            active: false,
+            ..cpu.clone()
+        };
+        assert!(!notifier.should_notify(&cleared, start + NOTIFY_COOLDOWN));
+        assert!(notifier.should_notify(&cpu, start + NOTIFY_COOLDOWN));
+
+        // 不同挂载点分别计算冷却时间
+        let data = alert(AlertKind::DiskFull, Some("/data"), true);
+        let home = alert(AlertKind::DiskFull, Some("/home"), true);
+        assert!(notifier.should_notify(&data, start));
+        assert!(notifier.should_notify(&home, start));
+        assert!(!notifier.should_notify(&data, start));
+    }
+
+    #[test]
+    fn test_notification_text() {
+        let (title, body) = notification_text(&alert(AlertKind::Cpu, None, true), Lang::En);
+        assert_eq!(title, "High CPU usage");
+        assert_eq!(body, "CPU usage 95.0% is above 90%");
+
+        let disk = alert(AlertKind::DiskFull, Some("/data"), true);
+        let (_, body) = notification_text(&disk, Lang::Zh);
+        assert_eq!(body, "/data 已使用 95.0%，超过 90%");
+
+        let network = MonitorAlert {
+            value: 2048.0,
+            threshold: 1024.0,
+            ..alert(AlertKind::Network, None, true)
+        };
+        let (_, body) = notification_text(&network, Lang::En);
+        assert_eq!(body, "Network speed 2.0 KiB/s is above 1.0 KiB/s");
+    }
+}
@@ -0,0 +1,237 @@
+//! Prometheus 指标端点
+//!
+//! 需要开启 `prometheus` feature 编译：
+//!
+//! ```bash
+//! pnpm tauri build -- --features prometheus
+//! ```
+//!
+//! 编译后通过 `set_metrics_port` 命令设置端口即可启动，服务只监听 `127.0.0.1`，
+//! 在 `http://127.0.0.1:<端口>/metrics` 以 Prometheus 文本格式输出最新的 `SystemInfo`。
+//! 端口设为 `null` 时关闭服务，默认关闭。
+
+use std::fmt::Write as _;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crate::monitor::SystemInfo;
+
+/// 单个请求的读写超时，避免慢客户端占住服务线程
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 运行中的指标服务
+pub struct MetricsServer {
+    addr: SocketAddr,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+impl MetricsServer {
+    /// 在 `127.0.0.1:port` 上启动服务，每次请求调用 `render` 生成响应内容
+    pub fn start<F>(port: u16, render: F) -> Result<Self, String>
+    where
+        F: Fn() -> String + Send + 'static,
+    {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+            .map_err(|error| format!("failed to bind port {}: {}", port, error))?;
+        let addr = listener.local_addr().map_err(|error| error.to_string())?;
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let stop = Arc::clone(&shutdown);
+        let handle = thread::spawn(move || {
+            for stream in listener.incoming() {
+                if stop.load(Ordering::SeqCst) {
+                    break;
+                }
+                if let Ok(stream) = stream {
+                    let _ = serve(stream, &render);
+                }
+            }
+        });
+        Ok(Self {
+            addr,
+            shutdown,
+            handle: Some(handle),
+        })
+    }
+
+    /// 实际监听的端口
+    pub fn port(&self) -> u16 {
+        self.addr.port()
+    }
+
+    /// 停止服务并等待服务线程退出
+    pub fn stop(mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // 连接一次自身，唤醒阻塞在 `accept` 上的服务线程
+        let _ = TcpStream::connect_timeout(&self.addr, CLIENT_TIMEOUT);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn serve<F: Fn() -> String>(mut stream: TcpStream, render: &F) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+    stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (status, content_type, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => ("200 OK", "text/plain; version=0.0.4", render()),
+        _ => ("404 Not Found", "text/plain", "not found\n".to_string()),
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+/// 以 Prometheus 文本格式输出系统信息
+pub fn prometheus_text(info: &SystemInfo) -> String {
+    let mut out = String::new();
+    let mut gauge = |name: &str, help: &str, samples: &[(String, f64)]| {
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+        let _ = writeln!(out, "# TYPE {} gauge", name);
+        for (labels, value) in samples {
+            let _ = writeln!(out, "{}{} {}", name, labels, value);
+        }
+    };
+
+    gauge(
+        "corner_monitor_cpu_usage_percent",
+        "Total CPU usage in percent.",
+        &[(String::new(), info.cpu.total_usage as f64)],
+    );
+    // 读不到温度、没有电池时只输出说明，不输出样本
+    let temperature: Vec<(String, f64)> = info
+        .cpu
+        .temperature
+        .map(|celsius| (String::new(), celsius as f64))
+        .into_iter()
+        .collect();
+    gauge(
+        "corner_monitor_cpu_temperature_celsius",
+        "CPU temperature in degrees Celsius.",
+        &temperature,
+    );
+    gauge(
+        "corner_monitor_memory_total_bytes",
+        "Total physical memory in bytes.",
+        &[(String::new(), info.memory.total as f64)],
+    );
+    gauge(
+        "corner_monitor_memory_used_bytes",
+        "Used physical memory in bytes, excluding reclaimable cache.",
+        &[(String::new(), info.memory.used as f64)],
+    );
+    gauge(
+        "corner_monitor_memory_usage_percent",
+        "Physical memory usage in percent.",
+        &[(String::new(), info.memory.usage_percent as f64)],
+    );
+    gauge(
+        "corner_monitor_swap_used_bytes",
+        "Used swap in bytes.",
+        &[(String::new(), info.memory.swap_used as f64)],
+    );
+    gauge(
+        "corner_monitor_network_upload_bytes_per_second",
+        "Total upload rate across all interfaces.",
+        &[(String::new(), info.network.total_upload_speed as f64)],
+    );
+    gauge(
+        "corner_monitor_network_download_bytes_per_second",
+        "Total download rate across all interfaces.",
+        &[(String::new(), info.network.total_download_speed as f64)],
+    );
+    let disks: Vec<(String, f64)> = info
+        .disk
+        .disks
+        .iter()
+        .map(|disk| {
+            (
+                format!("{{mount_point=\"{}\"}}", escape_label(&disk.mount_point)),
+                disk.usage_percent as f64,
+            )
+        })
+        .collect();
+    gauge(
+        "corner_monitor_disk_usage_percent",
+        "Disk usage in percent per mount point.",
+        &disks,
+    );
+    let battery: Vec<(String, f64)> = info
+        .battery
+        .iter()
+        .map(|battery| (String::new(), battery.percentage as f64))
+        .collect();
+    gauge(
+        "corner_monitor_battery_percent",
+        "Battery charge in percent.",
+        &battery,
+    );
+    out
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::DiskDetail;
+
+    #[test]
+    fn test_prometheus_text() {
+        let mut info = SystemInfo::default();
+        info.cpu.total_usage = 12.5;
+        info.disk.disks.push(DiskDetail {
+            name: "disk0".to_string(),
+            mount_point: "C:\\".to_string(),
+            file_system: "ntfs".to_string(),
+            total: 100,
+            used: 40,
+            available: 60,
+            usage_percent: 40.0,
+            is_removable: false,
+            read_speed: 0,
+            write_speed: 0,
+        });
+        let text = prometheus_text(&info);
+        assert!(text.contains("# TYPE corner_monitor_cpu_usage_percent gauge\n"));
+        assert!(text.contains("corner_monitor_cpu_usage_percent 12.5\n"));
+        assert!(text.contains("corner_monitor_disk_usage_percent{mount_point=\"C:\\\\\"} 40\n"));
+        // 没有温度和电池时只有说明
+        assert!(text.contains("# TYPE corner_monitor_cpu_temperature_celsius gauge\n"));
+        assert!(!text.contains("\ncorner_monitor_battery_percent "));
+
+        info.cpu.temperature = Some(55.0);
+        let text = prometheus_text(&info);
+        assert!(text.contains("corner_monitor_cpu_temperature_celsius 55\n"));
+    }
+
+    #[test]
+    fn test_server_serves_metrics() {
+        let server = MetricsServer::start(0, || "metric 1\n".to_string()).unwrap();
+        let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, server.port())).unwrap();
+        write!(stream, "GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        let mut response = String::new();
+        std::io::Read::read_to_string(&mut stream, &mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.ends_with("\r\n\r\nmetric 1\n"));
+        server.stop();
+    }
+}
@@ -1,13 +1,28 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tauri::{LogicalSize, Wry};
 
+use crate::i18n::{lang_from_str, lang_to_str, Lang};
+use crate::monitor::{EnabledMetrics, MonitorConfig};
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum WindowPosition {
     TopLeft,
     BottomLeft,
     TopRight,
     BottomRight,
+    TopCenter,
+    BottomCenter,
+    LeftCenter,
+    RightCenter,
+    /// 相对所选显示器左上角的偏移（物理像素），超出显示器范围时会被限制在边界内
+    Custom {
+        x: i32,
+        y: i32,
+    },
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -16,10 +31,60 @@ pub enum Layout {
     Vertical,
 }
 
+/// 网速显示单位，后端始终以字节/秒存储，由前端按此单位换算
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NetworkUnit {
+    Bytes,
+    Bits,
+}
+
+/// 吸附时考虑的停靠点
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SnapMode {
+    /// 四个角
+    Corners,
+    /// 四条边的中点
+    Edges,
+    /// 四个角和四条边的中点
+    Both,
+}
+
+impl SnapMode {
+    /// 该模式下的候选停靠点
+    pub fn anchors(self) -> &'static [WindowPosition] {
+        const CORNERS_AND_EDGES: [WindowPosition; 8] = [
+            WindowPosition::TopLeft,
+            WindowPosition::TopRight,
+            WindowPosition::BottomLeft,
+            WindowPosition::BottomRight,
+            WindowPosition::TopCenter,
+            WindowPosition::BottomCenter,
+            WindowPosition::LeftCenter,
+            WindowPosition::RightCenter,
+        ];
+        match self {
+            SnapMode::Corners => &CORNERS_AND_EDGES[..4],
+            SnapMode::Edges => &CORNERS_AND_EDGES[4..],
+            SnapMode::Both => &CORNERS_AND_EDGES,
+        }
+    }
+}
+
+/// 温度显示单位，后端始终以摄氏度存储
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TemperatureUnit {
+    Celsius,
+    Fahrenheit,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum MonitorItem {
     Cpu,
     Mem,
     Net,
+    Battery,
+    Temp,
+    Gpu,
 }
 
 #[derive(Clone, Debug)]
@@ -27,10 +92,177 @@ pub struct UiState {
     pub position: WindowPosition,
     pub layout: Layout,
     pub monitor_target: Option<MonitorTarget>,
+    /// 全局文字颜色，未单独设置颜色的指标使用它
     pub text_color: String,
+    /// 各指标单独设置的文字颜色
+    pub metric_colors: MetricColors,
     pub show_cpu: bool,
     pub show_mem: bool,
     pub show_net: bool,
+    pub show_battery: bool,
+    /// 单独显示 CPU 温度，开启后 CPU 块中不再重复显示温度
+    pub show_temp: bool,
+    pub show_gpu: bool,
+    pub intervals: MonitorIntervals,
+    pub opacity: f64,
+    pub always_on_top: bool,
+    pub click_through: bool,
+    /// 与显示器边缘的间距（逻辑像素）
+    pub margin: u32,
+    pub net_unit: NetworkUnit,
+    pub temp_unit: TemperatureUnit,
+    /// 托盘菜单语言
+    pub lang: Lang,
+    /// 各显示器上次使用的位置，键见 `monitor_position_key`
+    pub monitor_positions: HashMap<String, WindowPosition>,
+    /// 托盘图标是否按 CPU 使用率实时绘制
+    pub tray_dynamic_icon: bool,
+    /// 指标越过告警阈值时是否发送系统通知
+    pub notifications: bool,
+    /// 单个磁盘使用率的告警阈值 (0.0 - 100.0)，`None` 表示不检查
+    pub disk_alert_threshold: Option<f64>,
+    /// 悬浮窗是否显示，隐藏时监控仍在后台运行
+    pub visible: bool,
+    /// 各指标在悬浮窗中的显示顺序，始终包含全部指标
+    pub metric_order: Vec<MonitorItem>,
+    /// Prometheus 端点的端口，`None` 表示关闭
+    pub metrics_port: Option<u16>,
+    /// WebSocket 推送服务的端口，`None` 表示关闭
+    pub ws_port: Option<u16>,
+    /// 切换布局的全局快捷键
+    pub hotkey: String,
+    /// 吸附到最近角落的全局快捷键
+    pub snap_hotkey: String,
+    /// 水平布局的自定义窗口尺寸（逻辑像素），`None` 表示按显示的指标自动计算
+    pub size_horizontal: Option<LogicalSize<f64>>,
+    /// 垂直布局的自定义窗口尺寸（逻辑像素），`None` 表示按显示的指标自动计算
+    pub size_vertical: Option<LogicalSize<f64>>,
+    /// 拖动结束后是否吸附到最近的停靠点，关闭时窗口停在松手的位置
+    pub lock_to_corner: bool,
+    /// 吸附时考虑的停靠点
+    pub snap_mode: SnapMode,
+    /// 悬浮窗文字大小（像素）
+    pub font_size: u32,
+}
+
+impl UiState {
+    /// 指定布局当前使用的窗口尺寸，未自定义时按显示的指标自动计算
+    pub fn window_size(&self, layout: Layout) -> LogicalSize<f64> {
+        let custom = match layout {
+            Layout::Horizontal => self.size_horizontal,
+            Layout::Vertical => self.size_vertical,
+        };
+        custom.unwrap_or_else(|| {
+            let size = compute_window_size(layout, &visibility_from_state(self));
+            let scale = self.font_size as f64 / DEFAULT_FONT_SIZE as f64;
+            LogicalSize::new(size.width * scale, size.height * scale)
+        })
+    }
+
+    pub fn set_window_size(&mut self, layout: Layout, size: Option<LogicalSize<f64>>) {
+        match layout {
+            Layout::Horizontal => self.size_horizontal = size,
+            Layout::Vertical => self.size_vertical = size,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MonitorIntervals {
+    pub cpu_ms: u64,
+    pub memory_ms: u64,
+    pub disk_ms: u64,
+    pub network_ms: u64,
+}
+
+impl Default for MonitorIntervals {
+    fn default() -> Self {
+        Self {
+            cpu_ms: 1000,
+            memory_ms: 1000,
+            disk_ms: 30_000,
+            network_ms: 1000,
+        }
+    }
+}
+
+impl MonitorIntervals {
+    pub fn is_valid(&self) -> bool {
+        self.cpu_ms > 0 && self.memory_ms > 0 && self.disk_ms > 0 && self.network_ms > 0
+    }
+
+    pub fn apply(&self, config: MonitorConfig) -> MonitorConfig {
+        config
+            .cpu_interval(Duration::from_millis(self.cpu_ms))
+            .memory_interval(Duration::from_millis(self.memory_ms))
+            .disk_interval(Duration::from_millis(self.disk_ms))
+            .network_interval(Duration::from_millis(self.network_ms))
+    }
+}
+
+/// 各指标的文字颜色，`None` 表示沿用全局的 `text_color`
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MetricColors {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mem: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub net: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk: Option<String>,
+}
+
+impl MetricColors {
+    /// 所有指标都使用同一颜色，用于从只有全局颜色的旧设置迁移
+    pub fn seeded(color: &str) -> Self {
+        let color = Some(color.to_string());
+        Self {
+            cpu: color.clone(),
+            mem: color.clone(),
+            net: color.clone(),
+            disk: color,
+        }
+    }
+
+    /// 按名称取指标的颜色，名称为 `cpu`、`mem`、`net` 或 `disk`
+    pub fn slot_mut(&mut self, metric: &str) -> Option<&mut Option<String>> {
+        match metric.to_ascii_lowercase().as_str() {
+            "cpu" => Some(&mut self.cpu),
+            "mem" => Some(&mut self.mem),
+            "net" => Some(&mut self.net),
+            "disk" => Some(&mut self.disk),
+            _ => None,
+        }
+    }
+
+    /// 未单独设置的指标填入 `fallback`，得到前端实际使用的完整颜色表
+    pub fn resolved(&self, fallback: &str) -> Self {
+        let pick = |color: &Option<String>| color.clone().or_else(|| Some(fallback.to_string()));
+        Self {
+            cpu: pick(&self.cpu),
+            mem: pick(&self.mem),
+            net: pick(&self.net),
+            disk: pick(&self.disk),
+        }
+    }
+}
+
+/// 解析保存或导入的指标颜色，颜色格式不对或出现未知指标时返回 `None`
+pub fn metric_colors_from_value(value: &Value) -> Option<MetricColors> {
+    let mut colors: MetricColors = serde_json::from_value(value.clone()).ok()?;
+    for slot in [
+        &mut colors.cpu,
+        &mut colors.mem,
+        &mut colors.net,
+        &mut colors.disk,
+    ] {
+        if let Some(color) = slot.as_deref() {
+            *slot = Some(normalize_hex_color(color)?);
+        }
+    }
+    Some(colors)
 }
 
 pub const SETTINGS_PATH: &str = "ui-settings.json";
@@ -38,11 +270,64 @@ pub const KEY_POSITION: &str = "position";
 pub const KEY_LAYOUT: &str = "layout";
 pub const KEY_MONITOR_TARGET: &str = "monitor_target";
 pub const KEY_TEXT_COLOR: &str = "text_color";
+pub const KEY_METRIC_COLORS: &str = "metric_colors";
 pub const KEY_MONITOR_CPU: &str = "monitor_cpu";
 pub const KEY_MONITOR_MEM: &str = "monitor_mem";
 pub const KEY_MONITOR_NET: &str = "monitor_net";
-pub const SIZE_HORIZONTAL: LogicalSize<f64> = LogicalSize::new(190.0, 40.0);
-pub const SIZE_VERTICAL: LogicalSize<f64> = LogicalSize::new(75.0, 100.0);
+pub const KEY_MONITOR_BATTERY: &str = "monitor_battery";
+pub const KEY_MONITOR_TEMP: &str = "monitor_temp";
+pub const KEY_MONITOR_GPU: &str = "monitor_gpu";
+pub const KEY_MONITOR_INTERVALS: &str = "monitor_intervals";
+pub const KEY_OPACITY: &str = "opacity";
+pub const KEY_ALWAYS_ON_TOP: &str = "always_on_top";
+pub const KEY_CLICK_THROUGH: &str = "click_through";
+pub const KEY_MARGIN: &str = "margin";
+pub const KEY_NET_UNIT: &str = "net_unit";
+pub const KEY_TEMP_UNIT: &str = "temp_unit";
+pub const KEY_LANG: &str = "lang";
+pub const KEY_MONITOR_POSITIONS: &str = "monitor_positions";
+pub const KEY_TRAY_DYNAMIC_ICON: &str = "tray_dynamic_icon";
+pub const KEY_NOTIFICATIONS: &str = "notifications";
+pub const KEY_DISK_ALERT_THRESHOLD: &str = "disk_alert_threshold";
+pub const DEFAULT_DISK_ALERT_THRESHOLD: f64 = 90.0;
+pub const KEY_WINDOW_VISIBLE: &str = "window_visible";
+pub const KEY_METRIC_ORDER: &str = "metric_order";
+pub const KEY_METRICS_PORT: &str = "metrics_port";
+pub const KEY_WS_PORT: &str = "ws_port";
+pub const KEY_HOTKEY: &str = "hotkey";
+/// 切换布局的默认全局快捷键
+pub const DEFAULT_HOTKEY: &str = "ctrl+alt+m";
+pub const KEY_SNAP_HOTKEY: &str = "snap_hotkey";
+/// 吸附到最近角落的默认全局快捷键
+pub const DEFAULT_SNAP_HOTKEY: &str = "ctrl+alt+s";
+pub const KEY_LOCK_TO_CORNER: &str = "lock_to_corner";
+pub const KEY_SNAP_MODE: &str = "snap_mode";
+pub const KEY_FONT_SIZE: &str = "font_size";
+/// 按名称保存的配置方案，值为 `profile_from_state` 生成的设置片段
+pub const KEY_PROFILES: &str = "profiles";
+/// 文字大小（像素），自动尺寸按默认大小计算后等比缩放
+pub const DEFAULT_FONT_SIZE: u32 = 10;
+pub const MIN_FONT_SIZE: u32 = 8;
+pub const MAX_FONT_SIZE: u32 = 48;
+/// 托盘“刷新间隔”预设（毫秒），同时作用于 CPU 和网络采集
+pub const KEY_REFRESH_INTERVAL: &str = "refresh_interval";
+pub const MAX_MARGIN: u32 = 200;
+pub const MIN_OPACITY: f64 = 0.1;
+/// 导入设置时各采集间隔的下限（毫秒）
+const MIN_IMPORT_INTERVAL_MS: u64 = 100;
+/// 自动尺寸：每个指标块在水平布局中的宽度、垂直布局中的高度，以及两端留白（逻辑像素）
+const METRIC_BLOCK_WIDTH: f64 = 60.0;
+const METRIC_BLOCK_HEIGHT: f64 = 30.0;
+const WINDOW_PADDING: f64 = 10.0;
+/// 水平布局的固定高度、垂直布局的固定宽度
+const HORIZONTAL_HEIGHT: f64 = 40.0;
+const VERTICAL_WIDTH: f64 = 75.0;
+pub const KEY_SIZE_HORIZONTAL: &str = "size_horizontal";
+pub const KEY_SIZE_VERTICAL: &str = "size_vertical";
+/// 自定义窗口尺寸的范围（逻辑像素），避免窗口缩到看不见或铺满屏幕
+pub const MIN_WINDOW_WIDTH: f64 = 40.0;
+pub const MIN_WINDOW_HEIGHT: f64 = 20.0;
+pub const MAX_WINDOW_SIZE: f64 = 2000.0;
 pub type SettingsStore = Arc<tauri_plugin_store::Store<Wry>>;
 
 impl Default for UiState {
@@ -52,11 +337,365 @@ impl Default for UiState {
             layout: Layout::Vertical,
             monitor_target: None,
             text_color: "#ffffff".to_string(),
+            metric_colors: MetricColors::default(),
             show_cpu: true,
             show_mem: true,
             show_net: true,
+            show_battery: false,
+            show_temp: false,
+            show_gpu: false,
+            intervals: MonitorIntervals::default(),
+            opacity: 1.0,
+            always_on_top: true,
+            click_through: false,
+            margin: 0,
+            net_unit: NetworkUnit::Bytes,
+            temp_unit: TemperatureUnit::Celsius,
+            lang: Lang::Zh,
+            monitor_positions: HashMap::new(),
+            tray_dynamic_icon: false,
+            notifications: true,
+            disk_alert_threshold: Some(DEFAULT_DISK_ALERT_THRESHOLD),
+            visible: true,
+            metric_order: DEFAULT_METRIC_ORDER.to_vec(),
+            metrics_port: None,
+            ws_port: None,
+            hotkey: DEFAULT_HOTKEY.to_string(),
+            snap_hotkey: DEFAULT_SNAP_HOTKEY.to_string(),
+            size_horizontal: None,
+            size_vertical: None,
+            lock_to_corner: true,
+            snap_mode: SnapMode::Both,
+            font_size: DEFAULT_FONT_SIZE,
+        }
+    }
+}
+
+pub const DEFAULT_METRIC_ORDER: [MonitorItem; 6] = [
+    MonitorItem::Cpu,
+    MonitorItem::Temp,
+    MonitorItem::Gpu,
+    MonitorItem::Mem,
+    MonitorItem::Net,
+    MonitorItem::Battery,
+];
+
+pub fn monitor_item_to_str(item: MonitorItem) -> &'static str {
+    match item {
+        MonitorItem::Cpu => "cpu",
+        MonitorItem::Mem => "mem",
+        MonitorItem::Net => "net",
+        MonitorItem::Battery => "battery",
+        MonitorItem::Temp => "temp",
+        MonitorItem::Gpu => "gpu",
+    }
+}
+
+pub fn monitor_item_from_str(value: &str) -> Option<MonitorItem> {
+    match value {
+        "cpu" => Some(MonitorItem::Cpu),
+        "mem" => Some(MonitorItem::Mem),
+        "net" => Some(MonitorItem::Net),
+        "battery" => Some(MonitorItem::Battery),
+        "temp" => Some(MonitorItem::Temp),
+        "gpu" => Some(MonitorItem::Gpu),
+        _ => None,
+    }
+}
+
+/// 后来新增的指标，旧版本保存的顺序中没有
+const ADDED_METRICS: [MonitorItem; 2] = [MonitorItem::Temp, MonitorItem::Gpu];
+
+/// 解析指标顺序，必须恰好包含全部指标且没有重复，否则返回 `None`
+///
+/// 旧版本保存的顺序缺少后来新增的指标，此时按默认顺序补在前一项之后。
+pub fn metric_order_from_strs<S: AsRef<str>>(values: &[S]) -> Option<Vec<MonitorItem>> {
+    let mut order = Vec::with_capacity(DEFAULT_METRIC_ORDER.len());
+    for value in values {
+        let item = monitor_item_from_str(value.as_ref())?;
+        if order.contains(&item) {
+            return None;
+        }
+        order.push(item);
+    }
+    for (index, item) in DEFAULT_METRIC_ORDER.iter().enumerate() {
+        if !ADDED_METRICS.contains(item) || order.contains(item) {
+            continue;
+        }
+        let previous = DEFAULT_METRIC_ORDER[..index]
+            .iter()
+            .rev()
+            .find_map(|previous| order.iter().position(|item| item == previous));
+        order.insert(previous.map_or(0, |position| position + 1), *item);
+    }
+    (order.len() == DEFAULT_METRIC_ORDER.len()).then_some(order)
+}
+
+pub fn metric_order_to_strs(order: &[MonitorItem]) -> Vec<&'static str> {
+    order
+        .iter()
+        .map(|item| monitor_item_to_str(*item))
+        .collect()
+}
+
+/// 把 `UiState` 中需要持久化的字段全部写入设置
+pub fn save_ui_state(store: &SettingsStore, state: &UiState) {
+    store.set(KEY_POSITION, position_to_str(state.position));
+    store.set(KEY_LAYOUT, layout_to_str(state.layout).to_string());
+    store.set(KEY_TEXT_COLOR, state.text_color.clone());
+    if let Ok(value) = serde_json::to_value(&state.metric_colors) {
+        store.set(KEY_METRIC_COLORS, value);
+    }
+    store.set(KEY_OPACITY, state.opacity);
+    store.set(KEY_ALWAYS_ON_TOP, state.always_on_top);
+    store.set(KEY_CLICK_THROUGH, state.click_through);
+    store.set(KEY_MARGIN, state.margin);
+    store.set(KEY_FONT_SIZE, state.font_size);
+    store.set(KEY_METRIC_ORDER, metric_order_to_strs(&state.metric_order));
+    store.set(KEY_METRICS_PORT, state.metrics_port);
+    store.set(KEY_WS_PORT, state.ws_port);
+    store.set(KEY_HOTKEY, state.hotkey.clone());
+    store.set(KEY_SNAP_HOTKEY, state.snap_hotkey.clone());
+    store.set(
+        KEY_SIZE_HORIZONTAL,
+        window_size_to_value(state.size_horizontal),
+    );
+    store.set(KEY_SIZE_VERTICAL, window_size_to_value(state.size_vertical));
+    store.set(KEY_LOCK_TO_CORNER, state.lock_to_corner);
+    store.set(KEY_SNAP_MODE, snap_mode_to_str(state.snap_mode));
+    store.set(KEY_WINDOW_VISIBLE, state.visible);
+    store.set(KEY_TRAY_DYNAMIC_ICON, state.tray_dynamic_icon);
+    store.set(KEY_NOTIFICATIONS, state.notifications);
+    store.set(KEY_DISK_ALERT_THRESHOLD, state.disk_alert_threshold);
+    store.set(KEY_NET_UNIT, net_unit_to_str(state.net_unit));
+    store.set(KEY_TEMP_UNIT, temp_unit_to_str(state.temp_unit));
+    store.set(KEY_LANG, lang_to_str(state.lang));
+    store.set(
+        KEY_MONITOR_POSITIONS,
+        monitor_positions_to_value(&state.monitor_positions),
+    );
+    if let Some(target) = &state.monitor_target {
+        store.set(KEY_MONITOR_TARGET, monitor_target_to_str(target));
+    }
+    store.set(KEY_MONITOR_CPU, state.show_cpu);
+    store.set(KEY_MONITOR_MEM, state.show_mem);
+    store.set(KEY_MONITOR_NET, state.show_net);
+    store.set(KEY_MONITOR_BATTERY, state.show_battery);
+    store.set(KEY_MONITOR_TEMP, state.show_temp);
+    store.set(KEY_MONITOR_GPU, state.show_gpu);
+    if let Ok(value) = serde_json::to_value(state.intervals) {
+        store.set(KEY_MONITOR_INTERVALS, value);
+    }
+}
+
+/// 配置方案保存的设置：位置、布局、文字颜色（含各指标的颜色）和各指标的显示状态
+///
+/// 格式与导出的设置相同，应用时交给 `import_ui_state` 校验。
+pub fn profile_from_state(state: &UiState) -> Value {
+    serde_json::json!({
+        KEY_POSITION: position_to_str(state.position),
+        KEY_LAYOUT: layout_to_str(state.layout),
+        KEY_TEXT_COLOR: state.text_color,
+        KEY_METRIC_COLORS: state.metric_colors,
+        KEY_MONITOR_CPU: state.show_cpu,
+        KEY_MONITOR_MEM: state.show_mem,
+        KEY_MONITOR_NET: state.show_net,
+        KEY_MONITOR_BATTERY: state.show_battery,
+        KEY_MONITOR_TEMP: state.show_temp,
+        KEY_MONITOR_GPU: state.show_gpu,
+    })
+}
+
+/// 解析保存的配置方案，格式不对时视为没有方案
+pub fn profiles_from_value(value: Option<Value>) -> Map<String, Value> {
+    match value {
+        Some(Value::Object(profiles)) => profiles,
+        _ => Map::new(),
+    }
+}
+
+/// 解析导入的端口设置，`null` 表示关闭服务
+fn port_from_value(value: &Value, key: &str) -> Result<Option<u16>, String> {
+    match value {
+        Value::Null => Ok(None),
+        value => value
+            .as_u64()
+            .and_then(|port| u16::try_from(port).ok())
+            .filter(|port| *port > 0)
+            .map(Some)
+            .ok_or_else(|| format!("invalid value for {}", key)),
+    }
+}
+
+/// 在 `base` 的基础上应用导入的设置，未出现的键保持原值
+///
+/// 无法识别的字符串或类型不符的值直接报错，数值字段限制在合法范围内。
+pub fn import_ui_state(base: &UiState, value: &Value) -> Result<UiState, String> {
+    let map = value
+        .as_object()
+        .ok_or_else(|| "settings must be a JSON object".to_string())?;
+    let mut state = base.clone();
+
+    if let Some(value) = import_str(map, KEY_POSITION)? {
+        state.position =
+            position_from_str(value).ok_or_else(|| format!("invalid position: {}", value))?;
+    }
+    if let Some(value) = import_str(map, KEY_LAYOUT)? {
+        state.layout =
+            layout_from_str(value).ok_or_else(|| format!("invalid layout: {}", value))?;
+    }
+    if let Some(value) = import_str(map, KEY_TEXT_COLOR)? {
+        state.text_color =
+            normalize_hex_color(value).ok_or_else(|| format!("invalid color: {}", value))?;
+    }
+    if let Some(value) = map.get(KEY_METRIC_COLORS) {
+        state.metric_colors = metric_colors_from_value(value)
+            .ok_or_else(|| format!("invalid value for {}", KEY_METRIC_COLORS))?;
+    }
+    if let Some(value) = import_str(map, KEY_MONITOR_TARGET)? {
+        state.monitor_target = Some(
+            monitor_target_from_str(value)
+                .ok_or_else(|| format!("invalid monitor target: {}", value))?,
+        );
+    }
+    if let Some(value) = import_str(map, KEY_NET_UNIT)? {
+        state.net_unit =
+            net_unit_from_str(value).ok_or_else(|| format!("invalid network unit: {}", value))?;
+    }
+    if let Some(value) = import_str(map, KEY_TEMP_UNIT)? {
+        state.temp_unit = temp_unit_from_str(value)
+            .ok_or_else(|| format!("invalid temperature unit: {}", value))?;
+    }
+    if let Some(value) = import_str(map, KEY_SNAP_MODE)? {
+        state.snap_mode =
+            snap_mode_from_str(value).ok_or_else(|| format!("invalid snap mode: {}", value))?;
+    }
+    if let Some(value) = import_str(map, KEY_LANG)? {
+        state.lang = lang_from_str(value).ok_or_else(|| format!("invalid language: {}", value))?;
+    }
+    for (key, field) in [
+        (KEY_MONITOR_CPU, &mut state.show_cpu),
+        (KEY_MONITOR_MEM, &mut state.show_mem),
+        (KEY_MONITOR_NET, &mut state.show_net),
+        (KEY_MONITOR_BATTERY, &mut state.show_battery),
+        (KEY_MONITOR_TEMP, &mut state.show_temp),
+        (KEY_MONITOR_GPU, &mut state.show_gpu),
+        (KEY_ALWAYS_ON_TOP, &mut state.always_on_top),
+        (KEY_CLICK_THROUGH, &mut state.click_through),
+        (KEY_TRAY_DYNAMIC_ICON, &mut state.tray_dynamic_icon),
+        (KEY_NOTIFICATIONS, &mut state.notifications),
+        (KEY_WINDOW_VISIBLE, &mut state.visible),
+        (KEY_LOCK_TO_CORNER, &mut state.lock_to_corner),
+    ] {
+        if let Some(value) = map.get(key) {
+            *field = value
+                .as_bool()
+                .ok_or_else(|| format!("invalid value for {}", key))?;
+        }
+    }
+    if let Some(value) = map.get(KEY_OPACITY) {
+        let value = value
+            .as_f64()
+            .ok_or_else(|| format!("invalid value for {}", KEY_OPACITY))?;
+        state.opacity = clamp_opacity(value);
+    }
+    if let Some(value) = map.get(KEY_MARGIN) {
+        let value = value
+            .as_f64()
+            .ok_or_else(|| format!("invalid value for {}", KEY_MARGIN))?;
+        state.margin = value.clamp(0.0, MAX_MARGIN as f64) as u32;
+    }
+    if let Some(value) = map.get(KEY_FONT_SIZE) {
+        let value = value
+            .as_u64()
+            .ok_or_else(|| format!("invalid value for {}", KEY_FONT_SIZE))?;
+        state.font_size = clamp_font_size(value.min(u32::MAX as u64) as u32);
+    }
+    if let Some(value) = map.get(KEY_MONITOR_INTERVALS) {
+        let intervals = serde_json::from_value::<MonitorIntervals>(value.clone())
+            .map_err(|error| format!("invalid value for {}: {}", KEY_MONITOR_INTERVALS, error))?;
+        state.intervals = MonitorIntervals {
+            cpu_ms: intervals.cpu_ms.max(MIN_IMPORT_INTERVAL_MS),
+            memory_ms: intervals.memory_ms.max(MIN_IMPORT_INTERVAL_MS),
+            disk_ms: intervals.disk_ms.max(MIN_IMPORT_INTERVAL_MS),
+            network_ms: intervals.network_ms.max(MIN_IMPORT_INTERVAL_MS),
+        };
+    }
+    if let Some(value) = map.get(KEY_REFRESH_INTERVAL) {
+        let value = value
+            .as_u64()
+            .ok_or_else(|| format!("invalid value for {}", KEY_REFRESH_INTERVAL))?
+            .max(MIN_IMPORT_INTERVAL_MS);
+        state.intervals.cpu_ms = value;
+        state.intervals.network_ms = value;
+    }
+    if let Some(value) = map.get(KEY_MONITOR_POSITIONS) {
+        state.monitor_positions = monitor_positions_from_value(value);
+    }
+    if let Some(value) = map.get(KEY_METRIC_ORDER) {
+        let values: Vec<&str> = value
+            .as_array()
+            .map(|values| values.iter().filter_map(|item| item.as_str()).collect())
+            .unwrap_or_default();
+        state.metric_order = metric_order_from_strs(&values)
+            .ok_or_else(|| format!("invalid value for {}", KEY_METRIC_ORDER))?;
+    }
+    for (key, field) in [
+        (KEY_HOTKEY, &mut state.hotkey),
+        (KEY_SNAP_HOTKEY, &mut state.snap_hotkey),
+    ] {
+        if let Some(value) = import_str(map, key)? {
+            if value.trim().is_empty() {
+                return Err(format!("invalid value for {}", key));
+            }
+            *field = value.to_string();
         }
     }
+    for (key, field) in [
+        (KEY_SIZE_HORIZONTAL, &mut state.size_horizontal),
+        (KEY_SIZE_VERTICAL, &mut state.size_vertical),
+    ] {
+        if let Some(value) = map.get(key) {
+            *field = match value {
+                Value::Null => None,
+                value => Some(
+                    window_size_from_value(value)
+                        .ok_or_else(|| format!("invalid value for {}", key))?,
+                ),
+            };
+        }
+    }
+    if let Some(value) = map.get(KEY_METRICS_PORT) {
+        state.metrics_port = port_from_value(value, KEY_METRICS_PORT)?;
+    }
+    if let Some(value) = map.get(KEY_WS_PORT) {
+        state.ws_port = port_from_value(value, KEY_WS_PORT)?;
+    }
+    if let Some(value) = map.get(KEY_DISK_ALERT_THRESHOLD) {
+        state.disk_alert_threshold = match value {
+            Value::Null => None,
+            value => Some(
+                value
+                    .as_f64()
+                    .and_then(valid_disk_alert_threshold)
+                    .ok_or_else(|| format!("invalid value for {}", KEY_DISK_ALERT_THRESHOLD))?,
+            ),
+        };
+    }
+    if !has_core_metric(&visibility_from_state(&state)) {
+        state.show_cpu = true;
+    }
+    Ok(state)
+}
+
+fn import_str<'a>(map: &'a Map<String, Value>, key: &str) -> Result<Option<&'a str>, String> {
+    match map.get(key) {
+        Some(value) => value
+            .as_str()
+            .map(Some)
+            .ok_or_else(|| format!("invalid value for {}", key)),
+        None => Ok(None),
+    }
 }
 
 pub fn layout_to_str(layout: Layout) -> &'static str {
@@ -74,12 +713,85 @@ pub fn layout_from_str(value: &str) -> Option<Layout> {
     }
 }
 
-pub fn position_to_str(position: WindowPosition) -> &'static str {
+pub fn net_unit_to_str(unit: NetworkUnit) -> &'static str {
+    match unit {
+        NetworkUnit::Bytes => "bytes",
+        NetworkUnit::Bits => "bits",
+    }
+}
+
+pub fn net_unit_from_str(value: &str) -> Option<NetworkUnit> {
+    match value {
+        "bytes" => Some(NetworkUnit::Bytes),
+        "bits" => Some(NetworkUnit::Bits),
+        _ => None,
+    }
+}
+
+pub fn temp_unit_to_str(unit: TemperatureUnit) -> &'static str {
+    match unit {
+        TemperatureUnit::Celsius => "celsius",
+        TemperatureUnit::Fahrenheit => "fahrenheit",
+    }
+}
+
+pub fn temp_unit_from_str(value: &str) -> Option<TemperatureUnit> {
+    match value {
+        "celsius" => Some(TemperatureUnit::Celsius),
+        "fahrenheit" => Some(TemperatureUnit::Fahrenheit),
+        _ => None,
+    }
+}
+
+pub fn snap_mode_to_str(mode: SnapMode) -> &'static str {
+    match mode {
+        SnapMode::Corners => "corners",
+        SnapMode::Edges => "edges",
+        SnapMode::Both => "both",
+    }
+}
+
+pub fn snap_mode_from_str(value: &str) -> Option<SnapMode> {
+    match value {
+        "corners" => Some(SnapMode::Corners),
+        "edges" => Some(SnapMode::Edges),
+        "both" => Some(SnapMode::Both),
+        _ => None,
+    }
+}
+
+pub fn celsius_to_fahrenheit(celsius: f32) -> f32 {
+    celsius * 9.0 / 5.0 + 32.0
+}
+
+/// 将采集到的摄氏温度换算为指定单位
+pub fn convert_temperature(celsius: f32, unit: TemperatureUnit) -> f32 {
+    match unit {
+        TemperatureUnit::Celsius => celsius,
+        TemperatureUnit::Fahrenheit => celsius_to_fahrenheit(celsius),
+    }
+}
+
+/// 按指定单位格式化摄氏温度，保留一位小数，如 `45.0°C`、`113.0°F`
+pub fn format_celsius(celsius: f32, unit: TemperatureUnit) -> String {
+    let symbol = match unit {
+        TemperatureUnit::Celsius => "°C",
+        TemperatureUnit::Fahrenheit => "°F",
+    };
+    format!("{:.1}{}", convert_temperature(celsius, unit), symbol)
+}
+
+pub fn position_to_str(position: WindowPosition) -> String {
     match position {
-        WindowPosition::TopLeft => "top-left",
-        WindowPosition::BottomLeft => "bottom-left",
-        WindowPosition::TopRight => "top-right",
-        WindowPosition::BottomRight => "bottom-right",
+        WindowPosition::TopLeft => "top-left".to_string(),
+        WindowPosition::BottomLeft => "bottom-left".to_string(),
+        WindowPosition::TopRight => "top-right".to_string(),
+        WindowPosition::BottomRight => "bottom-right".to_string(),
+        WindowPosition::TopCenter => "top-center".to_string(),
+        WindowPosition::BottomCenter => "bottom-center".to_string(),
+        WindowPosition::LeftCenter => "left-center".to_string(),
+        WindowPosition::RightCenter => "right-center".to_string(),
+        WindowPosition::Custom { x, y } => format!("custom:{},{}", x, y),
     }
 }
 
@@ -89,7 +801,17 @@ pub fn position_from_str(value: &str) -> Option<WindowPosition> {
         "bottom-left" => Some(WindowPosition::BottomLeft),
         "top-right" => Some(WindowPosition::TopRight),
         "bottom-right" => Some(WindowPosition::BottomRight),
-        _ => None,
+        "top-center" => Some(WindowPosition::TopCenter),
+        "bottom-center" => Some(WindowPosition::BottomCenter),
+        "left-center" => Some(WindowPosition::LeftCenter),
+        "right-center" => Some(WindowPosition::RightCenter),
+        _ => {
+            let (x, y) = value.strip_prefix("custom:")?.split_once(',')?;
+            Some(WindowPosition::Custom {
+                x: x.trim().parse().ok()?,
+                y: y.trim().parse().ok()?,
+            })
+        }
     }
 }
 
@@ -98,6 +820,9 @@ pub struct MonitorVisibility {
     pub cpu: bool,
     pub mem: bool,
     pub net: bool,
+    pub battery: bool,
+    pub temp: bool,
+    pub gpu: bool,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -106,51 +831,259 @@ pub struct MonitorTarget {
     pub name: Option<String>,
 }
 
+/// 提供给前端的显示器信息，尺寸为物理像素
+#[derive(Clone, Debug, Serialize)]
+pub struct MonitorSummary {
+    /// `available_monitors` 中的序号，用于 `set_monitor_target`
+    pub index: usize,
+    pub name: Option<String>,
+    pub width: u32,
+    pub height: u32,
+    pub is_primary: bool,
+    pub scale_factor: f64,
+}
+
 #[derive(Clone, Copy)]
 pub struct ColorOption {
     pub id: &'static str,
-    pub label: &'static str,
     pub value: &'static str,
 }
 
 pub const COLOR_OPTIONS: [ColorOption; 7] = [
     ColorOption {
         id: "color_white",
-        label: "白色",
         value: "#ffffff",
     },
     ColorOption {
         id: "color_black",
-        label: "黑色",
         value: "#000000",
     },
     ColorOption {
         id: "color_cyan",
-        label: "青色",
         value: "#8fe9ff",
     },
     ColorOption {
         id: "color_green",
-        label: "绿色",
         value: "#7cff6b",
     },
     ColorOption {
         id: "color_orange",
-        label: "橙色",
         value: "#ffb454",
     },
     ColorOption {
         id: "color_pink",
-        label: "粉色",
         value: "#ff6fae",
     },
     ColorOption {
         id: "color_yellow",
-        label: "黄色",
         value: "#ffd56a",
     },
 ];
 
+pub fn clamp_opacity(value: f64) -> f64 {
+    if value.is_nan() {
+        return 1.0;
+    }
+    value.clamp(MIN_OPACITY, 1.0)
+}
+
+/// 磁盘告警阈值须在 0（不含）到 100 之间
+pub fn valid_disk_alert_threshold(value: f64) -> Option<f64> {
+    (value > 0.0 && value <= 100.0).then_some(value)
+}
+
+/// 把窗口尺寸限制在 `MIN_WINDOW_*` 与 `MAX_WINDOW_SIZE` 之间，非数值按下限处理
+pub fn clamp_window_size(width: f64, height: f64) -> LogicalSize<f64> {
+    let clamp = |value: f64, min: f64| {
+        if value.is_nan() {
+            min
+        } else {
+            value.clamp(min, MAX_WINDOW_SIZE)
+        }
+    };
+    LogicalSize::new(
+        clamp(width, MIN_WINDOW_WIDTH),
+        clamp(height, MIN_WINDOW_HEIGHT),
+    )
+}
+
+/// 按布局和显示的指标数量计算窗口尺寸（逻辑像素），至少按一个指标计算
+pub fn compute_window_size(layout: Layout, visibility: &MonitorVisibility) -> LogicalSize<f64> {
+    let count = [
+        visibility.cpu,
+        visibility.mem,
+        visibility.net,
+        visibility.battery,
+        visibility.temp,
+        visibility.gpu,
+    ]
+    .iter()
+    .filter(|visible| **visible)
+    .count()
+    .max(1) as f64;
+    match layout {
+        Layout::Horizontal => LogicalSize::new(
+            WINDOW_PADDING + METRIC_BLOCK_WIDTH * count,
+            HORIZONTAL_HEIGHT,
+        ),
+        Layout::Vertical => {
+            LogicalSize::new(VERTICAL_WIDTH, WINDOW_PADDING + METRIC_BLOCK_HEIGHT * count)
+        }
+    }
+}
+
+pub fn clamp_font_size(size: u32) -> u32 {
+    size.clamp(MIN_FONT_SIZE, MAX_FONT_SIZE)
+}
+
+/// 自定义尺寸写成 `{width, height}`，自动尺寸写成 `null`
+pub fn window_size_to_value(size: Option<LogicalSize<f64>>) -> Value {
+    match size {
+        Some(size) => serde_json::json!({ "width": size.width, "height": size.height }),
+        None => Value::Null,
+    }
+}
+
+pub fn window_size_from_value(value: &Value) -> Option<LogicalSize<f64>> {
+    let width = value.get("width")?.as_f64()?;
+    let height = value.get("height")?.as_f64()?;
+    Some(clamp_window_size(width, height))
+}
+
+pub fn normalize_hex_color(value: &str) -> Option<String> {
+    let hex = value.strip_prefix('#')?;
+    if !matches!(hex.len(), 3 | 6 | 8) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let hex = hex.to_ascii_lowercase();
+    if hex.len() == 3 {
+        let expanded: String = hex.chars().flat_map(|c| [c, c]).collect();
+        return Some(format!("#{}", expanded));
+    }
+    Some(format!("#{}", hex))
+}
+
+#[derive(Clone, Copy)]
+pub struct OpacityOption {
+    pub id: &'static str,
+    pub label: &'static str,
+    pub value: f64,
+}
+
+pub const OPACITY_OPTIONS: [OpacityOption; 4] = [
+    OpacityOption {
+        id: "opacity_100",
+        label: "100%",
+        value: 1.0,
+    },
+    OpacityOption {
+        id: "opacity_80",
+        label: "80%",
+        value: 0.8,
+    },
+    OpacityOption {
+        id: "opacity_60",
+        label: "60%",
+        value: 0.6,
+    },
+    OpacityOption {
+        id: "opacity_40",
+        label: "40%",
+        value: 0.4,
+    },
+];
+
+#[derive(Clone, Copy)]
+pub struct MarginOption {
+    pub id: &'static str,
+    pub label: &'static str,
+    pub value: u32,
+}
+
+pub const MARGIN_OPTIONS: [MarginOption; 3] = [
+    MarginOption {
+        id: "margin_0",
+        label: "0 px",
+        value: 0,
+    },
+    MarginOption {
+        id: "margin_8",
+        label: "8 px",
+        value: 8,
+    },
+    MarginOption {
+        id: "margin_16",
+        label: "16 px",
+        value: 16,
+    },
+];
+
+/// 托盘“文字大小”预设，标签为相对默认大小的比例
+#[derive(Clone, Copy)]
+pub struct FontSizeOption {
+    pub id: &'static str,
+    pub label: &'static str,
+    pub value: u32,
+}
+
+pub const FONT_SIZE_OPTIONS: [FontSizeOption; 5] = [
+    FontSizeOption {
+        id: "font_size_8",
+        label: "80%",
+        value: 8,
+    },
+    FontSizeOption {
+        id: "font_size_10",
+        label: "100%",
+        value: 10,
+    },
+    FontSizeOption {
+        id: "font_size_12",
+        label: "120%",
+        value: 12,
+    },
+    FontSizeOption {
+        id: "font_size_15",
+        label: "150%",
+        value: 15,
+    },
+    FontSizeOption {
+        id: "font_size_20",
+        label: "200%",
+        value: 20,
+    },
+];
+
+#[derive(Clone, Copy)]
+pub struct RefreshOption {
+    pub id: &'static str,
+    pub label: &'static str,
+    pub value: u64,
+}
+
+pub const REFRESH_OPTIONS: [RefreshOption; 4] = [
+    RefreshOption {
+        id: "refresh_500",
+        label: "0.5s",
+        value: 500,
+    },
+    RefreshOption {
+        id: "refresh_1000",
+        label: "1s",
+        value: 1000,
+    },
+    RefreshOption {
+        id: "refresh_2000",
+        label: "2s",
+        value: 2000,
+    },
+    RefreshOption {
+        id: "refresh_5000",
+        label: "5s",
+        value: 5000,
+    },
+];
+
 pub fn monitor_target_for_monitor(index: usize, monitor: &tauri::Monitor) -> MonitorTarget {
     MonitorTarget {
         index,
@@ -189,6 +1122,34 @@ fn same_monitor(a: &tauri::Monitor, b: &tauri::Monitor) -> bool {
     *a.position() == *b.position() && *a.size() == *b.size()
 }
 
+/// 按显示器记录位置时使用的键
+///
+/// 有名称时只使用名称：插拔显示器后序号可能变化，名称不会。
+pub fn monitor_position_key(target: &MonitorTarget) -> String {
+    match &target.name {
+        Some(name) => format!("name:{}", name),
+        None => format!("index:{}", target.index),
+    }
+}
+
+pub fn monitor_positions_to_value(positions: &HashMap<String, WindowPosition>) -> Value {
+    let map: Map<String, Value> = positions
+        .iter()
+        .map(|(key, position)| (key.clone(), Value::String(position_to_str(*position))))
+        .collect();
+    Value::Object(map)
+}
+
+/// 解析保存的显示器位置表，跳过无法识别的条目
+pub fn monitor_positions_from_value(value: &Value) -> HashMap<String, WindowPosition> {
+    let Some(map) = value.as_object() else {
+        return HashMap::new();
+    };
+    map.iter()
+        .filter_map(|(key, value)| Some((key.clone(), position_from_str(value.as_str()?)?)))
+        .collect()
+}
+
 pub fn monitor_target_from_monitor(
     app: &tauri::AppHandle,
     monitor: &tauri::Monitor,
@@ -209,10 +1170,371 @@ pub fn primary_monitor_target(app: &tauri::AppHandle) -> Option<MonitorTarget> {
     monitor_target_from_monitor(app, &primary)
 }
 
+/// 按 `available_monitors` 的顺序列出所有显示器
+pub fn monitor_summaries(app: &tauri::AppHandle) -> tauri::Result<Vec<MonitorSummary>> {
+    let primary = app.primary_monitor()?;
+    let summaries = app
+        .available_monitors()?
+        .iter()
+        .enumerate()
+        .map(|(index, monitor)| MonitorSummary {
+            index,
+            name: monitor.name().cloned(),
+            width: monitor.size().width,
+            height: monitor.size().height,
+            is_primary: primary
+                .as_ref()
+                .is_some_and(|primary| same_monitor(primary, monitor)),
+            scale_factor: monitor.scale_factor(),
+        })
+        .collect();
+    Ok(summaries)
+}
+
 pub fn visibility_from_state(state: &UiState) -> MonitorVisibility {
     MonitorVisibility {
         cpu: state.show_cpu,
         mem: state.show_mem,
         net: state.show_net,
+        battery: state.show_battery,
+        temp: state.show_temp,
+        gpu: state.show_gpu,
+    }
+}
+
+/// CPU/内存/网络/温度/GPU 至少要显示一项，电池在没有电池的设备上不可用，不计入
+pub fn has_core_metric(visibility: &MonitorVisibility) -> bool {
+    visibility.cpu || visibility.mem || visibility.net || visibility.temp || visibility.gpu
+}
+
+/// 隐藏的指标不再采集；磁盘不在窗口中显示，但告警和命令仍会用到，始终采集
+pub fn enabled_metrics(state: &UiState) -> EnabledMetrics {
+    EnabledMetrics {
+        // 温度由 CPU 采集器读取
+        cpu: state.show_cpu || state.show_temp,
+        memory: state.show_mem,
+        disk: true,
+        network: state.show_net,
+        battery: state.show_battery,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_round_trip() {
+        for position in [
+            WindowPosition::TopLeft,
+            WindowPosition::BottomRight,
+            WindowPosition::LeftCenter,
+            WindowPosition::Custom { x: 120, y: -40 },
+        ] {
+            assert_eq!(
+                position_from_str(&position_to_str(position)),
+                Some(position)
+            );
+        }
+        assert_eq!(position_from_str("custom:1"), None);
+        assert_eq!(position_from_str("custom:a,b"), None);
+    }
+
+    #[test]
+    fn test_snap_mode() {
+        for mode in [SnapMode::Corners, SnapMode::Edges, SnapMode::Both] {
+            assert_eq!(snap_mode_from_str(snap_mode_to_str(mode)), Some(mode));
+        }
+        assert_eq!(snap_mode_from_str("center"), None);
+        let corners = SnapMode::Corners.anchors();
+        assert!(corners.contains(&WindowPosition::TopRight));
+        assert!(!corners.contains(&WindowPosition::TopCenter));
+        let edges = SnapMode::Edges.anchors();
+        assert!(edges.contains(&WindowPosition::LeftCenter));
+        assert!(!edges.contains(&WindowPosition::BottomLeft));
+        assert_eq!(SnapMode::Both.anchors().len(), 8);
+    }
+
+    #[test]
+    fn test_monitor_positions_round_trip() {
+        let mut positions = HashMap::new();
+        positions.insert("name:DELL".to_string(), WindowPosition::BottomRight);
+        positions.insert(
+            "index:1".to_string(),
+            WindowPosition::Custom { x: 10, y: 20 },
+        );
+        let value = monitor_positions_to_value(&positions);
+        assert_eq!(monitor_positions_from_value(&value), positions);
+
+        let value = serde_json::json!({ "name:DELL": "top-left", "bad": "nowhere", "num": 1 });
+        let parsed = monitor_positions_from_value(&value);
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed["name:DELL"], WindowPosition::TopLeft);
+    }
+
+    #[test]
+    fn test_metric_order_from_strs() {
+        assert_eq!(
+            metric_order_from_strs(&["net", "gpu", "temp", "cpu", "battery", "mem"]),
+            Some(vec![
+                MonitorItem::Net,
+                MonitorItem::Gpu,
+                MonitorItem::Temp,
+                MonitorItem::Cpu,
+                MonitorItem::Battery,
+                MonitorItem::Mem,
+            ])
+        );
+        assert_eq!(
+            metric_order_to_strs(&DEFAULT_METRIC_ORDER),
+            vec!["cpu", "temp", "gpu", "mem", "net", "battery"]
+        );
+        // 旧版本保存的顺序没有温度和 GPU，按默认顺序补在前一项之后
+        assert_eq!(
+            metric_order_from_strs(&["net", "cpu", "battery", "mem"]),
+            Some(vec![
+                MonitorItem::Net,
+                MonitorItem::Cpu,
+                MonitorItem::Temp,
+                MonitorItem::Gpu,
+                MonitorItem::Battery,
+                MonitorItem::Mem,
+            ])
+        );
+        // 缺项、重复或未知指标都视为无效
+        assert_eq!(metric_order_from_strs(&["cpu", "mem", "net"]), None);
+        assert_eq!(
+            metric_order_from_strs(&["cpu", "cpu", "net", "battery"]),
+            None
+        );
+        assert_eq!(metric_order_from_strs(&["cpu", "mem", "net", "disk"]), None);
+    }
+
+    #[test]
+    fn test_import_ui_state() {
+        let base = UiState::default();
+        let value = serde_json::json!({
+            "position": "bottom-right",
+            "layout": "horizontal",
+            "opacity": 5.0,
+            "margin": 10_000,
+            "font_size": 100,
+            "monitor_cpu": false,
+            "monitor_mem": false,
+            "monitor_net": false,
+            "refresh_interval": 2000,
+        });
+        let state = import_ui_state(&base, &value).unwrap();
+        assert_eq!(state.position, WindowPosition::BottomRight);
+        assert_eq!(state.layout, Layout::Horizontal);
+        assert_eq!(state.opacity, 1.0);
+        assert_eq!(state.margin, MAX_MARGIN);
+        assert_eq!(state.font_size, MAX_FONT_SIZE);
+        assert!(state.show_cpu);
+        assert_eq!(state.intervals.cpu_ms, 2000);
+        assert_eq!(state.text_color, base.text_color);
+
+        assert!(import_ui_state(&base, &serde_json::json!({ "layout": "diagonal" })).is_err());
+        assert!(import_ui_state(&base, &serde_json::json!({ "position": "middle" })).is_err());
+        assert!(import_ui_state(&base, &serde_json::json!({ "always_on_top": "yes" })).is_err());
+        assert!(import_ui_state(&base, &serde_json::json!([1, 2])).is_err());
+        assert!(import_ui_state(&base, &serde_json::json!({ "metrics_port": 70000 })).is_err());
+        let state = import_ui_state(&base, &serde_json::json!({ "metrics_port": 9100 })).unwrap();
+        assert_eq!(state.metrics_port, Some(9100));
+        assert!(import_ui_state(&base, &serde_json::json!({ "ws_port": 0 })).is_err());
+        let state = import_ui_state(&base, &serde_json::json!({ "ws_port": 9200 })).unwrap();
+        assert_eq!(state.ws_port, Some(9200));
+
+        let disk_alert = |value: Value| {
+            import_ui_state(&base, &serde_json::json!({ "disk_alert_threshold": value }))
+                .map(|state| state.disk_alert_threshold)
+        };
+        assert_eq!(disk_alert(serde_json::json!(85)), Ok(Some(85.0)));
+        assert_eq!(disk_alert(Value::Null), Ok(None));
+        assert!(disk_alert(serde_json::json!(0)).is_err());
+        assert!(disk_alert(serde_json::json!(120)).is_err());
+    }
+
+    #[test]
+    fn test_profile_round_trip() {
+        let gaming = UiState {
+            position: WindowPosition::BottomLeft,
+            layout: Layout::Horizontal,
+            text_color: "#ff0000".to_string(),
+            show_net: false,
+            show_gpu: true,
+            opacity: 0.5,
+            ..UiState::default()
+        };
+        let profile = profile_from_state(&gaming);
+
+        let work = UiState {
+            position: WindowPosition::TopRight,
+            opacity: 0.8,
+            ..UiState::default()
+        };
+        let applied = import_ui_state(&work, &profile).unwrap();
+        assert_eq!(applied.position, WindowPosition::BottomLeft);
+        assert_eq!(applied.layout, Layout::Horizontal);
+        assert_eq!(applied.text_color, "#ff0000");
+        assert!(!applied.show_net);
+        assert!(applied.show_gpu);
+        // 方案之外的设置保持不变
+        assert_eq!(applied.opacity, 0.8);
+
+        let mut profiles = profiles_from_value(Some(serde_json::json!({ "gaming": profile })));
+        assert!(profiles.contains_key("gaming"));
+        profiles = profiles_from_value(Some(serde_json::json!("gaming")));
+        assert!(profiles.is_empty());
+        assert!(profiles_from_value(None).is_empty());
+    }
+
+    #[test]
+    fn test_clamp_opacity() {
+        assert_eq!(clamp_opacity(0.5), 0.5);
+        assert_eq!(clamp_opacity(-1.0), MIN_OPACITY);
+        assert_eq!(clamp_opacity(0.0), MIN_OPACITY);
+        assert_eq!(clamp_opacity(2.0), 1.0);
+        assert_eq!(clamp_opacity(f64::NAN), 1.0);
+    }
+
+    #[test]
+    fn test_window_size() {
+        let size = clamp_window_size(1.0, f64::NAN);
+        assert_eq!(size.width, MIN_WINDOW_WIDTH);
+        assert_eq!(size.height, MIN_WINDOW_HEIGHT);
+        assert_eq!(clamp_window_size(1e6, 50.0).width, MAX_WINDOW_SIZE);
+
+        let value = window_size_to_value(Some(LogicalSize::new(220.0, 48.0)));
+        assert_eq!(
+            window_size_from_value(&value),
+            Some(LogicalSize::new(220.0, 48.0))
+        );
+        assert_eq!(
+            window_size_from_value(&serde_json::json!({ "width": 10 })),
+            None
+        );
+        assert_eq!(window_size_to_value(None), Value::Null);
+        assert_eq!(window_size_from_value(&Value::Null), None);
+    }
+
+    #[test]
+    fn test_compute_window_size() {
+        let mut visibility = MonitorVisibility {
+            cpu: true,
+            mem: true,
+            net: true,
+            battery: false,
+            temp: false,
+            gpu: false,
+        };
+        assert_eq!(
+            compute_window_size(Layout::Horizontal, &visibility),
+            LogicalSize::new(190.0, 40.0)
+        );
+        assert_eq!(
+            compute_window_size(Layout::Vertical, &visibility),
+            LogicalSize::new(75.0, 100.0)
+        );
+
+        visibility.battery = true;
+        assert_eq!(
+            compute_window_size(Layout::Horizontal, &visibility).width,
+            250.0
+        );
+
+        visibility = MonitorVisibility {
+            cpu: false,
+            mem: false,
+            net: false,
+            battery: false,
+            temp: false,
+            gpu: false,
+        };
+        assert_eq!(
+            compute_window_size(Layout::Vertical, &visibility),
+            LogicalSize::new(75.0, 40.0)
+        );
+
+        let mut state = UiState {
+            show_net: false,
+            ..UiState::default()
+        };
+        assert_eq!(
+            state.window_size(Layout::Horizontal),
+            LogicalSize::new(130.0, 40.0)
+        );
+        state.font_size = 20;
+        assert_eq!(
+            state.window_size(Layout::Horizontal),
+            LogicalSize::new(260.0, 80.0)
+        );
+        state.set_window_size(Layout::Horizontal, Some(LogicalSize::new(220.0, 48.0)));
+        assert_eq!(
+            state.window_size(Layout::Horizontal),
+            LogicalSize::new(220.0, 48.0)
+        );
+    }
+
+    #[test]
+    fn test_normalize_hex_color() {
+        assert_eq!(normalize_hex_color("#FFB454"), Some("#ffb454".to_string()));
+        assert_eq!(
+            normalize_hex_color("#ffb45480"),
+            Some("#ffb45480".to_string())
+        );
+        assert_eq!(normalize_hex_color("ffb454"), None);
+        assert_eq!(normalize_hex_color("#F0a"), Some("#ff00aa".to_string()));
+        assert_eq!(normalize_hex_color("#ffff"), None);
+        assert_eq!(normalize_hex_color("#ggb454"), None);
+    }
+
+    #[test]
+    fn test_metric_colors() {
+        let mut colors = metric_colors_from_value(&serde_json::json!({
+            "cpu": "#7CFF6B",
+            "net": "#0ff",
+        }))
+        .unwrap();
+        assert_eq!(colors.cpu.as_deref(), Some("#7cff6b"));
+        assert_eq!(colors.net.as_deref(), Some("#00ffff"));
+        assert_eq!(colors.mem, None);
+
+        let resolved = colors.resolved("#ffffff");
+        assert_eq!(resolved.cpu.as_deref(), Some("#7cff6b"));
+        assert_eq!(resolved.mem.as_deref(), Some("#ffffff"));
+        assert_eq!(resolved.disk.as_deref(), Some("#ffffff"));
+
+        *colors.slot_mut("Disk").unwrap() = Some("#ffb454".to_string());
+        assert_eq!(colors.disk.as_deref(), Some("#ffb454"));
+        assert!(colors.slot_mut("gpu").is_none());
+        let seeded = MetricColors::seeded("#ff0000").resolved("#ffffff");
+        assert_eq!(seeded.mem.as_deref(), Some("#ff0000"));
+
+        assert!(metric_colors_from_value(&serde_json::json!({ "cpu": "green" })).is_none());
+        assert!(metric_colors_from_value(&serde_json::json!({ "gpu": "#ffffff" })).is_none());
+
+        let imported = import_ui_state(
+            &UiState::default(),
+            &serde_json::json!({ "metric_colors": { "mem": "#FF6FAE" } }),
+        )
+        .unwrap();
+        assert_eq!(imported.metric_colors.mem.as_deref(), Some("#ff6fae"));
+        assert!(import_ui_state(
+            &UiState::default(),
+            &serde_json::json!({ "metric_colors": "#ffffff" })
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_celsius_to_fahrenheit() {
+        assert_eq!(celsius_to_fahrenheit(0.0), 32.0);
+        assert_eq!(celsius_to_fahrenheit(100.0), 212.0);
+        assert_eq!(celsius_to_fahrenheit(-40.0), -40.0);
+        assert_eq!(convert_temperature(37.5, TemperatureUnit::Celsius), 37.5);
+        assert_eq!(convert_temperature(37.5, TemperatureUnit::Fahrenheit), 99.5);
+        assert_eq!(format_celsius(45.0, TemperatureUnit::Celsius), "45.0°C");
+        assert_eq!(format_celsius(45.0, TemperatureUnit::Fahrenheit), "113.0°F");
     }
 }
@@ -1,49 +1,183 @@
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
 
 use tauri::{
-    menu::{CheckMenuItem, MenuBuilder, MenuItem, SubmenuBuilder},
-    tray::TrayIconBuilder,
+    image::Image,
+    menu::{CheckMenuItem, MenuBuilder, MenuItem, Submenu, SubmenuBuilder},
+    tray::{TrayIconBuilder, TrayIconEvent},
     Emitter, Manager, Wry,
 };
 use tauri_plugin_autostart::ManagerExt as AutoLaunchManagerExt;
 
+use crate::commands::{apply_metrics_port, apply_ws_port};
+use crate::format::{format_bit_speed, format_percent, format_speed, UnitBase};
+use crate::hotkey::rebind_hotkey;
+use crate::i18n::{detect_lang, label, lang_to_str, Lang};
+use crate::monitor::{Monitor, SystemInfo};
 use crate::state::{
-    layout_to_str, monitor_target_from_monitor, monitor_target_to_str, position_to_str,
-    visibility_from_state, Layout, MonitorItem, MonitorVisibility, SettingsStore, UiState,
-    WindowPosition, COLOR_OPTIONS, KEY_LAYOUT, KEY_MONITOR_CPU, KEY_MONITOR_MEM,
-    KEY_MONITOR_NET, KEY_MONITOR_TARGET, KEY_POSITION, KEY_TEXT_COLOR, SIZE_HORIZONTAL,
-    SIZE_VERTICAL,
+    enabled_metrics, has_core_metric, layout_to_str, metric_order_to_strs, monitor_position_key,
+    monitor_target_for_monitor, monitor_target_from_monitor, monitor_target_to_str,
+    net_unit_to_str, position_to_str, primary_monitor_target, save_ui_state, temp_unit_to_str,
+    visibility_from_state, Layout, MetricColors, MonitorIntervals, MonitorItem, MonitorTarget,
+    MonitorVisibility, NetworkUnit, SettingsStore, SnapMode, TemperatureUnit, UiState,
+    WindowPosition, COLOR_OPTIONS, FONT_SIZE_OPTIONS, KEY_ALWAYS_ON_TOP, KEY_CLICK_THROUGH,
+    KEY_FONT_SIZE, KEY_LANG, KEY_LAYOUT, KEY_MARGIN, KEY_METRIC_COLORS, KEY_MONITOR_BATTERY,
+    KEY_MONITOR_CPU, KEY_MONITOR_GPU, KEY_MONITOR_INTERVALS, KEY_MONITOR_MEM, KEY_MONITOR_NET,
+    KEY_MONITOR_TARGET, KEY_MONITOR_TEMP, KEY_NET_UNIT, KEY_NOTIFICATIONS, KEY_OPACITY,
+    KEY_POSITION, KEY_REFRESH_INTERVAL, KEY_TEMP_UNIT, KEY_TEXT_COLOR, KEY_TRAY_DYNAMIC_ICON,
+    KEY_WINDOW_VISIBLE, MARGIN_OPTIONS, OPACITY_OPTIONS, REFRESH_OPTIONS,
 };
 use crate::window::{
-    apply_window_position, calculate_window_position_on_monitor, monitor_for_window, nearest_corner,
+    apply_layout_and_position, apply_window_position, calculate_window_position_on_monitor,
+    close_settings_window, layout_size, monitor_for_window, nearest_snap_point,
+    remember_monitor_position, selected_monitor, show_settings_window, window_margin, Debounce,
+    SETTINGS_WINDOW,
 };
 
+/// 托盘图标的 id，用于通过 `tray_by_id` 更新图标
+pub const TRAY_ID: &str = "main";
+/// 动态托盘图标的边长（像素）
+const TRAY_ICON_SIZE: u32 = 32;
+/// 当前显示的是静态图标
+const NO_TRAY_ICON: u32 = u32::MAX;
+/// 默认托盘提示，CPU 和内存都隐藏时使用
+const TRAY_TOOLTIP: &str = "corner-monitor";
+/// 拖动中的窗口停止移动多久后保存位置，见 `window_moved`
+const DRAG_SAVE_DELAY: Duration = Duration::from_millis(500);
+
+/// 用户拖动窗口的状态，作为托管状态保存
+#[derive(Debug, Default)]
+pub struct WindowDrag {
+    /// 窗口正由用户拖动，程序调整位置引起的移动不会保存
+    dragging: AtomicBool,
+    /// 合并拖动中的移动事件，窗口停下后保存位置
+    save: Debounce,
+}
+
+/// 上一次绘制的动态托盘图标，见 `cpu_icon_key`
+///
+/// 托盘创建后才注册为托管状态，之前的采集不会记下从未画出的图标。
+pub struct TrayIconCache(AtomicU32);
+
+impl Default for TrayIconCache {
+    fn default() -> Self {
+        Self(AtomicU32::new(NO_TRAY_ICON))
+    }
+}
+
 #[derive(Clone)]
 pub struct TrayMenuItems {
     autostart: CheckMenuItem<Wry>,
+    window_visible: CheckMenuItem<Wry>,
+    settings_window: CheckMenuItem<Wry>,
+    always_on_top: CheckMenuItem<Wry>,
+    click_through: CheckMenuItem<Wry>,
     pos_top_left: CheckMenuItem<Wry>,
     pos_bottom_left: CheckMenuItem<Wry>,
     pos_top_right: CheckMenuItem<Wry>,
     pos_bottom_right: CheckMenuItem<Wry>,
+    pos_top_center: CheckMenuItem<Wry>,
+    pos_bottom_center: CheckMenuItem<Wry>,
+    pos_left_center: CheckMenuItem<Wry>,
+    pos_right_center: CheckMenuItem<Wry>,
     layout_horizontal: CheckMenuItem<Wry>,
     layout_vertical: CheckMenuItem<Wry>,
+    net_unit_bits: CheckMenuItem<Wry>,
+    net_unit_bytes: CheckMenuItem<Wry>,
+    temp_unit_celsius: CheckMenuItem<Wry>,
+    temp_unit_fahrenheit: CheckMenuItem<Wry>,
     color_items: Vec<ColorMenuItem>,
+    opacity_items: Vec<OpacityMenuItem>,
+    margin_items: Vec<MarginMenuItem>,
+    font_size_items: Vec<FontSizeMenuItem>,
+    refresh_items: Vec<RefreshMenuItem>,
     monitor_cpu: CheckMenuItem<Wry>,
     monitor_mem: CheckMenuItem<Wry>,
     monitor_net: CheckMenuItem<Wry>,
+    monitor_battery: CheckMenuItem<Wry>,
+    monitor_temp: CheckMenuItem<Wry>,
+    monitor_gpu: CheckMenuItem<Wry>,
+    monitoring_paused: CheckMenuItem<Wry>,
+    tray_dynamic_icon: CheckMenuItem<Wry>,
+    notifications: CheckMenuItem<Wry>,
+    position_menu: Submenu<Wry>,
+    margin_menu: Submenu<Wry>,
+    layout_menu: Submenu<Wry>,
+    color_menu: Submenu<Wry>,
+    opacity_menu: Submenu<Wry>,
+    font_size_menu: Submenu<Wry>,
+    monitor_menu: Submenu<Wry>,
+    net_unit_menu: Submenu<Wry>,
+    temp_unit_menu: Submenu<Wry>,
+    refresh_menu: Submenu<Wry>,
+    display_menu: Submenu<Wry>,
+    display_next: MenuItem<Wry>,
+    /// 显示器列表会随插拔变化，重建时整体替换
+    display_items: Arc<Mutex<Vec<DisplayMenuItem>>>,
+    reset_settings: MenuItem<Wry>,
+    quit: MenuItem<Wry>,
 }
 
 #[derive(Clone)]
 struct ColorMenuItem {
+    id: &'static str,
     value: &'static str,
     item: CheckMenuItem<Wry>,
 }
 
+#[derive(Clone)]
+struct OpacityMenuItem {
+    value: f64,
+    item: CheckMenuItem<Wry>,
+}
+
+#[derive(Clone)]
+struct MarginMenuItem {
+    value: u32,
+    item: CheckMenuItem<Wry>,
+}
+
+#[derive(Clone)]
+struct FontSizeMenuItem {
+    value: u32,
+    item: CheckMenuItem<Wry>,
+}
+
+#[derive(Clone)]
+struct DisplayMenuItem {
+    target: MonitorTarget,
+    item: CheckMenuItem<Wry>,
+}
+
+#[derive(Clone)]
+struct RefreshMenuItem {
+    value: u64,
+    item: CheckMenuItem<Wry>,
+}
+
 impl TrayMenuItems {
     pub fn set_autostart(&self, enabled: bool) {
         let _ = self.autostart.set_checked(enabled);
     }
 
+    pub fn set_window_visible(&self, visible: bool) {
+        let _ = self.window_visible.set_checked(visible);
+    }
+
+    pub fn set_settings_window(&self, open: bool) {
+        let _ = self.settings_window.set_checked(open);
+    }
+
+    pub fn set_always_on_top(&self, enabled: bool) {
+        let _ = self.always_on_top.set_checked(enabled);
+    }
+
+    pub fn set_click_through(&self, enabled: bool) {
+        let _ = self.click_through.set_checked(enabled);
+    }
+
     pub fn set_position(&self, position: WindowPosition) {
         let _ = self
             .pos_top_left
@@ -57,6 +191,18 @@ impl TrayMenuItems {
         let _ = self
             .pos_bottom_right
             .set_checked(position == WindowPosition::BottomRight);
+        let _ = self
+            .pos_top_center
+            .set_checked(position == WindowPosition::TopCenter);
+        let _ = self
+            .pos_bottom_center
+            .set_checked(position == WindowPosition::BottomCenter);
+        let _ = self
+            .pos_left_center
+            .set_checked(position == WindowPosition::LeftCenter);
+        let _ = self
+            .pos_right_center
+            .set_checked(position == WindowPosition::RightCenter);
     }
 
     pub fn set_layout(&self, layout: Layout) {
@@ -66,6 +212,20 @@ impl TrayMenuItems {
         let _ = self.layout_vertical.set_checked(layout == Layout::Vertical);
     }
 
+    pub fn set_net_unit(&self, unit: NetworkUnit) {
+        let _ = self.net_unit_bits.set_checked(unit == NetworkUnit::Bits);
+        let _ = self.net_unit_bytes.set_checked(unit == NetworkUnit::Bytes);
+    }
+
+    pub fn set_temp_unit(&self, unit: TemperatureUnit) {
+        let _ = self
+            .temp_unit_celsius
+            .set_checked(unit == TemperatureUnit::Celsius);
+        let _ = self
+            .temp_unit_fahrenheit
+            .set_checked(unit == TemperatureUnit::Fahrenheit);
+    }
+
     pub fn set_text_color(&self, color: &str) {
         for item in &self.color_items {
             let checked = item.value.eq_ignore_ascii_case(color);
@@ -73,11 +233,245 @@ impl TrayMenuItems {
         }
     }
 
+    pub fn set_opacity(&self, opacity: f64) {
+        for item in &self.opacity_items {
+            let checked = (item.value - opacity).abs() < 0.001;
+            let _ = item.item.set_checked(checked);
+        }
+    }
+
+    pub fn set_margin(&self, margin: u32) {
+        for item in &self.margin_items {
+            let _ = item.item.set_checked(item.value == margin);
+        }
+    }
+
+    /// 勾选与 `size` 一致的预设，自定义大小时全部取消勾选
+    pub fn set_font_size(&self, size: u32) {
+        for item in &self.font_size_items {
+            let _ = item.item.set_checked(item.value == size);
+        }
+    }
+
+    /// 勾选与 `interval_ms` 一致的预设，自定义间隔时全部取消勾选
+    pub fn set_refresh_interval(&self, interval_ms: u64) {
+        for item in &self.refresh_items {
+            let _ = item.item.set_checked(item.value == interval_ms);
+        }
+    }
+
+    /// 按 `state` 同步所有勾选状态和菜单语言
+    pub fn sync(&self, state: &UiState) {
+        self.set_window_visible(state.visible);
+        self.set_always_on_top(state.always_on_top);
+        self.set_click_through(state.click_through);
+        self.set_position(state.position);
+        self.set_layout(state.layout);
+        self.set_net_unit(state.net_unit);
+        self.set_temp_unit(state.temp_unit);
+        self.set_text_color(&state.text_color);
+        self.set_opacity(state.opacity);
+        self.set_margin(state.margin);
+        self.set_font_size(state.font_size);
+        self.set_monitor_visibility(visibility_from_state(state));
+        let intervals = state.intervals;
+        let preset = if intervals.cpu_ms == intervals.network_ms {
+            intervals.cpu_ms
+        } else {
+            0
+        };
+        self.set_refresh_interval(preset);
+        self.set_tray_dynamic_icon(state.tray_dynamic_icon);
+        self.set_notifications(state.notifications);
+        self.set_monitor_target(state.monitor_target.as_ref());
+        self.set_lang(state.lang);
+    }
+
+    pub fn set_monitor_target(&self, target: Option<&MonitorTarget>) {
+        if let Ok(items) = self.display_items.lock() {
+            for item in items.iter() {
+                let _ = item.item.set_checked(same_target(&item.target, target));
+            }
+        }
+    }
+
+    /// 显示器列表发生变化时重建“显示器”子菜单，否则只同步勾选状态
+    pub fn refresh_displays(&self, app: &tauri::AppHandle) {
+        let current = app
+            .state::<Mutex<UiState>>()
+            .lock()
+            .ok()
+            .and_then(|state| state.monitor_target.clone());
+        let targets = available_targets(app);
+        let Ok(mut items) = self.display_items.lock() else {
+            return;
+        };
+        let unchanged = items.len() == targets.len()
+            && items
+                .iter()
+                .zip(&targets)
+                .all(|(item, (target, _))| item.target == *target);
+        if !unchanged {
+            for item in items.drain(..) {
+                let _ = self.display_menu.remove(&item.item);
+            }
+            if let Ok(new_items) = display_menu_items(app, targets, current.as_ref()) {
+                for item in &new_items {
+                    let _ = self.display_menu.append(&item.item);
+                }
+                *items = new_items;
+            }
+            return;
+        }
+        for item in items.iter() {
+            let _ = item
+                .item
+                .set_checked(same_target(&item.target, current.as_ref()));
+        }
+    }
+
     pub fn set_monitor_visibility(&self, visibility: MonitorVisibility) {
         let _ = self.monitor_cpu.set_checked(visibility.cpu);
         let _ = self.monitor_mem.set_checked(visibility.mem);
         let _ = self.monitor_net.set_checked(visibility.net);
+        let _ = self.monitor_battery.set_checked(visibility.battery);
+        let _ = self.monitor_temp.set_checked(visibility.temp);
+        let _ = self.monitor_gpu.set_checked(visibility.gpu);
+    }
+
+    pub fn set_monitoring_paused(&self, paused: bool) {
+        let _ = self.monitoring_paused.set_checked(paused);
+    }
+
+    pub fn set_tray_dynamic_icon(&self, enabled: bool) {
+        let _ = self.tray_dynamic_icon.set_checked(enabled);
+    }
+
+    pub fn set_notifications(&self, enabled: bool) {
+        let _ = self.notifications.set_checked(enabled);
     }
+
+    /// 按新语言重设所有菜单文案，菜单结构和勾选状态保持不变
+    pub fn set_lang(&self, lang: Lang) {
+        let check_items = [
+            (&self.autostart, "autostart"),
+            (&self.window_visible, "window_visible"),
+            (&self.settings_window, "settings_window"),
+            (&self.always_on_top, "always_on_top"),
+            (&self.click_through, "click_through"),
+            (&self.pos_top_left, "pos_top_left"),
+            (&self.pos_bottom_left, "pos_bottom_left"),
+            (&self.pos_top_right, "pos_top_right"),
+            (&self.pos_bottom_right, "pos_bottom_right"),
+            (&self.pos_top_center, "pos_top_center"),
+            (&self.pos_bottom_center, "pos_bottom_center"),
+            (&self.pos_left_center, "pos_left_center"),
+            (&self.pos_right_center, "pos_right_center"),
+            (&self.layout_horizontal, "layout_horizontal"),
+            (&self.layout_vertical, "layout_vertical"),
+            (&self.monitor_battery, "monitor_battery"),
+            (&self.monitor_temp, "monitor_temp"),
+            (&self.monitoring_paused, "monitoring_paused"),
+            (&self.tray_dynamic_icon, "tray_dynamic_icon"),
+            (&self.notifications, "notifications"),
+        ];
+        for (item, key) in check_items {
+            let _ = item.set_text(label(key, lang));
+        }
+        for item in &self.color_items {
+            let _ = item.item.set_text(label(item.id, lang));
+        }
+
+        let submenus = [
+            (&self.position_menu, "menu_position"),
+            (&self.margin_menu, "menu_margin"),
+            (&self.layout_menu, "menu_layout"),
+            (&self.color_menu, "menu_color"),
+            (&self.opacity_menu, "menu_opacity"),
+            (&self.font_size_menu, "menu_font_size"),
+            (&self.monitor_menu, "menu_monitor"),
+            (&self.net_unit_menu, "menu_net_unit"),
+            (&self.temp_unit_menu, "menu_temp_unit"),
+            (&self.refresh_menu, "menu_refresh_interval"),
+            (&self.display_menu, "menu_display"),
+        ];
+        for (menu, key) in submenus {
+            let _ = menu.set_text(label(key, lang));
+        }
+        let _ = self.display_next.set_text(label("display_next", lang));
+        let _ = self.reset_settings.set_text(label("reset_settings", lang));
+        let _ = self.quit.set_text(label("quit", lang));
+    }
+}
+
+/// 推送前端关心的所有设置，启动和恢复默认时使用
+pub fn emit_ui_state(app: &tauri::AppHandle, state: &UiState) {
+    let _ = app.emit("layout-changed", layout_to_str(state.layout));
+    let _ = app.emit("text-color-changed", state.text_color.clone());
+    emit_metric_colors(app, state);
+    let _ = app.emit("opacity-changed", state.opacity);
+    let _ = app.emit("font-size-changed", state.font_size);
+    let _ = app.emit("click-through-changed", state.click_through);
+    let _ = app.emit("net-unit-changed", net_unit_to_str(state.net_unit));
+    let _ = app.emit("temp-unit-changed", temp_unit_to_str(state.temp_unit));
+    let _ = app.emit(
+        "metric-order-changed",
+        metric_order_to_strs(&state.metric_order),
+    );
+    let _ = app.emit("monitor-visibility-changed", visibility_from_state(state));
+}
+
+/// 恢复默认设置：重置 `UiState`、写回所有设置项、同步托盘并重新定位窗口
+///
+/// 显示器改为主显示器，语言按系统 locale 重新检测；开机启动属于系统设置，保持不变。
+pub fn reset_ui_state(app: &tauri::AppHandle) {
+    let defaults = UiState {
+        monitor_target: primary_monitor_target(app),
+        lang: detect_lang(),
+        ..UiState::default()
+    };
+    // 刷新间隔预设不在 `UiState` 中，删除后回到默认间隔
+    app.state::<SettingsStore>().delete(KEY_REFRESH_INTERVAL);
+    apply_ui_state(app, &defaults);
+}
+
+/// 整体替换 `UiState`：写入设置，并同步监控配置、托盘菜单、窗口和前端
+pub fn apply_ui_state(app: &tauri::AppHandle, ui_state: &UiState) {
+    let mut previous = None;
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        previous = Some(std::mem::replace(&mut *state, ui_state.clone()));
+    }
+    if let Some(previous) = previous {
+        let _ = rebind_hotkey(app, &previous.hotkey, &ui_state.hotkey);
+        let _ = rebind_hotkey(app, &previous.snap_hotkey, &ui_state.snap_hotkey);
+    }
+    let store = app.state::<SettingsStore>();
+    save_ui_state(&store, ui_state);
+
+    if let Ok(monitor) = app.state::<Mutex<Monitor>>().lock() {
+        let mut config = ui_state.intervals.apply(monitor.config());
+        config.thresholds.disk_full = ui_state.disk_alert_threshold;
+        monitor.set_config(config.enabled(enabled_metrics(ui_state)));
+    }
+    if let Some(tray) = app.try_state::<TrayMenuItems>() {
+        tray.sync(ui_state);
+    }
+    let _ = apply_metrics_port(app, ui_state.metrics_port);
+    let _ = apply_ws_port(app, ui_state.ws_port);
+    if !ui_state.tray_dynamic_icon {
+        restore_static_tray_icon(app);
+    }
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.set_always_on_top(ui_state.always_on_top);
+        let _ = window.set_ignore_cursor_events(ui_state.click_through);
+        let _ = if ui_state.visible {
+            window.show()
+        } else {
+            window.hide()
+        };
+        apply_layout_and_position(app, &window);
+    }
+    emit_ui_state(app, ui_state);
 }
 
 pub fn update_position(app: &tauri::AppHandle, position: WindowPosition, tray: &TrayMenuItems) {
@@ -86,12 +480,279 @@ pub fn update_position(app: &tauri::AppHandle, position: WindowPosition, tray: &
     }
     tray.set_position(position);
     let store = app.state::<SettingsStore>();
-    store.set(KEY_POSITION, position_to_str(position).to_string());
+    store.set(KEY_POSITION, position_to_str(position));
     if let Some(window) = app.get_webview_window("main") {
         let _ = apply_window_position(app, &window, position);
+        if let Some(monitor) = monitor_for_window(app, &window) {
+            remember_monitor_position(app, &monitor, position);
+        }
+    }
+}
+
+/// 显示或隐藏悬浮窗，只影响窗口，采集线程继续运行以便重新显示时数据是最新的
+pub fn update_window_visible(app: &tauri::AppHandle, visible: bool, tray: &TrayMenuItems) {
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.visible = visible;
+    }
+    tray.set_window_visible(visible);
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_WINDOW_VISIBLE, visible);
+    if let Some(window) = app.get_webview_window("main") {
+        if visible {
+            let _ = window.show();
+        } else {
+            let _ = window.hide();
+        }
+    }
+}
+
+/// 打开或关闭设置窗口，失败时勾选状态保持不变
+pub fn update_settings_window(app: &tauri::AppHandle, open: bool, tray: &TrayMenuItems) {
+    let result = if open {
+        show_settings_window(app)
+    } else {
+        close_settings_window(app)
+    };
+    tray.set_settings_window(if result.is_ok() { open } else { !open });
+}
+
+pub fn update_always_on_top(app: &tauri::AppHandle, enabled: bool, tray: &TrayMenuItems) {
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.always_on_top = enabled;
+    }
+    tray.set_always_on_top(enabled);
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_ALWAYS_ON_TOP, enabled);
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.set_always_on_top(enabled);
     }
 }
 
+/// 开启后窗口不再接收鼠标事件，拖动、松开吸附（`snap_window`）和右键切换布局都会失效，
+/// 只能通过托盘菜单关闭。
+pub fn update_click_through(app: &tauri::AppHandle, enabled: bool, tray: &TrayMenuItems) {
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.click_through = enabled;
+    }
+    tray.set_click_through(enabled);
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_CLICK_THROUGH, enabled);
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.set_ignore_cursor_events(enabled);
+    }
+    let _ = app.emit("click-through-changed", enabled);
+}
+
+/// 暂停或恢复数据采集，窗口保留暂停前的最后数据
+pub fn update_monitoring_paused(app: &tauri::AppHandle, paused: bool, tray: &TrayMenuItems) {
+    if let Ok(monitor) = app.state::<Mutex<Monitor>>().lock() {
+        if paused {
+            monitor.pause();
+        } else {
+            monitor.resume();
+        }
+    }
+    tray.set_monitoring_paused(paused);
+    let _ = app.emit("monitoring-paused-changed", paused);
+}
+
+/// 开关告警的系统通知，只影响之后的告警
+pub fn update_notifications(app: &tauri::AppHandle, enabled: bool, tray: &TrayMenuItems) {
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.notifications = enabled;
+    }
+    tray.set_notifications(enabled);
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_NOTIFICATIONS, enabled);
+}
+
+pub fn update_tray_dynamic_icon(app: &tauri::AppHandle, enabled: bool, tray: &TrayMenuItems) {
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.tray_dynamic_icon = enabled;
+    }
+    tray.set_tray_dynamic_icon(enabled);
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_TRAY_DYNAMIC_ICON, enabled);
+    // 关闭时恢复静态图标，开启后等下一次采集再绘制
+    if !enabled {
+        restore_static_tray_icon(app);
+    }
+}
+
+fn restore_static_tray_icon(app: &tauri::AppHandle) {
+    if let Some(cache) = app.try_state::<TrayIconCache>() {
+        cache.0.store(NO_TRAY_ICON, Ordering::Relaxed);
+    }
+    if let (Some(tray_icon), Some(icon)) =
+        (app.tray_by_id(TRAY_ID), app.default_window_icon().cloned())
+    {
+        let _ = tray_icon.set_icon(Some(icon));
+    }
+}
+
+/// 按 CPU 使用率重绘托盘图标，未开启动态图标时不做任何事
+///
+/// 填充高度和颜色与上一次相同时跳过，CPU 平稳时不必每次采集都分配新图标。
+pub fn update_tray_icon(app: &tauri::AppHandle, usage: f32) {
+    let enabled = app
+        .state::<Mutex<UiState>>()
+        .lock()
+        .map(|state| state.tray_dynamic_icon)
+        .unwrap_or(false);
+    if !enabled {
+        return;
+    }
+    let (Some(cache), Some(tray_icon)) =
+        (app.try_state::<TrayIconCache>(), app.tray_by_id(TRAY_ID))
+    else {
+        return;
+    };
+    let key = cpu_icon_key(usage, TRAY_ICON_SIZE);
+    if cache.0.load(Ordering::Relaxed) == key {
+        return;
+    }
+    let rgba = cpu_icon_rgba(usage, TRAY_ICON_SIZE);
+    let icon = Image::new_owned(rgba, TRAY_ICON_SIZE, TRAY_ICON_SIZE);
+    // 设置失败时不记录，下一次采集重试
+    if tray_icon.set_icon(Some(icon)).is_ok() {
+        cache.0.store(key, Ordering::Relaxed);
+    }
+}
+
+/// 按最新数据更新托盘提示，由采集线程调用，窗口隐藏时同样更新
+pub fn update_tray_tooltip(app: &tauri::AppHandle, info: &SystemInfo) {
+    let (visibility, net_unit) = match app.state::<Mutex<UiState>>().lock() {
+        Ok(state) => (visibility_from_state(&state), state.net_unit),
+        Err(_) => return,
+    };
+    if let Some(tray_icon) = app.tray_by_id(TRAY_ID) {
+        let _ = tray_icon.set_tooltip(Some(tray_tooltip(info, &visibility, net_unit)));
+    }
+}
+
+/// 按网络单位设置格式化速率，字节按 1024 进位，比特按 1000 进位
+pub fn format_net_speed(bytes_per_sec: u64, unit: NetworkUnit) -> String {
+    match unit {
+        NetworkUnit::Bytes => format_speed(bytes_per_sec, UnitBase::Binary),
+        NetworkUnit::Bits => format_bit_speed(bytes_per_sec, UnitBase::Decimal),
+    }
+}
+
+/// 托盘提示文字，如 `CPU 23.00% · MEM 61.00% · NET ↑1.0 KiB/s ↓2.0 MiB/s`，
+/// 只包含窗口中显示的指标，网络速率使用与窗口相同的单位
+fn tray_tooltip(
+    info: &SystemInfo,
+    visibility: &MonitorVisibility,
+    net_unit: NetworkUnit,
+) -> String {
+    let mut parts = Vec::new();
+    if visibility.cpu {
+        parts.push(format!("CPU {}", format_percent(info.cpu.total_usage)));
+    }
+    if visibility.mem {
+        parts.push(format!("MEM {}", format_percent(info.memory.usage_percent)));
+    }
+    if visibility.net {
+        parts.push(format!(
+            "NET ↑{} ↓{}",
+            format_net_speed(info.network.total_upload_speed, net_unit),
+            format_net_speed(info.network.total_download_speed, net_unit),
+        ));
+    }
+    if parts.is_empty() {
+        TRAY_TOOLTIP.to_string()
+    } else {
+        parts.join(" · ")
+    }
+}
+
+/// 填充颜色随负载变化：低于 60% 为绿色，低于 85% 为橙色，否则为红色
+const CPU_ICON_FILLS: [[u8; 4]; 3] = [
+    [0x7c, 0xff, 0x6b, 0xff],
+    [0xff, 0xb4, 0x54, 0xff],
+    [0xff, 0x4d, 0x4d, 0xff],
+];
+
+/// 图标的填充高度（像素）和颜色序号
+fn cpu_icon_fill(usage: f32, size: u32) -> (u32, usize) {
+    let tier = match usage {
+        usage if usage < 60.0 => 0,
+        usage if usage < 85.0 => 1,
+        _ => 2,
+    };
+    let usage = if usage.is_nan() {
+        0.0
+    } else {
+        usage.clamp(0.0, 100.0)
+    };
+    // 外框占 2 像素，内部留 1 像素空隙
+    let inner = size.saturating_sub(6);
+    let filled = (inner as f32 * usage / 100.0).round() as u32;
+    (filled, tier)
+}
+
+/// 绘制结果相同的使用率对应同一个值，用于跳过重复绘制
+fn cpu_icon_key(usage: f32, size: u32) -> u32 {
+    let (filled, tier) = cpu_icon_fill(usage, size);
+    filled * CPU_ICON_FILLS.len() as u32 + tier as u32
+}
+
+/// 绘制 `size`×`size` 的 RGBA 图标：外框加自下而上、高度与使用率成正比的填充
+fn cpu_icon_rgba(usage: f32, size: u32) -> Vec<u8> {
+    const FRAME: [u8; 4] = [0xdd, 0xdd, 0xdd, 0xff];
+    let (filled, tier) = cpu_icon_fill(usage, size);
+    let fill = CPU_ICON_FILLS[tier];
+    let mut rgba = vec![0u8; (size * size * 4) as usize];
+    for y in 0..size {
+        for x in 0..size {
+            let frame = x < 2 || y < 2 || x >= size - 2 || y >= size - 2;
+            let inside = (3..size - 3).contains(&x) && (3..size - 3).contains(&y);
+            let color = if frame {
+                FRAME
+            } else if inside && y >= size - 3 - filled {
+                fill
+            } else {
+                continue;
+            };
+            let offset = ((y * size + x) * 4) as usize;
+            rgba[offset..offset + 4].copy_from_slice(&color);
+        }
+    }
+    rgba
+}
+
+pub fn update_net_unit(app: &tauri::AppHandle, unit: NetworkUnit, tray: &TrayMenuItems) {
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.net_unit = unit;
+    }
+    tray.set_net_unit(unit);
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_NET_UNIT, net_unit_to_str(unit));
+    let _ = app.emit("net-unit-changed", net_unit_to_str(unit));
+}
+
+pub fn update_lang(app: &tauri::AppHandle, lang: Lang, tray: &TrayMenuItems) {
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.lang = lang;
+    }
+    tray.set_lang(lang);
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_LANG, lang_to_str(lang));
+    if let Some(window) = app.get_webview_window(SETTINGS_WINDOW) {
+        let _ = window.set_title(label("settings_window", lang));
+    }
+}
+
+pub fn update_temp_unit(app: &tauri::AppHandle, unit: TemperatureUnit, tray: &TrayMenuItems) {
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.temp_unit = unit;
+    }
+    tray.set_temp_unit(unit);
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_TEMP_UNIT, temp_unit_to_str(unit));
+    let _ = app.emit("temp-unit-changed", temp_unit_to_str(unit));
+}
+
 pub fn update_layout(app: &tauri::AppHandle, layout: Layout, tray: &TrayMenuItems) {
     let mut changed = true;
     if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
@@ -109,11 +770,7 @@ pub fn update_layout(app: &tauri::AppHandle, layout: Layout, tray: &TrayMenuItem
     }
 
     if let Some(window) = app.get_webview_window("main") {
-        let target = match layout {
-            Layout::Horizontal => SIZE_HORIZONTAL,
-            Layout::Vertical => SIZE_VERTICAL,
-        };
-        let _ = window.set_size(target);
+        let _ = window.set_size(layout_size(app, layout));
 
         let position = match app.state::<Mutex<UiState>>().lock() {
             Ok(state) => state.position,
@@ -138,99 +795,453 @@ pub fn update_layout(app: &tauri::AppHandle, layout: Layout, tray: &TrayMenuItem
     }
 }
 
+/// 推送各指标实际使用的文字颜色，未单独设置的指标填入全局颜色
+pub fn emit_metric_colors(app: &tauri::AppHandle, state: &UiState) {
+    let _ = app.emit(
+        "metric-colors-changed",
+        state.metric_colors.resolved(&state.text_color),
+    );
+}
+
+/// 设置全局文字颜色，同时清除各指标单独设置的颜色，使所有指标统一使用该颜色
 pub fn update_text_color(app: &tauri::AppHandle, color: &str, tray: &TrayMenuItems) {
+    tray.set_text_color(color);
+    set_global_text_color(app, color);
+}
+
+/// `update_text_color` 中与托盘无关的部分，托盘尚未创建时由命令直接调用
+pub fn set_global_text_color(app: &tauri::AppHandle, color: &str) {
     if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
         state.text_color = color.to_string();
+        state.metric_colors = MetricColors::default();
     }
-    tray.set_text_color(color);
     let store = app.state::<SettingsStore>();
     store.set(KEY_TEXT_COLOR, color.to_string());
+    store.set(KEY_METRIC_COLORS, serde_json::json!({}));
     let _ = app.emit("text-color-changed", color);
+    let _ = app.emit("metric-colors-changed", MetricColors::seeded(color));
+}
+
+pub fn update_opacity(app: &tauri::AppHandle, opacity: f64, tray: &TrayMenuItems) {
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.opacity = opacity;
+    }
+    tray.set_opacity(opacity);
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_OPACITY, opacity);
+    let _ = app.emit("opacity-changed", opacity);
+}
+
+pub fn update_margin(app: &tauri::AppHandle, margin: u32, tray: &TrayMenuItems) {
+    let position = match app.state::<Mutex<UiState>>().lock() {
+        Ok(mut state) => {
+            state.margin = margin;
+            state.position
+        }
+        Err(_) => WindowPosition::TopLeft,
+    };
+    tray.set_margin(margin);
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_MARGIN, margin);
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = apply_window_position(app, &window, position);
+    }
+}
+
+/// 调整文字大小，自动尺寸随之缩放，窗口重新调整并贴回角落
+pub fn update_font_size(app: &tauri::AppHandle, size: u32, tray: &TrayMenuItems) {
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.font_size = size;
+    }
+    tray.set_font_size(size);
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_FONT_SIZE, size);
+    let _ = app.emit("font-size-changed", size);
+    if let Some(window) = app.get_webview_window("main") {
+        apply_layout_and_position(app, &window);
+    }
+}
+
+/// 调整 CPU 和网络的采集间隔，其他采集器保持不变
+pub fn update_refresh_interval(app: &tauri::AppHandle, interval_ms: u64, tray: &TrayMenuItems) {
+    let mut intervals = MonitorIntervals::default();
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.intervals.cpu_ms = interval_ms;
+        state.intervals.network_ms = interval_ms;
+        intervals = state.intervals;
+    }
+    if let Ok(monitor) = app.state::<Mutex<Monitor>>().lock() {
+        monitor.set_config(intervals.apply(monitor.config()));
+    }
+    tray.set_refresh_interval(interval_ms);
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_REFRESH_INTERVAL, interval_ms);
+    if let Ok(value) = serde_json::to_value(intervals) {
+        store.set(KEY_MONITOR_INTERVALS, value);
+    }
+}
+
+/// 把窗口移到第 `index` 个显示器上，保持当前位置
+pub fn update_monitor_target(app: &tauri::AppHandle, index: usize, tray: &TrayMenuItems) {
+    let Ok(monitors) = app.available_monitors() else {
+        return;
+    };
+    let Some(monitor) = monitors.get(index) else {
+        return;
+    };
+    let target = monitor_target_for_monitor(index, monitor);
+    let position = match app.state::<Mutex<UiState>>().lock() {
+        Ok(mut state) => {
+            state.monitor_target = Some(target.clone());
+            state.position
+        }
+        Err(_) => WindowPosition::TopLeft,
+    };
+    tray.set_monitor_target(Some(&target));
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_MONITOR_TARGET, monitor_target_to_str(&target));
+    if let Some(window) = app.get_webview_window("main") {
+        if let Ok(target_pos) =
+            calculate_window_position_on_monitor(app, &window, position, monitor)
+        {
+            let _ = window.set_position(target_pos);
+        }
+    }
+}
+
+/// 按 `available_monitors` 的顺序切换到下一个显示器
+pub fn select_next_monitor(app: &tauri::AppHandle, tray: &TrayMenuItems) {
+    let Ok(monitors) = app.available_monitors() else {
+        return;
+    };
+    if monitors.len() < 2 {
+        return;
+    }
+    let current_index = selected_monitor(app)
+        .or_else(|| {
+            app.get_webview_window("main")
+                .and_then(|window| monitor_for_window(app, &window))
+        })
+        .and_then(|monitor| monitor_target_from_monitor(app, &monitor))
+        .map(|target| target.index)
+        .unwrap_or(0);
+    update_monitor_target(app, (current_index + 1) % monitors.len(), tray);
+}
+
+fn available_targets(app: &tauri::AppHandle) -> Vec<(MonitorTarget, tauri::Monitor)> {
+    app.available_monitors()
+        .map(|monitors| {
+            monitors
+                .into_iter()
+                .enumerate()
+                .map(|(index, monitor)| (monitor_target_for_monitor(index, &monitor), monitor))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 与 `monitor_position_key` 一致：有名称时按名称比较，插拔后序号变化也能匹配
+fn same_target(target: &MonitorTarget, current: Option<&MonitorTarget>) -> bool {
+    current.is_some_and(|current| monitor_position_key(target) == monitor_position_key(current))
+}
+
+fn display_menu_items(
+    app: &tauri::AppHandle,
+    targets: Vec<(MonitorTarget, tauri::Monitor)>,
+    current: Option<&MonitorTarget>,
+) -> tauri::Result<Vec<DisplayMenuItem>> {
+    let mut items = Vec::new();
+    for (target, monitor) in targets {
+        let size = monitor.size();
+        let resolution = format!("{}×{}", size.width, size.height);
+        let text = match &target.name {
+            Some(name) => format!("{}. {} ({})", target.index + 1, name, resolution),
+            None => format!("{}. {}", target.index + 1, resolution),
+        };
+        let item = CheckMenuItem::with_id(
+            app,
+            format!("display_{}", target.index),
+            text,
+            true,
+            same_target(&target, current),
+            None::<&str>,
+        )?;
+        items.push(DisplayMenuItem { target, item });
+    }
+    Ok(items)
 }
 
 pub fn update_monitor_visibility(app: &tauri::AppHandle, item: MonitorItem, tray: &TrayMenuItems) {
+    let visible = match app.state::<Mutex<UiState>>().lock() {
+        Ok(state) => !monitor_item_visible(&state, item),
+        Err(_) => return,
+    };
+    set_monitor_item_visibility(app, item, visible, Some(tray));
+}
+
+fn monitor_item_visible(state: &UiState, item: MonitorItem) -> bool {
+    match item {
+        MonitorItem::Cpu => state.show_cpu,
+        MonitorItem::Mem => state.show_mem,
+        MonitorItem::Net => state.show_net,
+        MonitorItem::Battery => state.show_battery,
+        MonitorItem::Temp => state.show_temp,
+        MonitorItem::Gpu => state.show_gpu,
+    }
+}
+
+/// 显示或隐藏单个指标，CPU/内存/网络/温度/GPU 至少保留一项，否则拒绝修改并返回 false
+pub fn set_monitor_item_visibility(
+    app: &tauri::AppHandle,
+    item: MonitorItem,
+    visible: bool,
+    tray: Option<&TrayMenuItems>,
+) -> bool {
     let mut next = None;
     if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
-        let mut cpu = state.show_cpu;
-        let mut mem = state.show_mem;
-        let mut net = state.show_net;
+        let mut visibility = visibility_from_state(&state);
         match item {
-            MonitorItem::Cpu => cpu = !cpu,
-            MonitorItem::Mem => mem = !mem,
-            MonitorItem::Net => net = !net,
+            MonitorItem::Cpu => visibility.cpu = visible,
+            MonitorItem::Mem => visibility.mem = visible,
+            MonitorItem::Net => visibility.net = visible,
+            MonitorItem::Battery => visibility.battery = visible,
+            MonitorItem::Temp => visibility.temp = visible,
+            MonitorItem::Gpu => visibility.gpu = visible,
+        }
+
+        if !has_core_metric(&visibility) {
+            if let Some(tray) = tray {
+                tray.set_monitor_visibility(visibility_from_state(&state));
+            }
+            return false;
         }
 
-        if !(cpu || mem || net) {
-            tray.set_monitor_visibility(visibility_from_state(&state));
+        state.show_cpu = visibility.cpu;
+        state.show_mem = visibility.mem;
+        state.show_net = visibility.net;
+        state.show_battery = visibility.battery;
+        state.show_temp = visibility.temp;
+        state.show_gpu = visibility.gpu;
+        next = Some(visibility);
+
+        // 隐藏的指标同时停止采集
+        if let Ok(monitor) = app.state::<Mutex<Monitor>>().lock() {
+            monitor.set_config(monitor.config().enabled(enabled_metrics(&state)));
+        }
+    }
+
+    let Some(visibility) = next else {
+        return false;
+    };
+    if let Some(tray) = tray {
+        tray.set_monitor_visibility(visibility);
+    }
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_MONITOR_CPU, visibility.cpu);
+    store.set(KEY_MONITOR_MEM, visibility.mem);
+    store.set(KEY_MONITOR_NET, visibility.net);
+    store.set(KEY_MONITOR_BATTERY, visibility.battery);
+    store.set(KEY_MONITOR_TEMP, visibility.temp);
+    store.set(KEY_MONITOR_GPU, visibility.gpu);
+    // 自动尺寸随显示的指标变化，重新调整尺寸并贴回当前角落
+    if let Some(window) = app.get_webview_window("main") {
+        apply_layout_and_position(app, &window);
+    }
+    let _ = app.emit("monitor-visibility-changed", visibility);
+    true
+}
+
+/// 把窗口吸附到 `snap_mode` 中离当前位置最近的停靠点，并保存为当前位置
+pub fn snap_window_to_nearest_corner(
+    app: &tauri::AppHandle,
+    window: &tauri::WebviewWindow,
+) -> tauri::Result<()> {
+    let current_pos = window.outer_position()?;
+    let current_size = window.outer_size()?;
+    let Some(monitor) = monitor_for_window(app, window) else {
+        return Ok(());
+    };
+    let monitor_pos = *monitor.position();
+    let monitor_size = *monitor.size();
+    let margin = window_margin(app, window);
+    let snap_mode = app
+        .state::<Mutex<UiState>>()
+        .lock()
+        .map(|state| state.snap_mode)
+        .unwrap_or(SnapMode::Both);
+    let (corner, target_pos) = nearest_snap_point(
+        monitor_pos,
+        monitor_size,
+        current_size,
+        current_pos,
+        margin,
+        snap_mode,
+    );
+
+    if current_pos.x != target_pos.x || current_pos.y != target_pos.y {
+        window.set_position(target_pos)?;
+    }
+    commit_window_position(app, &monitor, corner);
+    Ok(())
+}
+
+/// 交给系统开始拖动窗口
+pub fn begin_window_drag(window: &tauri::WebviewWindow) -> tauri::Result<()> {
+    if let Some(drag) = window.try_state::<WindowDrag>() {
+        drag.dragging.store(true, Ordering::SeqCst);
+    }
+    window.start_dragging()
+}
+
+/// 窗口移动时调用，未锁定到角落时保存拖动后的位置
+///
+/// 系统接管拖动后，部分平台上前端收不到鼠标松开事件，`finish_drag` 不会被调用。
+/// 因此拖动中的窗口停止移动 [`DRAG_SAVE_DELAY`] 后也视为拖动结束，保存当前位置。
+/// 锁定时不在这里吸附，避免用户按住鼠标停顿时窗口被移走。
+pub fn window_moved(app: &tauri::AppHandle) {
+    let Some(drag) = app.try_state::<WindowDrag>() else {
+        return;
+    };
+    if !drag.dragging.load(Ordering::SeqCst) || lock_to_corner(app) {
+        return;
+    }
+    if !drag.save.record() {
+        return;
+    }
+    let app = app.clone();
+    thread::spawn(move || {
+        let drag = app.state::<WindowDrag>();
+        drag.save.settle(DRAG_SAVE_DELAY);
+        // 期间 `finish_drag` 已经保存过时不再重复
+        if !drag.dragging.swap(false, Ordering::SeqCst) {
             return;
         }
+        let handle = app.clone();
+        let _ = app.run_on_main_thread(move || {
+            if let Some(window) = handle.get_webview_window("main") {
+                let _ = save_custom_position(&handle, &window);
+            }
+        });
+    });
+}
 
-        state.show_cpu = cpu;
-        state.show_mem = mem;
-        state.show_net = net;
-        next = Some(visibility_from_state(&state));
+fn lock_to_corner(app: &tauri::AppHandle) -> bool {
+    app.state::<Mutex<UiState>>()
+        .lock()
+        .map(|state| state.lock_to_corner)
+        .unwrap_or(true)
+}
+
+/// 拖动结束后确定窗口位置：`lock_to_corner` 开启时吸附到最近的停靠点，
+/// 否则把松手的位置保存为相对所在显示器的自定义位置
+pub fn finish_window_drag(
+    app: &tauri::AppHandle,
+    window: &tauri::WebviewWindow,
+) -> tauri::Result<()> {
+    if let Some(drag) = app.try_state::<WindowDrag>() {
+        drag.dragging.store(false, Ordering::SeqCst);
     }
-
-    if let Some(visibility) = next {
-        tray.set_monitor_visibility(visibility);
-        let store = app.state::<SettingsStore>();
-        store.set(KEY_MONITOR_CPU, visibility.cpu);
-        store.set(KEY_MONITOR_MEM, visibility.mem);
-        store.set(KEY_MONITOR_NET, visibility.net);
-        let _ = app.emit("monitor-visibility-changed", visibility);
+    if lock_to_corner(app) {
+        return snap_window_to_nearest_corner(app, window);
     }
+    save_custom_position(app, window)
 }
 
-pub fn snap_window_to_nearest_corner(
+/// 把窗口当前位置保存为相对所在显示器的自定义位置
+fn save_custom_position(
     app: &tauri::AppHandle,
     window: &tauri::WebviewWindow,
 ) -> tauri::Result<()> {
     let current_pos = window.outer_position()?;
-    let current_size = window.outer_size()?;
     let Some(monitor) = monitor_for_window(app, window) else {
         return Ok(());
     };
     let monitor_pos = *monitor.position();
-    let monitor_size = *monitor.size();
-    let (corner, target_pos) =
-        nearest_corner(monitor_pos, monitor_size, current_size, current_pos);
-
-    if current_pos.x != target_pos.x || current_pos.y != target_pos.y {
-        window.set_position(target_pos)?;
-    }
+    let position = WindowPosition::Custom {
+        x: current_pos.x - monitor_pos.x,
+        y: current_pos.y - monitor_pos.y,
+    };
+    commit_window_position(app, &monitor, position);
+    Ok(())
+}
 
-    let target_monitor = monitor_target_from_monitor(app, &monitor);
+/// 记录窗口停靠的显示器和位置，同步托盘勾选并通知前端
+fn commit_window_position(
+    app: &tauri::AppHandle,
+    monitor: &tauri::Monitor,
+    position: WindowPosition,
+) {
+    let target_monitor = monitor_target_from_monitor(app, monitor);
     if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
-        state.position = corner;
+        state.position = position;
         state.monitor_target = target_monitor.clone();
     }
     let store = app.state::<SettingsStore>();
-    store.set(KEY_POSITION, position_to_str(corner).to_string());
+    store.set(KEY_POSITION, position_to_str(position));
     if let Some(target) = target_monitor {
         store.set(KEY_MONITOR_TARGET, monitor_target_to_str(&target));
     }
+    remember_monitor_position(app, monitor, position);
 
     if let Some(tray) = app.try_state::<TrayMenuItems>() {
-        tray.set_position(corner);
+        tray.set_position(position);
+        tray.refresh_displays(app);
     }
-    Ok(())
+    let _ = app.emit("position-changed", position_to_str(position));
 }
 
 pub fn setup_tray(app: &tauri::AppHandle, ui_state: &UiState) -> tauri::Result<TrayMenuItems> {
+    let lang = ui_state.lang;
     let autostart_enabled = app.autolaunch().is_enabled().unwrap_or(false);
     let autostart_item = CheckMenuItem::with_id(
         app,
         "autostart",
-        "开机启动",
+        label("autostart", lang),
         true,
         autostart_enabled,
         None::<&str>,
     )?;
 
+    let window_visible_item = CheckMenuItem::with_id(
+        app,
+        "window_visible",
+        label("window_visible", lang),
+        true,
+        ui_state.visible,
+        None::<&str>,
+    )?;
+
+    let settings_window_item = CheckMenuItem::with_id(
+        app,
+        "settings_window",
+        label("settings_window", lang),
+        true,
+        app.get_webview_window(SETTINGS_WINDOW).is_some(),
+        None::<&str>,
+    )?;
+
+    let always_on_top_item = CheckMenuItem::with_id(
+        app,
+        "always_on_top",
+        label("always_on_top", lang),
+        true,
+        ui_state.always_on_top,
+        None::<&str>,
+    )?;
+
+    let click_through_item = CheckMenuItem::with_id(
+        app,
+        "click_through",
+        label("click_through", lang),
+        true,
+        ui_state.click_through,
+        None::<&str>,
+    )?;
+
     let pos_top_left = CheckMenuItem::with_id(
         app,
         "pos_top_left",
-        "左上",
+        label("pos_top_left", lang),
         true,
         ui_state.position == WindowPosition::TopLeft,
         None::<&str>,
@@ -238,7 +1249,7 @@ pub fn setup_tray(app: &tauri::AppHandle, ui_state: &UiState) -> tauri::Result<T
     let pos_bottom_left = CheckMenuItem::with_id(
         app,
         "pos_bottom_left",
-        "左下",
+        label("pos_bottom_left", lang),
         true,
         ui_state.position == WindowPosition::BottomLeft,
         None::<&str>,
@@ -246,7 +1257,7 @@ pub fn setup_tray(app: &tauri::AppHandle, ui_state: &UiState) -> tauri::Result<T
     let pos_top_right = CheckMenuItem::with_id(
         app,
         "pos_top_right",
-        "右上",
+        label("pos_top_right", lang),
         true,
         ui_state.position == WindowPosition::TopRight,
         None::<&str>,
@@ -254,16 +1265,48 @@ pub fn setup_tray(app: &tauri::AppHandle, ui_state: &UiState) -> tauri::Result<T
     let pos_bottom_right = CheckMenuItem::with_id(
         app,
         "pos_bottom_right",
-        "右下",
+        label("pos_bottom_right", lang),
         true,
         ui_state.position == WindowPosition::BottomRight,
         None::<&str>,
     )?;
+    let pos_top_center = CheckMenuItem::with_id(
+        app,
+        "pos_top_center",
+        label("pos_top_center", lang),
+        true,
+        ui_state.position == WindowPosition::TopCenter,
+        None::<&str>,
+    )?;
+    let pos_bottom_center = CheckMenuItem::with_id(
+        app,
+        "pos_bottom_center",
+        label("pos_bottom_center", lang),
+        true,
+        ui_state.position == WindowPosition::BottomCenter,
+        None::<&str>,
+    )?;
+    let pos_left_center = CheckMenuItem::with_id(
+        app,
+        "pos_left_center",
+        label("pos_left_center", lang),
+        true,
+        ui_state.position == WindowPosition::LeftCenter,
+        None::<&str>,
+    )?;
+    let pos_right_center = CheckMenuItem::with_id(
+        app,
+        "pos_right_center",
+        label("pos_right_center", lang),
+        true,
+        ui_state.position == WindowPosition::RightCenter,
+        None::<&str>,
+    )?;
 
     let layout_horizontal = CheckMenuItem::with_id(
         app,
         "layout_horizontal",
-        "水平",
+        label("layout_horizontal", lang),
         true,
         ui_state.layout == Layout::Horizontal,
         None::<&str>,
@@ -271,24 +1314,126 @@ pub fn setup_tray(app: &tauri::AppHandle, ui_state: &UiState) -> tauri::Result<T
     let layout_vertical = CheckMenuItem::with_id(
         app,
         "layout_vertical",
-        "垂直",
+        label("layout_vertical", lang),
         true,
         ui_state.layout == Layout::Vertical,
         None::<&str>,
     )?;
 
+    let net_unit_bits = CheckMenuItem::with_id(
+        app,
+        "net_unit_bits",
+        "bit/s",
+        true,
+        ui_state.net_unit == NetworkUnit::Bits,
+        None::<&str>,
+    )?;
+    let net_unit_bytes = CheckMenuItem::with_id(
+        app,
+        "net_unit_bytes",
+        "B/s",
+        true,
+        ui_state.net_unit == NetworkUnit::Bytes,
+        None::<&str>,
+    )?;
+
+    let temp_unit_celsius = CheckMenuItem::with_id(
+        app,
+        "temp_unit_celsius",
+        "°C",
+        true,
+        ui_state.temp_unit == TemperatureUnit::Celsius,
+        None::<&str>,
+    )?;
+    let temp_unit_fahrenheit = CheckMenuItem::with_id(
+        app,
+        "temp_unit_fahrenheit",
+        "°F",
+        true,
+        ui_state.temp_unit == TemperatureUnit::Fahrenheit,
+        None::<&str>,
+    )?;
+
     let mut color_items = Vec::new();
     for option in COLOR_OPTIONS {
         let checked = option.value.eq_ignore_ascii_case(&ui_state.text_color);
         let item = CheckMenuItem::with_id(
             app,
             option.id,
-            option.label,
+            label(option.id, lang),
             true,
             checked,
             None::<&str>,
         )?;
         color_items.push(ColorMenuItem {
+            id: option.id,
+            value: option.value,
+            item,
+        });
+    }
+
+    let mut opacity_items = Vec::new();
+    for option in OPACITY_OPTIONS {
+        let checked = (option.value - ui_state.opacity).abs() < 0.001;
+        let item = CheckMenuItem::with_id(
+            app,
+            option.id,
+            option.label,
+            true,
+            checked,
+            None::<&str>,
+        )?;
+        opacity_items.push(OpacityMenuItem {
+            value: option.value,
+            item,
+        });
+    }
+
+    let mut margin_items = Vec::new();
+    for option in MARGIN_OPTIONS {
+        let item = CheckMenuItem::with_id(
+            app,
+            option.id,
+            option.label,
+            true,
+            option.value == ui_state.margin,
+            None::<&str>,
+        )?;
+        margin_items.push(MarginMenuItem {
+            value: option.value,
+            item,
+        });
+    }
+
+    let mut font_size_items = Vec::new();
+    for option in FONT_SIZE_OPTIONS {
+        let item = CheckMenuItem::with_id(
+            app,
+            option.id,
+            option.label,
+            true,
+            option.value == ui_state.font_size,
+            None::<&str>,
+        )?;
+        font_size_items.push(FontSizeMenuItem {
+            value: option.value,
+            item,
+        });
+    }
+
+    let mut refresh_items = Vec::new();
+    for option in REFRESH_OPTIONS {
+        let intervals = ui_state.intervals;
+        let checked = option.value == intervals.cpu_ms && option.value == intervals.network_ms;
+        let item = CheckMenuItem::with_id(
+            app,
+            option.id,
+            option.label,
+            true,
+            checked,
+            None::<&str>,
+        )?;
+        refresh_items.push(RefreshMenuItem {
             value: option.value,
             item,
         });
@@ -318,59 +1463,242 @@ pub fn setup_tray(app: &tauri::AppHandle, ui_state: &UiState) -> tauri::Result<T
         ui_state.show_net,
         None::<&str>,
     )?;
+    let monitor_temp = CheckMenuItem::with_id(
+        app,
+        "monitor_temp",
+        label("monitor_temp", lang),
+        true,
+        ui_state.show_temp,
+        None::<&str>,
+    )?;
+    let monitor_gpu = CheckMenuItem::with_id(
+        app,
+        "monitor_gpu",
+        "GPU",
+        true,
+        ui_state.show_gpu,
+        None::<&str>,
+    )?;
+    let monitor_battery = CheckMenuItem::with_id(
+        app,
+        "monitor_battery",
+        label("monitor_battery", lang),
+        true,
+        ui_state.show_battery,
+        None::<&str>,
+    )?;
+    // 没有电池的设备上直接隐藏该菜单项
+    let has_battery = app
+        .try_state::<Mutex<Monitor>>()
+        .and_then(|monitor| {
+            monitor
+                .lock()
+                .ok()
+                .map(|monitor| monitor.get_battery_info().is_some())
+        })
+        .unwrap_or(false);
 
-    let tray_items = TrayMenuItems {
-        autostart: autostart_item.clone(),
-        pos_top_left: pos_top_left.clone(),
-        pos_bottom_left: pos_bottom_left.clone(),
-        pos_top_right: pos_top_right.clone(),
-        pos_bottom_right: pos_bottom_right.clone(),
-        layout_horizontal: layout_horizontal.clone(),
-        layout_vertical: layout_vertical.clone(),
-        color_items: color_items.clone(),
-        monitor_cpu: monitor_cpu.clone(),
-        monitor_mem: monitor_mem.clone(),
-        monitor_net: monitor_net.clone(),
-    };
+    let monitoring_paused = CheckMenuItem::with_id(
+        app,
+        "monitoring_paused",
+        label("monitoring_paused", lang),
+        true,
+        false,
+        None::<&str>,
+    )?;
+
+    let tray_dynamic_icon = CheckMenuItem::with_id(
+        app,
+        "tray_dynamic_icon",
+        label("tray_dynamic_icon", lang),
+        true,
+        ui_state.tray_dynamic_icon,
+        None::<&str>,
+    )?;
+
+    let notifications = CheckMenuItem::with_id(
+        app,
+        "notifications",
+        label("notifications", lang),
+        true,
+        ui_state.notifications,
+        None::<&str>,
+    )?;
 
-    let position_menu = SubmenuBuilder::new(app, "位置")
+    let position_menu = SubmenuBuilder::new(app, label("menu_position", lang))
         .item(&pos_top_left)
         .item(&pos_bottom_left)
         .item(&pos_top_right)
         .item(&pos_bottom_right)
+        .separator()
+        .item(&pos_top_center)
+        .item(&pos_bottom_center)
+        .item(&pos_left_center)
+        .item(&pos_right_center)
         .build()?;
 
-    let layout_menu = SubmenuBuilder::new(app, "布局")
+    let mut margin_menu_builder = SubmenuBuilder::new(app, label("menu_margin", lang));
+    for margin_item in &margin_items {
+        margin_menu_builder = margin_menu_builder.item(&margin_item.item);
+    }
+    let margin_menu = margin_menu_builder.build()?;
+
+    let layout_menu = SubmenuBuilder::new(app, label("menu_layout", lang))
         .item(&layout_horizontal)
         .item(&layout_vertical)
         .build()?;
 
-    let mut color_menu_builder = SubmenuBuilder::new(app, "颜色");
+    let net_unit_menu = SubmenuBuilder::new(app, label("menu_net_unit", lang))
+        .item(&net_unit_bits)
+        .item(&net_unit_bytes)
+        .build()?;
+
+    let temp_unit_menu = SubmenuBuilder::new(app, label("menu_temp_unit", lang))
+        .item(&temp_unit_celsius)
+        .item(&temp_unit_fahrenheit)
+        .build()?;
+
+    let mut color_menu_builder = SubmenuBuilder::new(app, label("menu_color", lang));
     for color_item in &color_items {
         color_menu_builder = color_menu_builder.item(&color_item.item);
     }
     let color_menu = color_menu_builder.build()?;
 
-    let monitor_menu = SubmenuBuilder::new(app, "监控")
+    let mut opacity_menu_builder = SubmenuBuilder::new(app, label("menu_opacity", lang));
+    for opacity_item in &opacity_items {
+        opacity_menu_builder = opacity_menu_builder.item(&opacity_item.item);
+    }
+    let opacity_menu = opacity_menu_builder.build()?;
+
+    let mut font_size_menu_builder = SubmenuBuilder::new(app, label("menu_font_size", lang));
+    for font_size_item in &font_size_items {
+        font_size_menu_builder = font_size_menu_builder.item(&font_size_item.item);
+    }
+    let font_size_menu = font_size_menu_builder.build()?;
+
+    let mut monitor_menu_builder = SubmenuBuilder::new(app, label("menu_monitor", lang))
         .item(&monitor_cpu)
+        .item(&monitor_temp)
+        .item(&monitor_gpu)
         .item(&monitor_mem)
-        .item(&monitor_net)
+        .item(&monitor_net);
+    if has_battery {
+        monitor_menu_builder = monitor_menu_builder.item(&monitor_battery);
+    }
+    let monitor_menu = monitor_menu_builder
+        .separator()
+        .item(&monitoring_paused)
+        .item(&tray_dynamic_icon)
+        .item(&notifications)
         .build()?;
 
-    let quit_item = MenuItem::with_id(app, "quit", "退出", true, None::<&str>)?;
+    let mut refresh_menu_builder = SubmenuBuilder::new(app, label("menu_refresh_interval", lang));
+    for refresh_item in &refresh_items {
+        refresh_menu_builder = refresh_menu_builder.item(&refresh_item.item);
+    }
+    let refresh_menu = refresh_menu_builder.build()?;
+
+    let display_next = MenuItem::with_id(
+        app,
+        "display_next",
+        label("display_next", lang),
+        true,
+        None::<&str>,
+    )?;
+    let display_items =
+        display_menu_items(app, available_targets(app), ui_state.monitor_target.as_ref())?;
+    let mut display_menu_builder = SubmenuBuilder::new(app, label("menu_display", lang))
+        .item(&display_next)
+        .separator();
+    for display_item in &display_items {
+        display_menu_builder = display_menu_builder.item(&display_item.item);
+    }
+    let display_menu = display_menu_builder.build()?;
+
+    let reset_settings_item = MenuItem::with_id(
+        app,
+        "reset_settings",
+        label("reset_settings", lang),
+        true,
+        None::<&str>,
+    )?;
+    let quit_item = MenuItem::with_id(app, "quit", label("quit", lang), true, None::<&str>)?;
+
+    let tray_items = TrayMenuItems {
+        autostart: autostart_item.clone(),
+        window_visible: window_visible_item.clone(),
+        settings_window: settings_window_item.clone(),
+        always_on_top: always_on_top_item.clone(),
+        click_through: click_through_item.clone(),
+        pos_top_left: pos_top_left.clone(),
+        pos_bottom_left: pos_bottom_left.clone(),
+        pos_top_right: pos_top_right.clone(),
+        pos_bottom_right: pos_bottom_right.clone(),
+        pos_top_center: pos_top_center.clone(),
+        pos_bottom_center: pos_bottom_center.clone(),
+        pos_left_center: pos_left_center.clone(),
+        pos_right_center: pos_right_center.clone(),
+        layout_horizontal: layout_horizontal.clone(),
+        layout_vertical: layout_vertical.clone(),
+        net_unit_bits: net_unit_bits.clone(),
+        net_unit_bytes: net_unit_bytes.clone(),
+        temp_unit_celsius: temp_unit_celsius.clone(),
+        temp_unit_fahrenheit: temp_unit_fahrenheit.clone(),
+        color_items: color_items.clone(),
+        opacity_items: opacity_items.clone(),
+        margin_items: margin_items.clone(),
+        font_size_items: font_size_items.clone(),
+        refresh_items: refresh_items.clone(),
+        monitor_cpu: monitor_cpu.clone(),
+        monitor_mem: monitor_mem.clone(),
+        monitor_net: monitor_net.clone(),
+        monitor_battery: monitor_battery.clone(),
+        monitor_temp: monitor_temp.clone(),
+        monitor_gpu: monitor_gpu.clone(),
+        monitoring_paused: monitoring_paused.clone(),
+        tray_dynamic_icon: tray_dynamic_icon.clone(),
+        notifications: notifications.clone(),
+        position_menu: position_menu.clone(),
+        margin_menu: margin_menu.clone(),
+        layout_menu: layout_menu.clone(),
+        color_menu: color_menu.clone(),
+        opacity_menu: opacity_menu.clone(),
+        font_size_menu: font_size_menu.clone(),
+        monitor_menu: monitor_menu.clone(),
+        net_unit_menu: net_unit_menu.clone(),
+        temp_unit_menu: temp_unit_menu.clone(),
+        refresh_menu: refresh_menu.clone(),
+        display_menu: display_menu.clone(),
+        display_next: display_next.clone(),
+        display_items: Arc::new(Mutex::new(display_items)),
+        reset_settings: reset_settings_item.clone(),
+        quit: quit_item.clone(),
+    };
 
     let tray_menu = MenuBuilder::new(app)
         .item(&position_menu)
+        .item(&display_menu)
+        .item(&margin_menu)
         .item(&layout_menu)
         .item(&color_menu)
+        .item(&opacity_menu)
+        .item(&font_size_menu)
         .item(&monitor_menu)
+        .item(&refresh_menu)
+        .item(&net_unit_menu)
+        .item(&temp_unit_menu)
         .separator()
+        .item(&settings_window_item)
+        .item(&window_visible_item)
+        .item(&always_on_top_item)
+        .item(&click_through_item)
         .item(&autostart_item)
         .separator()
+        .item(&reset_settings_item)
         .item(&quit_item)
         .build()?;
 
-    let mut tray_builder = TrayIconBuilder::new()
+    let mut tray_builder = TrayIconBuilder::with_id(TRAY_ID)
         .menu(&tray_menu)
         .show_menu_on_left_click(true)
         .on_menu_event({
@@ -389,6 +1717,34 @@ pub fn setup_tray(app: &tauri::AppHandle, ui_state: &UiState) -> tauri::Result<T
                             tray_items.set_autostart(!enabled);
                         }
                     }
+                    "window_visible" => {
+                        let visible = app
+                            .state::<Mutex<UiState>>()
+                            .lock()
+                            .map(|state| state.visible)
+                            .unwrap_or(true);
+                        update_window_visible(app, !visible, &tray_items);
+                    }
+                    "settings_window" => {
+                        let open = app.get_webview_window(SETTINGS_WINDOW).is_some();
+                        update_settings_window(app, !open, &tray_items);
+                    }
+                    "always_on_top" => {
+                        let enabled = app
+                            .state::<Mutex<UiState>>()
+                            .lock()
+                            .map(|state| state.always_on_top)
+                            .unwrap_or(true);
+                        update_always_on_top(app, !enabled, &tray_items);
+                    }
+                    "click_through" => {
+                        let enabled = app
+                            .state::<Mutex<UiState>>()
+                            .lock()
+                            .map(|state| state.click_through)
+                            .unwrap_or(false);
+                        update_click_through(app, !enabled, &tray_items);
+                    }
                     "pos_top_left" => {
                         update_position(app, WindowPosition::TopLeft, &tray_items);
                     }
@@ -401,12 +1757,57 @@ pub fn setup_tray(app: &tauri::AppHandle, ui_state: &UiState) -> tauri::Result<T
                     "pos_bottom_right" => {
                         update_position(app, WindowPosition::BottomRight, &tray_items);
                     }
+                    "pos_top_center" => {
+                        update_position(app, WindowPosition::TopCenter, &tray_items);
+                    }
+                    "pos_bottom_center" => {
+                        update_position(app, WindowPosition::BottomCenter, &tray_items);
+                    }
+                    "pos_left_center" => {
+                        update_position(app, WindowPosition::LeftCenter, &tray_items);
+                    }
+                    "pos_right_center" => {
+                        update_position(app, WindowPosition::RightCenter, &tray_items);
+                    }
+                    "margin_0" => {
+                        update_margin(app, 0, &tray_items);
+                    }
+                    "margin_8" => {
+                        update_margin(app, 8, &tray_items);
+                    }
+                    "margin_16" => {
+                        update_margin(app, 16, &tray_items);
+                    }
+                    "refresh_500" => {
+                        update_refresh_interval(app, 500, &tray_items);
+                    }
+                    "refresh_1000" => {
+                        update_refresh_interval(app, 1000, &tray_items);
+                    }
+                    "refresh_2000" => {
+                        update_refresh_interval(app, 2000, &tray_items);
+                    }
+                    "refresh_5000" => {
+                        update_refresh_interval(app, 5000, &tray_items);
+                    }
                     "layout_horizontal" => {
                         update_layout(app, Layout::Horizontal, &tray_items);
                     }
                     "layout_vertical" => {
                         update_layout(app, Layout::Vertical, &tray_items);
                     }
+                    "net_unit_bits" => {
+                        update_net_unit(app, NetworkUnit::Bits, &tray_items);
+                    }
+                    "net_unit_bytes" => {
+                        update_net_unit(app, NetworkUnit::Bytes, &tray_items);
+                    }
+                    "temp_unit_celsius" => {
+                        update_temp_unit(app, TemperatureUnit::Celsius, &tray_items);
+                    }
+                    "temp_unit_fahrenheit" => {
+                        update_temp_unit(app, TemperatureUnit::Fahrenheit, &tray_items);
+                    }
                     "color_white" => {
                         update_text_color(app, "#ffffff", &tray_items);
                     }
@@ -428,6 +1829,33 @@ pub fn setup_tray(app: &tauri::AppHandle, ui_state: &UiState) -> tauri::Result<T
                     "color_yellow" => {
                         update_text_color(app, "#ffd56a", &tray_items);
                     }
+                    "opacity_100" => {
+                        update_opacity(app, 1.0, &tray_items);
+                    }
+                    "opacity_80" => {
+                        update_opacity(app, 0.8, &tray_items);
+                    }
+                    "opacity_60" => {
+                        update_opacity(app, 0.6, &tray_items);
+                    }
+                    "opacity_40" => {
+                        update_opacity(app, 0.4, &tray_items);
+                    }
+                    "font_size_8" => {
+                        update_font_size(app, 8, &tray_items);
+                    }
+                    "font_size_10" => {
+                        update_font_size(app, 10, &tray_items);
+                    }
+                    "font_size_12" => {
+                        update_font_size(app, 12, &tray_items);
+                    }
+                    "font_size_15" => {
+                        update_font_size(app, 15, &tray_items);
+                    }
+                    "font_size_20" => {
+                        update_font_size(app, 20, &tray_items);
+                    }
                     "monitor_cpu" => {
                         update_monitor_visibility(app, MonitorItem::Cpu, &tray_items);
                     }
@@ -437,18 +1865,140 @@ pub fn setup_tray(app: &tauri::AppHandle, ui_state: &UiState) -> tauri::Result<T
                     "monitor_net" => {
                         update_monitor_visibility(app, MonitorItem::Net, &tray_items);
                     }
+                    "monitor_battery" => {
+                        update_monitor_visibility(app, MonitorItem::Battery, &tray_items);
+                    }
+                    "monitor_temp" => {
+                        update_monitor_visibility(app, MonitorItem::Temp, &tray_items);
+                    }
+                    "monitor_gpu" => {
+                        update_monitor_visibility(app, MonitorItem::Gpu, &tray_items);
+                    }
+                    "monitoring_paused" => {
+                        let paused = app
+                            .state::<Mutex<Monitor>>()
+                            .lock()
+                            .map(|monitor| monitor.is_paused())
+                            .unwrap_or(false);
+                        update_monitoring_paused(app, !paused, &tray_items);
+                    }
+                    "tray_dynamic_icon" => {
+                        let enabled = app
+                            .state::<Mutex<UiState>>()
+                            .lock()
+                            .map(|state| state.tray_dynamic_icon)
+                            .unwrap_or(false);
+                        update_tray_dynamic_icon(app, !enabled, &tray_items);
+                    }
+                    "notifications" => {
+                        let enabled = app
+                            .state::<Mutex<UiState>>()
+                            .lock()
+                            .map(|state| state.notifications)
+                            .unwrap_or(true);
+                        update_notifications(app, !enabled, &tray_items);
+                    }
+                    "display_next" => {
+                        select_next_monitor(app, &tray_items);
+                    }
+                    "reset_settings" => {
+                        reset_ui_state(app);
+                    }
                     "quit" => {
                         app.exit(0);
                     }
+                    id if id.starts_with("display_") => {
+                        if let Ok(index) = id["display_".len()..].parse() {
+                            update_monitor_target(app, index, &tray_items);
+                        }
+                    }
                     _ => {}
                 }
             }
         });
 
+    // 打开菜单前刷新显示器列表，避免残留已断开的显示器
+    tray_builder = tray_builder.on_tray_icon_event(|tray, event| {
+        if matches!(event, TrayIconEvent::Enter { .. } | TrayIconEvent::Click { .. }) {
+            let app = tray.app_handle();
+            if let Some(tray_items) = app.try_state::<TrayMenuItems>() {
+                tray_items.refresh_displays(app);
+            }
+        }
+    });
+
     if let Some(icon) = app.default_window_icon().cloned() {
         tray_builder = tray_builder.icon(icon);
     }
 
-    tray_builder.tooltip("corner-monitor").build(app)?;
+    tray_builder.tooltip(TRAY_TOOLTIP).build(app)?;
     Ok(tray_items)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixel(rgba: &[u8], size: u32, x: u32, y: u32) -> [u8; 4] {
+        let offset = ((y * size + x) * 4) as usize;
+        rgba[offset..offset + 4].try_into().unwrap()
+    }
+
+    #[test]
+    fn test_cpu_icon_fill_height() {
+        let size = 32;
+        let empty = cpu_icon_rgba(0.0, size);
+        assert_eq!(empty.len(), (size * size * 4) as usize);
+        assert_eq!(pixel(&empty, size, 0, 0)[3], 0xff);
+        assert_eq!(pixel(&empty, size, 16, 28)[3], 0);
+
+        let half = cpu_icon_rgba(50.0, size);
+        assert_eq!(pixel(&half, size, 16, 28), [0x7c, 0xff, 0x6b, 0xff]);
+        assert_eq!(pixel(&half, size, 16, 4)[3], 0);
+
+        let full = cpu_icon_rgba(150.0, size);
+        assert_eq!(pixel(&full, size, 16, 3), [0xff, 0x4d, 0x4d, 0xff]);
+    }
+
+    #[test]
+    fn test_cpu_icon_key() {
+        let size = 32;
+        // 填充高度和颜色都相同时不需要重绘
+        assert_eq!(cpu_icon_key(39.0, size), cpu_icon_key(40.0, size));
+        assert_ne!(cpu_icon_key(40.0, size), cpu_icon_key(50.0, size));
+        // 高度相同但跨过颜色分界
+        assert_ne!(cpu_icon_key(59.9, size), cpu_icon_key(60.0, size));
+        assert_ne!(cpu_icon_key(100.0, size), NO_TRAY_ICON);
+    }
+
+    #[test]
+    fn test_tray_tooltip() {
+        let mut info = SystemInfo::default();
+        info.cpu.total_usage = 23.0;
+        info.memory.usage_percent = 61.5;
+        info.network.total_upload_speed = 1024;
+        info.network.total_download_speed = 2 * 1024 * 1024;
+        let mut visibility = visibility_from_state(&UiState::default());
+        let bytes = NetworkUnit::Bytes;
+        assert_eq!(
+            tray_tooltip(&info, &visibility, bytes),
+            "CPU 23.00% · MEM 61.50% · NET ↑1.0 KiB/s ↓2.0 MiB/s"
+        );
+        assert_eq!(
+            tray_tooltip(&info, &visibility, NetworkUnit::Bits),
+            "CPU 23.00% · MEM 61.50% · NET ↑8.2 Kb/s ↓16.8 Mb/s"
+        );
+
+        visibility.net = false;
+        assert_eq!(
+            tray_tooltip(&info, &visibility, bytes),
+            "CPU 23.00% · MEM 61.50%"
+        );
+
+        visibility.cpu = false;
+        assert_eq!(tray_tooltip(&info, &visibility, bytes), "MEM 61.50%");
+
+        visibility.mem = false;
+        assert_eq!(tray_tooltip(&info, &visibility, bytes), TRAY_TOOLTIP);
+    }
+}
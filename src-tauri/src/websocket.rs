@@ -0,0 +1,194 @@
+//! WebSocket 指标推送
+//!
+//! 需要开启 `websocket` feature 编译：
+//!
+//! ```bash
+//! pnpm tauri build -- --features websocket
+//! ```
+//!
+//! 编译后通过 `set_ws_port` 命令设置端口即可启动，服务只监听 `127.0.0.1`。每次采集后
+//! 把最新的 `SystemInfo` 以 JSON 文本帧推送给所有已连接的客户端，推送频率与悬浮窗刷新
+//! 一致。端口设为 `null` 时关闭服务，默认关闭。
+
+use std::io::Write;
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use tungstenite::Message;
+
+use crate::monitor::SystemInfo;
+
+/// 同时连接的客户端上限，超出时直接拒绝新连接
+pub const MAX_CLIENTS: usize = 8;
+/// 每个客户端最多积压的快照数，发送跟不上时丢弃新的快照
+const CLIENT_QUEUE: usize = 4;
+/// 握手和写入的超时，避免慢客户端占住连接线程
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(5);
+/// 没有新快照时，连接线程检查服务是否停止的间隔
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 运行中的推送服务
+pub struct StreamServer {
+    addr: SocketAddr,
+    shutdown: Arc<AtomicBool>,
+    /// 各连接的快照队列，连接断开后在下一次推送时移除
+    clients: Arc<Mutex<Vec<SyncSender<Arc<str>>>>>,
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+impl StreamServer {
+    /// 在 `127.0.0.1:port` 上启动服务
+    pub fn start(port: u16) -> Result<Self, String> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+            .map_err(|error| format!("failed to bind port {}: {}", port, error))?;
+        let addr = listener.local_addr().map_err(|error| error.to_string())?;
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let clients: Arc<Mutex<Vec<SyncSender<Arc<str>>>>> = Arc::new(Mutex::new(Vec::new()));
+        let stop = Arc::clone(&shutdown);
+        let senders = Arc::clone(&clients);
+        let handle = thread::spawn(move || {
+            // 仍在运行的连接线程数
+            let active = Arc::new(AtomicUsize::new(0));
+            for stream in listener.incoming() {
+                if stop.load(Ordering::SeqCst) {
+                    break;
+                }
+                let Ok(mut stream) = stream else {
+                    continue;
+                };
+                if active.load(Ordering::SeqCst) >= MAX_CLIENTS {
+                    let _ = stream.set_write_timeout(Some(CLIENT_TIMEOUT));
+                    let _ = write!(
+                        stream,
+                        "HTTP/1.1 503 Service Unavailable\r\nConnection: close\r\n\r\n"
+                    );
+                    continue;
+                }
+                let (sender, updates) = mpsc::sync_channel(CLIENT_QUEUE);
+                if let Ok(mut senders) = senders.lock() {
+                    senders.push(sender);
+                }
+                active.fetch_add(1, Ordering::SeqCst);
+                let active = Arc::clone(&active);
+                let stop = Arc::clone(&stop);
+                thread::spawn(move || {
+                    let _ = serve(stream, updates, &stop);
+                    active.fetch_sub(1, Ordering::SeqCst);
+                });
+            }
+        });
+        Ok(Self {
+            addr,
+            shutdown,
+            clients,
+            handle: Some(handle),
+        })
+    }
+
+    /// 实际监听的端口
+    pub fn port(&self) -> u16 {
+        self.addr.port()
+    }
+
+    /// 把快照推送给所有客户端，并移除已断开的连接
+    pub fn broadcast(&self, info: &SystemInfo) {
+        let Ok(mut clients) = self.clients.lock() else {
+            return;
+        };
+        if clients.is_empty() {
+            return;
+        }
+        let Ok(json) = serde_json::to_string(info) else {
+            return;
+        };
+        let json: Arc<str> = Arc::from(json);
+        clients.retain(|client| {
+            !matches!(
+                client.try_send(Arc::clone(&json)),
+                Err(TrySendError::Disconnected(_))
+            )
+        });
+    }
+
+    /// 停止服务，断开所有客户端并等待监听线程退出
+    pub fn stop(mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // 丢弃快照队列，连接线程随即关闭各自的连接
+        if let Ok(mut clients) = self.clients.lock() {
+            clients.clear();
+        }
+        // 连接一次自身，唤醒阻塞在 `accept` 上的监听线程
+        let _ = TcpStream::connect_timeout(&self.addr, CLIENT_TIMEOUT);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// 完成握手后逐个发送快照，客户端断开或服务停止时返回
+fn serve(stream: TcpStream, updates: Receiver<Arc<str>>, stop: &AtomicBool) -> Result<(), String> {
+    stream
+        .set_read_timeout(Some(CLIENT_TIMEOUT))
+        .and_then(|_| stream.set_write_timeout(Some(CLIENT_TIMEOUT)))
+        .map_err(|error| error.to_string())?;
+    let mut socket = tungstenite::accept(stream).map_err(|error| error.to_string())?;
+    while !stop.load(Ordering::SeqCst) {
+        match updates.recv_timeout(POLL_INTERVAL) {
+            Ok(json) => socket
+                .send(Message::text(json.to_string()))
+                .map_err(|error| error.to_string())?,
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+    let _ = socket.close(None);
+    let _ = socket.flush();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_broadcast_to_clients() {
+        let server = StreamServer::start(0).unwrap();
+        let url = format!("ws://127.0.0.1:{}", server.port());
+        let (mut client, _) = tungstenite::connect(&url).unwrap();
+
+        let mut info = SystemInfo::default();
+        info.cpu.total_usage = 42.0;
+        server.broadcast(&info);
+        let message = client.read().unwrap();
+        let value: serde_json::Value = serde_json::from_str(message.to_text().unwrap()).unwrap();
+        assert_eq!(value["cpu"]["total_usage"], 42.0);
+
+        // 客户端断开后继续推送不会出错；连接线程写入失败退出后，下一次推送移除该连接
+        drop(client);
+        for _ in 0..100 {
+            server.broadcast(&info);
+            if server.clients.lock().unwrap().is_empty() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+        assert_eq!(server.clients.lock().unwrap().len(), 0);
+        server.stop();
+    }
+
+    #[test]
+    fn test_rejects_clients_over_limit() {
+        let server = StreamServer::start(0).unwrap();
+        let url = format!("ws://127.0.0.1:{}", server.port());
+        let clients: Vec<_> = (0..MAX_CLIENTS)
+            .map(|_| tungstenite::connect(&url).unwrap())
+            .collect();
+        assert!(tungstenite::connect(&url).is_err());
+        drop(clients);
+        server.stop();
+    }
+}
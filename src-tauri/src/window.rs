@@ -1,33 +1,104 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Mutex;
-use tauri::{Manager, PhysicalPosition, PhysicalSize};
+use std::thread;
+use std::time::Duration;
 
+use tauri::{
+    LogicalSize, Manager, PhysicalPosition, PhysicalSize, WebviewUrl, WebviewWindowBuilder,
+};
+
+use crate::i18n::{label, Lang};
 use crate::state::{
-    monitor_target_from_monitor, monitor_target_to_str, Layout, SettingsStore, UiState,
-    WindowPosition, KEY_MONITOR_TARGET, SIZE_HORIZONTAL, SIZE_VERTICAL,
+    monitor_position_key, monitor_positions_to_value, monitor_target_from_monitor,
+    monitor_target_to_str, position_to_str, Layout, SettingsStore, SnapMode, UiState,
+    WindowPosition, KEY_MONITOR_POSITIONS, KEY_MONITOR_TARGET, KEY_POSITION,
 };
+use crate::tray::TrayMenuItems;
+
+/// 设置窗口的标签，页面为 `settings.html`
+pub const SETTINGS_WINDOW: &str = "settings";
+/// 尺寸或缩放变化停止多久后重新定位，见 `schedule_reposition`
+const REPOSITION_DELAY: Duration = Duration::from_millis(150);
+
+/// 合并连续触发的窗口事件，事件停止一段时间后只处理一次
+///
+/// 调用方在 `record()` 返回 `true` 时启动等待线程，线程中调用 `settle()`
+/// 阻塞到事件停止后再处理。
+#[derive(Debug, Default)]
+pub struct Debounce {
+    /// 收到的事件数，等待线程据此判断事件是否已经停止
+    events: AtomicU64,
+    /// 已有等待中的线程
+    pending: AtomicBool,
+}
+
+impl Debounce {
+    /// 记录一次事件，返回是否需要启动新的等待线程
+    pub fn record(&self) -> bool {
+        self.events.fetch_add(1, Ordering::SeqCst);
+        !self.pending.swap(true, Ordering::SeqCst)
+    }
+
+    /// 阻塞到 `delay` 内没有新事件为止
+    pub fn settle(&self, delay: Duration) {
+        let mut seen = self.events.load(Ordering::SeqCst);
+        loop {
+            thread::sleep(delay);
+            let events = self.events.load(Ordering::SeqCst);
+            if events == seen {
+                break;
+            }
+            seen = events;
+        }
+        self.pending.store(false, Ordering::SeqCst);
+    }
+}
+
+/// 合并连续的尺寸、缩放变化事件，作为托管状态保存
+///
+/// 在显示器之间拖动时这些事件会连续触发几十次，每次都重新定位会让窗口抖动。
+#[derive(Debug, Default)]
+pub struct RepositionDebounce(Debounce);
 
 fn desired_position(
     monitor_pos: PhysicalPosition<i32>,
     monitor_size: PhysicalSize<u32>,
     window_size: PhysicalSize<u32>,
     position: WindowPosition,
+    margin: i32,
 ) -> PhysicalPosition<i32> {
-    let min_x = monitor_pos.x;
-    let min_y = monitor_pos.y;
-    let max_x = monitor_pos.x + monitor_size.width as i32 - window_size.width as i32;
-    let max_y = monitor_pos.y + monitor_size.height as i32 - window_size.height as i32;
-
-    let x = match position {
-        WindowPosition::TopLeft | WindowPosition::BottomLeft => min_x,
-        WindowPosition::TopRight | WindowPosition::BottomRight => max_x,
-    };
-    let y = match position {
-        WindowPosition::TopLeft | WindowPosition::TopRight => min_y,
-        WindowPosition::BottomLeft | WindowPosition::BottomRight => max_y,
+    let free_x = monitor_size.width as i32 - window_size.width as i32;
+    let free_y = monitor_size.height as i32 - window_size.height as i32;
+    let min_x = monitor_pos.x + margin;
+    let min_y = monitor_pos.y + margin;
+    let max_x = monitor_pos.x + free_x - margin;
+    let max_y = monitor_pos.y + free_y - margin;
+    let center_x = monitor_pos.x + free_x / 2;
+    let center_y = monitor_pos.y + free_y / 2;
+
+    let (x, y) = match position {
+        WindowPosition::TopLeft => (min_x, min_y),
+        WindowPosition::BottomLeft => (min_x, max_y),
+        WindowPosition::TopRight => (max_x, min_y),
+        WindowPosition::BottomRight => (max_x, max_y),
+        WindowPosition::TopCenter => (center_x, min_y),
+        WindowPosition::BottomCenter => (center_x, max_y),
+        WindowPosition::LeftCenter => (min_x, center_y),
+        WindowPosition::RightCenter => (max_x, center_y),
+        WindowPosition::Custom { x, y } => (monitor_pos.x + x, monitor_pos.y + y),
     };
 
-    let final_x = if max_x < min_x { min_x } else { x };
-    let final_y = if max_y < min_y { min_y } else { y };
+    // 显示器放不下窗口和间距时居中，避免超出屏幕
+    let final_x = if max_x < min_x {
+        monitor_pos.x + free_x.max(0) / 2
+    } else {
+        x.clamp(min_x, max_x)
+    };
+    let final_y = if max_y < min_y {
+        monitor_pos.y + free_y.max(0) / 2
+    } else {
+        y.clamp(min_y, max_y)
+    };
 
     PhysicalPosition::new(final_x, final_y)
 }
@@ -41,16 +112,31 @@ fn layout_window_size(
         .lock()
         .map(|state| state.layout)
         .unwrap_or(Layout::Vertical);
-    let logical = match layout {
-        Layout::Horizontal => SIZE_HORIZONTAL,
-        Layout::Vertical => SIZE_VERTICAL,
-    };
+    let logical = layout_size(app, layout);
     let scale = window.scale_factor()?;
     let width = (logical.width * scale).round() as u32;
     let height = (logical.height * scale).round() as u32;
     Ok(PhysicalSize::new(width, height))
 }
 
+/// 当前设置下指定布局的窗口尺寸（逻辑像素）
+pub fn layout_size(app: &tauri::AppHandle, layout: Layout) -> LogicalSize<f64> {
+    app.state::<Mutex<UiState>>()
+        .lock()
+        .map(|state| state.window_size(layout))
+        .unwrap_or_else(|_| UiState::default().window_size(layout))
+}
+
+pub fn window_margin(app: &tauri::AppHandle, window: &tauri::WebviewWindow) -> i32 {
+    let margin = app
+        .state::<Mutex<UiState>>()
+        .lock()
+        .map(|state| state.margin)
+        .unwrap_or(0);
+    let scale = window.scale_factor().unwrap_or(1.0);
+    (margin as f64 * scale).round() as i32
+}
+
 pub fn calculate_window_position_on_monitor(
     app: &tauri::AppHandle,
     window: &tauri::WebviewWindow,
@@ -68,6 +154,7 @@ pub fn calculate_window_position_on_monitor(
         monitor_size,
         window_size,
         position,
+        window_margin(app, window),
     ))
 }
 
@@ -78,16 +165,41 @@ pub fn selected_monitor(app: &tauri::AppHandle) -> Option<tauri::Monitor> {
         .ok()
         .and_then(|state| state.monitor_target.clone())?;
     let monitors = app.available_monitors().ok()?;
-    if let Some(monitor) = monitors.get(target.index) {
-        return Some(monitor.clone());
-    }
+    // 有名称时按名称匹配，避免显示器断开后序号错位指向别的显示器
     if let Some(name) = &target.name {
         return monitors
             .iter()
             .find(|monitor| monitor.name().map(|value| value == name).unwrap_or(false))
             .cloned();
     }
-    None
+    monitors.get(target.index).cloned()
+}
+
+/// 保存的显示器已断开（有记录但无法再匹配到）时返回 `true`
+pub fn selected_monitor_lost(app: &tauri::AppHandle) -> bool {
+    let has_target = app
+        .state::<Mutex<UiState>>()
+        .lock()
+        .map(|state| state.monitor_target.is_some())
+        .unwrap_or(false);
+    has_target && selected_monitor(app).is_none()
+}
+
+/// 返回保存的目标显示器；如果它已断开（或从未保存），回退到主显示器并更新保存的目标
+pub fn resolve_monitor_or_primary(app: &tauri::AppHandle) -> Option<tauri::Monitor> {
+    if let Some(monitor) = selected_monitor(app) {
+        return Some(monitor);
+    }
+    let primary = app.primary_monitor().ok().flatten()?;
+    let target = monitor_target_from_monitor(app, &primary);
+    if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+        state.monitor_target = target.clone();
+    }
+    if let Some(target) = target {
+        let store = app.state::<SettingsStore>();
+        store.set(KEY_MONITOR_TARGET, monitor_target_to_str(&target));
+    }
+    Some(primary)
 }
 
 pub fn monitor_for_window(
@@ -136,45 +248,29 @@ pub fn monitor_for_window(
         .or_else(|| app.primary_monitor().ok().flatten())
 }
 
-pub fn nearest_corner(
+/// 在 `mode` 的停靠点中找出离当前位置最近的一个
+pub fn nearest_snap_point(
     monitor_pos: PhysicalPosition<i32>,
     monitor_size: PhysicalSize<u32>,
     window_size: PhysicalSize<u32>,
     current_pos: PhysicalPosition<i32>,
+    margin: i32,
+    mode: SnapMode,
 ) -> (WindowPosition, PhysicalPosition<i32>) {
-    let candidates = [
-        (
-            WindowPosition::TopLeft,
-            desired_position(monitor_pos, monitor_size, window_size, WindowPosition::TopLeft),
-        ),
-        (
-            WindowPosition::TopRight,
-            desired_position(monitor_pos, monitor_size, window_size, WindowPosition::TopRight),
-        ),
-        (
-            WindowPosition::BottomLeft,
-            desired_position(monitor_pos, monitor_size, window_size, WindowPosition::BottomLeft),
-        ),
-        (
-            WindowPosition::BottomRight,
-            desired_position(
-                monitor_pos,
-                monitor_size,
-                window_size,
-                WindowPosition::BottomRight,
-            ),
-        ),
-    ];
-
-    let mut best = candidates[0];
+    let anchors = mode.anchors();
+    let mut best = (
+        anchors[0],
+        desired_position(monitor_pos, monitor_size, window_size, anchors[0], margin),
+    );
     let mut best_distance = i64::MAX;
-    for candidate in candidates {
-        let dx = current_pos.x as i64 - candidate.1.x as i64;
-        let dy = current_pos.y as i64 - candidate.1.y as i64;
+    for &anchor in anchors {
+        let position = desired_position(monitor_pos, monitor_size, window_size, anchor, margin);
+        let dx = current_pos.x as i64 - position.x as i64;
+        let dy = current_pos.y as i64 - position.y as i64;
         let distance = dx * dx + dy * dy;
         if distance < best_distance {
             best_distance = distance;
-            best = candidate;
+            best = (anchor, position);
         }
     }
     best
@@ -185,9 +281,8 @@ pub fn calculate_window_position(
     window: &tauri::WebviewWindow,
     position: WindowPosition,
 ) -> tauri::Result<PhysicalPosition<i32>> {
-    let monitor = selected_monitor(app)
-        .or_else(|| window.current_monitor().ok().flatten())
-        .or_else(|| window.primary_monitor().ok().flatten());
+    let monitor =
+        resolve_monitor_or_primary(app).or_else(|| window.current_monitor().ok().flatten());
     let Some(monitor) = monitor else {
         return Ok(PhysicalPosition::new(0, 0));
     };
@@ -208,17 +303,71 @@ pub fn apply_window_position(
     window.set_position(target)
 }
 
+/// 记录窗口在 `monitor` 上使用的位置，下次回到该显示器时优先恢复
+pub fn remember_monitor_position(
+    app: &tauri::AppHandle,
+    monitor: &tauri::Monitor,
+    position: WindowPosition,
+) {
+    let Some(target) = monitor_target_from_monitor(app, monitor) else {
+        return;
+    };
+    let key = monitor_position_key(&target);
+    let positions = match app.state::<Mutex<UiState>>().lock() {
+        Ok(mut state) => {
+            if state.monitor_positions.get(&key) == Some(&position) {
+                return;
+            }
+            state.monitor_positions.insert(key, position);
+            state.monitor_positions.clone()
+        }
+        Err(_) => return,
+    };
+    let store = app.state::<SettingsStore>();
+    store.set(KEY_MONITOR_POSITIONS, monitor_positions_to_value(&positions));
+}
+
+/// `monitor` 上保存的位置，没有记录时返回 `None`
+fn saved_monitor_position(
+    app: &tauri::AppHandle,
+    monitor: &tauri::Monitor,
+) -> Option<WindowPosition> {
+    let key = monitor_position_key(&monitor_target_from_monitor(app, monitor)?);
+    app.state::<Mutex<UiState>>()
+        .lock()
+        .ok()?
+        .monitor_positions
+        .get(&key)
+        .copied()
+}
+
 pub fn apply_layout_and_position(app: &tauri::AppHandle, window: &tauri::WebviewWindow) {
     let (layout, position) = match app.state::<Mutex<UiState>>().lock() {
         Ok(state) => (state.layout, state.position),
         Err(_) => (Layout::Vertical, WindowPosition::TopLeft),
     };
-    let target = match layout {
-        Layout::Horizontal => SIZE_HORIZONTAL,
-        Layout::Vertical => SIZE_VERTICAL,
+    let _ = window.set_size(layout_size(app, layout));
+    // 保存的显示器仍然存在时跟随窗口所在的显示器（拖动换屏），已断开时回退到主显示器
+    let monitor = match selected_monitor(app) {
+        Some(_) => monitor_for_window(app, window),
+        None => resolve_monitor_or_primary(app),
     };
-    let _ = window.set_size(target);
-    if let Some(monitor) = monitor_for_window(app, window) {
+    if let Some(monitor) = monitor {
+        // 该显示器有保存的位置时优先使用，没有时沿用全局位置
+        let position = match saved_monitor_position(app, &monitor) {
+            Some(saved) if saved != position => {
+                if let Ok(mut state) = app.state::<Mutex<UiState>>().lock() {
+                    state.position = saved;
+                }
+                let store = app.state::<SettingsStore>();
+                store.set(KEY_POSITION, position_to_str(saved));
+                if let Some(tray) = app.try_state::<TrayMenuItems>() {
+                    tray.set_position(saved);
+                }
+                saved
+            }
+            _ => position,
+        };
         if let Ok(target_pos) = calculate_window_position_on_monitor(app, window, position, &monitor)
         {
             let _ = window.set_position(target_pos);
@@ -235,3 +384,129 @@ pub fn apply_layout_and_position(app: &tauri::AppHandle, window: &tauri::Webview
         let _ = apply_window_position(app, window, position);
     }
 }
+
+/// 悬浮窗尺寸或缩放变化时调用，事件停止 [`REPOSITION_DELAY`] 后重新定位一次
+///
+/// 重新定位读取的是执行时窗口所在的显示器和尺寸，因此总是反映最后一次事件。
+pub fn schedule_reposition(app: &tauri::AppHandle) {
+    let Some(debounce) = app.try_state::<RepositionDebounce>() else {
+        return;
+    };
+    if !debounce.0.record() {
+        return;
+    }
+    let app = app.clone();
+    thread::spawn(move || {
+        app.state::<RepositionDebounce>().0.settle(REPOSITION_DELAY);
+        let handle = app.clone();
+        let _ = app.run_on_main_thread(move || {
+            if let Some(window) = handle.get_webview_window("main") {
+                apply_layout_and_position(&handle, &window);
+            }
+            if let Some(tray) = handle.try_state::<TrayMenuItems>() {
+                tray.refresh_displays(&handle);
+            }
+        });
+    });
+}
+
+/// 打开设置窗口，已经打开时只显示并聚焦，保证只有一个设置窗口
+pub fn show_settings_window(app: &tauri::AppHandle) -> tauri::Result<()> {
+    if let Some(window) = app.get_webview_window(SETTINGS_WINDOW) {
+        window.unminimize()?;
+        window.show()?;
+        return window.set_focus();
+    }
+    let lang = app
+        .state::<Mutex<UiState>>()
+        .lock()
+        .map(|state| state.lang)
+        .unwrap_or(Lang::Zh);
+    let url = WebviewUrl::App("settings.html".into());
+    WebviewWindowBuilder::new(app, SETTINGS_WINDOW, url)
+        .title(label("settings_window", lang))
+        .inner_size(420.0, 560.0)
+        .min_inner_size(360.0, 400.0)
+        .center()
+        .build()?;
+    Ok(())
+}
+
+/// 关闭设置窗口，悬浮窗仍在，关闭后应用不会退出
+pub fn close_settings_window(app: &tauri::AppHandle) -> tauri::Result<()> {
+    match app.get_webview_window(SETTINGS_WINDOW) {
+        Some(window) => window.close(),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debounce_coalesces_events() {
+        let debounce = Debounce::default();
+        assert!(debounce.record());
+        assert!(!debounce.record());
+        assert!(!debounce.record());
+        debounce.settle(Duration::from_millis(10));
+        // 等待结束后的下一次事件重新开始计时
+        assert!(debounce.record());
+    }
+
+    #[test]
+    fn test_desired_position_with_margin() {
+        let monitor_pos = PhysicalPosition::new(100, 0);
+        let monitor_size = PhysicalSize::new(1000, 800);
+        let window_size = PhysicalSize::new(200, 100);
+
+        let top_left = desired_position(
+            monitor_pos,
+            monitor_size,
+            window_size,
+            WindowPosition::TopLeft,
+            10,
+        );
+        assert_eq!(top_left, PhysicalPosition::new(110, 10));
+        let bottom_right = desired_position(
+            monitor_pos,
+            monitor_size,
+            window_size,
+            WindowPosition::BottomRight,
+            10,
+        );
+        assert_eq!(bottom_right, PhysicalPosition::new(890, 690));
+
+        // 间距过大时居中，不会超出屏幕
+        let centered = desired_position(
+            monitor_pos,
+            PhysicalSize::new(220, 120),
+            window_size,
+            WindowPosition::BottomRight,
+            50,
+        );
+        assert_eq!(centered, PhysicalPosition::new(110, 10));
+
+        let nearest = |current_pos, mode| {
+            nearest_snap_point(
+                monitor_pos,
+                monitor_size,
+                window_size,
+                current_pos,
+                10,
+                mode,
+            )
+        };
+        let (anchor, _) = nearest(PhysicalPosition::new(850, 650), SnapMode::Both);
+        assert_eq!(anchor, WindowPosition::BottomRight);
+        let (anchor, position) = nearest(PhysicalPosition::new(480, 30), SnapMode::Both);
+        assert_eq!(anchor, WindowPosition::TopCenter);
+        assert_eq!(position, PhysicalPosition::new(500, 10));
+        // 只吸附到角落或边缘时忽略另一类停靠点
+        let (anchor, _) = nearest(PhysicalPosition::new(480, 30), SnapMode::Corners);
+        assert_eq!(anchor, WindowPosition::TopLeft);
+        let (anchor, _) = nearest(PhysicalPosition::new(850, 650), SnapMode::Edges);
+        assert_eq!(anchor, WindowPosition::RightCenter);
+    }
+}
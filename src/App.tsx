@@ -1,95 +1,203 @@
-import { useEffect, useState } from "react";
+import { Fragment, useEffect, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
-import { getCurrentWindow } from "@tauri-apps/api/window";
 import "./App.css";
 
 type SystemInfo = {
   cpu: {
     total_usage: number;
     temperature: number | null;
+    temperature_source: string | null;
+    load_average: [number, number, number] | null;
+    avg_frequency: number;
+    max_frequency: number;
   };
   memory: {
     total: number;
     used: number;
     usage_percent: number;
+    pressure: "normal" | "warning" | "critical";
   };
   network: {
     total_upload_speed: number;
     total_download_speed: number;
+    peak_upload_speed: number;
+    peak_download_speed: number;
   };
+  battery: {
+    percentage: number;
+    state: "charging" | "discharging" | "full" | "unknown";
+  } | null;
+  uptime_secs: number;
+  last_error: { kind: string; message?: string } | null;
   timestamp: number;
 };
 
+type MonitorAlert = {
+  kind: "cpu" | "memory" | "disk" | "network" | "swap" | "disk_full";
+  value: number;
+  threshold: number;
+  active: boolean;
+  mount_point?: string;
+};
+
 type MonitorVisibility = {
   cpu: boolean;
   mem: boolean;
   net: boolean;
+  battery: boolean;
+  temp: boolean;
+  gpu: boolean;
+};
+
+type MetricItem = keyof MonitorVisibility;
+
+type MetricColors = {
+  cpu: string;
+  mem: string;
+  net: string;
+  disk: string;
 };
 
+const DEFAULT_METRIC_ORDER: MetricItem[] = [
+  "cpu",
+  "temp",
+  "gpu",
+  "mem",
+  "net",
+  "battery",
+];
+
 const formatPercent = (value: number) => `${value.toFixed(2)}%`;
 
+// 频率单位为 MHz，如 `3.8 GHz`
+const formatFrequency = (mhz: number) => `${(mhz / 1000).toFixed(1)} GHz`;
+
 const formatGB = (value: number, digits: number) =>
   `${(value / 1024 / 1024 / 1024).toFixed(digits)}`;
 
-const formatNetSpeed = (bytesPerSec: number) => {
-  const bitsPerSec = bytesPerSec * 8;
-  if (bitsPerSec < 1_000) {
-    return `${bitsPerSec.toFixed(0)}b`;
-  }
-  if (bitsPerSec < 1_000_000) {
-    return `${(bitsPerSec / 1_000).toFixed(1)}Kb`;
+type NetUnit = "bits" | "bytes";
+
+// 与后端 `format::UnitBase` 的单位保持一致：字节按 1024 进位，比特按 1000 进位
+const BYTE_UNITS = ["B", "KiB", "MiB", "GiB", "TiB"];
+const BIT_UNITS = ["b", "Kb", "Mb", "Gb", "Tb"];
+
+// 与后端 `format::format_scaled` 规则相同：不足一档显示整数，否则保留一位小数，
+// 按保留一位小数后的值进位，避免出现 `1024.0 KiB`
+const formatScaled = (amount: number, step: number, units: string[]) => {
+  if (amount < step) {
+    return [`${Math.round(amount)}`, units[0]];
   }
-  if (bitsPerSec < 1_000_000_000) {
-    return `${(bitsPerSec / 1_000_000).toFixed(1)}Mb`;
+  let value = amount / step;
+  let unit = 1;
+  while (Math.round(value * 10) >= step * 10 && unit < units.length - 1) {
+    value /= step;
+    unit += 1;
   }
-  return `${(bitsPerSec / 1_000_000_000).toFixed(1)}Gb`;
+  return [value.toFixed(1), units[unit]];
 };
 
+const scaleSpeed = (bytesPerSec: number, unit: NetUnit) =>
+  unit === "bits"
+    ? formatScaled(bytesPerSec * 8, 1000, BIT_UNITS)
+    : formatScaled(bytesPerSec, 1024, BYTE_UNITS);
+
+// 与托盘提示相同的完整格式，如 `1.5 MiB/s`
+const formatSpeed = (bytesPerSec: number, unit: NetUnit) => {
+  const [value, suffix] = scaleSpeed(bytesPerSec, unit);
+  return `${value} ${suffix}/s`;
+};
+
+// 窗口中省略空格和 `/s`，如 `1.5MiB`，最长为 `1023.9KiB`，竖排时不会换行
+const formatSpeedCompact = (bytesPerSec: number, unit: NetUnit) =>
+  scaleSpeed(bytesPerSec, unit).join("");
+
+type TempUnit = "celsius" | "fahrenheit";
+
 function App() {
   const [layout, setLayout] = useState<"vertical" | "horizontal">("vertical");
   const [textColor, setTextColor] = useState("#ffffff");
+  const [metricColors, setMetricColors] = useState<MetricColors | null>(null);
+  const [opacity, setOpacity] = useState(1);
+  const [clickThrough, setClickThrough] = useState(false);
+  const [paused, setPaused] = useState(false);
+  const [fontSize, setFontSize] = useState(10);
+  const [netUnit, setNetUnit] = useState<NetUnit>("bytes");
+  const [tempUnit, setTempUnit] = useState<TempUnit>("celsius");
+  const [cpuTemp, setCpuTemp] = useState("--");
+  const [alerts, setAlerts] = useState<Set<MonitorAlert["kind"]>>(new Set());
+  // 使用率超过阈值的磁盘挂载点，来自 `disk-alert` 事件
+  const [fullDisks, setFullDisks] = useState<Set<string>>(new Set());
+  const [metricOrder, setMetricOrder] =
+    useState<MetricItem[]>(DEFAULT_METRIC_ORDER);
   const [visibility, setVisibility] = useState<MonitorVisibility>({
     cpu: true,
     mem: true,
     net: true,
+    battery: false,
+    temp: false,
+    gpu: false,
   });
   const [stats, setStats] = useState({
     cpuUsage: 0,
     cpuTemp: null as number | null,
+    cpuTempSource: null as string | null,
+    cpuAvgFrequency: 0,
+    cpuMaxFrequency: 0,
     memUsage: 0,
     memUsed: 0,
     memTotal: 0,
+    memPressure: "normal" as SystemInfo["memory"]["pressure"],
     netUp: 0,
     netDown: 0,
+    netPeakUp: 0,
+    netPeakDown: 0,
+    battery: null as SystemInfo["battery"],
   });
   useEffect(() => {
     let mounted = true;
-    const fetchInfo = async () => {
-      try {
-        const info = await invoke<SystemInfo>("get_system_info");
-        if (!mounted) {
-          return;
-        }
-        setStats({
-          cpuUsage: info.cpu.total_usage ?? 0,
-          cpuTemp: info.cpu.temperature ?? null,
-          memUsage: info.memory.usage_percent ?? 0,
-          memUsed: info.memory.used ?? 0,
-          memTotal: info.memory.total ?? 0,
-          netUp: info.network.total_upload_speed ?? 0,
-          netDown: info.network.total_download_speed ?? 0,
-        });
-      } catch (error) {
-        console.error("Failed to fetch system info", error);
+    let unlisten: (() => void) | undefined;
+    const applyInfo = (info: SystemInfo) => {
+      if (!mounted) {
+        return;
       }
+      setStats({
+        cpuUsage: info.cpu.total_usage ?? 0,
+        cpuTemp: info.cpu.temperature ?? null,
+        cpuTempSource: info.cpu.temperature_source ?? null,
+        cpuAvgFrequency: info.cpu.avg_frequency ?? 0,
+        cpuMaxFrequency: info.cpu.max_frequency ?? 0,
+        memUsage: info.memory.usage_percent ?? 0,
+        memUsed: info.memory.used ?? 0,
+        memTotal: info.memory.total ?? 0,
+        memPressure: info.memory.pressure ?? "normal",
+        netUp: info.network.total_upload_speed ?? 0,
+        netDown: info.network.total_download_speed ?? 0,
+        netPeakUp: info.network.peak_upload_speed ?? 0,
+        netPeakDown: info.network.peak_download_speed ?? 0,
+        battery: info.battery ?? null,
+      });
     };
 
-    fetchInfo();
-    const timer = window.setInterval(fetchInfo, 1000);
+    invoke<SystemInfo>("get_system_info")
+      .then(applyInfo)
+      .catch((error) => {
+        console.error("Failed to fetch system info", error);
+      });
+    listen<SystemInfo>("system-info", (event) => {
+      applyInfo(event.payload);
+    })
+      .then((handler) => {
+        unlisten = handler;
+      })
+      .catch((error) => {
+        console.error("Failed to listen for system info", error);
+      });
     return () => {
       mounted = false;
-      window.clearInterval(timer);
+      if (unlisten) {
+        unlisten();
+      }
     };
   }, []);
 
@@ -156,6 +264,146 @@ function App() {
       });
   }, []);
 
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    listen<MetricColors>("metric-colors-changed", (event) => {
+      setMetricColors(event.payload);
+    })
+      .then((handler) => {
+        unlisten = handler;
+      })
+      .catch((error) => {
+        console.error("Failed to listen for metric colors", error);
+      });
+    return () => {
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, []);
+
+  useEffect(() => {
+    invoke<MetricColors>("get_metric_colors")
+      .then((value) => {
+        setMetricColors(value);
+      })
+      .catch((error) => {
+        console.error("Failed to load metric colors", error);
+      });
+  }, []);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    listen<number>("opacity-changed", (event) => {
+      setOpacity(event.payload);
+    })
+      .then((handler) => {
+        unlisten = handler;
+      })
+      .catch((error) => {
+        console.error("Failed to listen for opacity", error);
+      });
+    return () => {
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, []);
+
+  useEffect(() => {
+    invoke<number>("get_opacity")
+      .then((value) => {
+        setOpacity(value);
+      })
+      .catch((error) => {
+        console.error("Failed to load opacity", error);
+      });
+  }, []);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    listen<number>("font-size-changed", (event) => {
+      setFontSize(event.payload);
+    })
+      .then((handler) => {
+        unlisten = handler;
+      })
+      .catch((error) => {
+        console.error("Failed to listen for font size", error);
+      });
+    return () => {
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, []);
+
+  useEffect(() => {
+    invoke<number>("get_font_size")
+      .then((value) => {
+        setFontSize(value);
+      })
+      .catch((error) => {
+        console.error("Failed to load font size", error);
+      });
+  }, []);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    listen<boolean>("click-through-changed", (event) => {
+      setClickThrough(event.payload);
+    })
+      .then((handler) => {
+        unlisten = handler;
+      })
+      .catch((error) => {
+        console.error("Failed to listen for click-through", error);
+      });
+    return () => {
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, []);
+
+  useEffect(() => {
+    invoke<boolean>("get_click_through")
+      .then((value) => {
+        setClickThrough(value);
+      })
+      .catch((error) => {
+        console.error("Failed to load click-through", error);
+      });
+  }, []);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    listen<boolean>("monitoring-paused-changed", (event) => {
+      setPaused(event.payload);
+    })
+      .then((handler) => {
+        unlisten = handler;
+      })
+      .catch((error) => {
+        console.error("Failed to listen for monitoring pause", error);
+      });
+    return () => {
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, []);
+
+  useEffect(() => {
+    invoke<boolean>("get_monitoring_paused")
+      .then((value) => {
+        setPaused(value);
+      })
+      .catch((error) => {
+        console.error("Failed to load monitoring pause", error);
+      });
+  }, []);
+
   useEffect(() => {
     let unlisten: (() => void) | undefined;
     listen<MonitorVisibility>("monitor-visibility-changed", (event) => {
@@ -174,6 +422,34 @@ function App() {
     };
   }, []);
 
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    listen<MetricItem[]>("metric-order-changed", (event) => {
+      setMetricOrder(event.payload);
+    })
+      .then((handler) => {
+        unlisten = handler;
+      })
+      .catch((error) => {
+        console.error("Failed to listen for metric order", error);
+      });
+    return () => {
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, []);
+
+  useEffect(() => {
+    invoke<MetricItem[]>("get_metric_order")
+      .then((value) => {
+        setMetricOrder(value);
+      })
+      .catch((error) => {
+        console.error("Failed to load metric order", error);
+      });
+  }, []);
+
   useEffect(() => {
     invoke<MonitorVisibility>("get_monitor_visibility")
       .then((value) => {
@@ -184,6 +460,180 @@ function App() {
       });
   }, []);
 
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    listen<string>("net-unit-changed", (event) => {
+      const next = event.payload;
+      if (next === "bits" || next === "bytes") {
+        setNetUnit(next);
+      }
+    })
+      .then((handler) => {
+        unlisten = handler;
+      })
+      .catch((error) => {
+        console.error("Failed to listen for network unit", error);
+      });
+    return () => {
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, []);
+
+  useEffect(() => {
+    invoke<string>("get_net_unit")
+      .then((value) => {
+        if (value === "bits" || value === "bytes") {
+          setNetUnit(value);
+        }
+      })
+      .catch((error) => {
+        console.error("Failed to load network unit", error);
+      });
+  }, []);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    listen<string>("temp-unit-changed", (event) => {
+      const next = event.payload;
+      if (next === "celsius" || next === "fahrenheit") {
+        setTempUnit(next);
+      }
+    })
+      .then((handler) => {
+        unlisten = handler;
+      })
+      .catch((error) => {
+        console.error("Failed to listen for temperature unit", error);
+      });
+    return () => {
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, []);
+
+  useEffect(() => {
+    invoke<string>("get_temperature_unit")
+      .then((value) => {
+        if (value === "celsius" || value === "fahrenheit") {
+          setTempUnit(value);
+        }
+      })
+      .catch((error) => {
+        console.error("Failed to load temperature unit", error);
+      });
+  }, []);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    listen<MonitorAlert>("monitor-alert", (event) => {
+      const { kind, active } = event.payload;
+      setAlerts((current) => {
+        const next = new Set(current);
+        if (active) {
+          next.add(kind);
+        } else {
+          next.delete(kind);
+        }
+        return next;
+      });
+    })
+      .then((handler) => {
+        unlisten = handler;
+      })
+      .catch((error) => {
+        console.error("Failed to listen for monitor alerts", error);
+      });
+    return () => {
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, []);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    listen<MonitorAlert>("swap-pressure", (event) => {
+      const { active } = event.payload;
+      setAlerts((current) => {
+        const next = new Set(current);
+        if (active) {
+          next.add("swap");
+        } else {
+          next.delete("swap");
+        }
+        return next;
+      });
+    })
+      .then((handler) => {
+        unlisten = handler;
+      })
+      .catch((error) => {
+        console.error("Failed to listen for swap pressure", error);
+      });
+    return () => {
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, []);
+
+  // 温度由后端按单位换算并格式化，前端不重复换算公式
+  useEffect(() => {
+    if (stats.cpuTemp == null) {
+      setCpuTemp("--");
+      return;
+    }
+    let current = true;
+    invoke<string>("format_temperature", {
+      celsius: stats.cpuTemp,
+      unit: tempUnit,
+    })
+      .then((value) => {
+        if (current) {
+          setCpuTemp(value);
+        }
+      })
+      .catch((error) => {
+        console.error("Failed to format temperature", error);
+      });
+    return () => {
+      current = false;
+    };
+  }, [stats.cpuTemp, tempUnit]);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    listen<MonitorAlert>("disk-alert", (event) => {
+      const { mount_point: mountPoint, active } = event.payload;
+      if (!mountPoint) {
+        return;
+      }
+      setFullDisks((current) => {
+        const next = new Set(current);
+        if (active) {
+          next.add(mountPoint);
+        } else {
+          next.delete(mountPoint);
+        }
+        return next;
+      });
+    })
+      .then((handler) => {
+        unlisten = handler;
+      })
+      .catch((error) => {
+        console.error("Failed to listen for disk alerts", error);
+      });
+    return () => {
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, []);
+
   const handleMouseDown = (event: React.MouseEvent<HTMLDivElement>) => {
     if (event.button !== 0) {
       if (event.button === 2) {
@@ -193,7 +643,7 @@ function App() {
       }
       return;
     }
-    getCurrentWindow().startDragging().catch((error) => {
+    invoke("enable_drag").catch((error) => {
       console.error("Failed to start dragging", error);
     });
   };
@@ -202,44 +652,107 @@ function App() {
     if (event.button !== 0) {
       return;
     }
-    invoke("snap_window").catch((error) => {
-      console.error("Failed to snap window", error);
+    invoke("finish_drag").catch((error) => {
+      console.error("Failed to finish dragging", error);
     });
   };
 
+  // 读不到频率时（部分虚拟机）平均和最大值均为 0，不显示提示
+  const cpuFrequency =
+    stats.cpuAvgFrequency > 0
+      ? `${formatFrequency(stats.cpuAvgFrequency)} / max ${formatFrequency(stats.cpuMaxFrequency)}`
+      : undefined;
+
+  const peakUp = formatSpeed(stats.netPeakUp, netUnit);
+  const peakDown = formatSpeed(stats.netPeakDown, netUnit);
+
+  const metricViews: Record<MetricItem, React.ReactNode> = {
+    cpu: visibility.cpu && (
+      <div
+        className={alerts.has("cpu") ? "alert" : undefined}
+        style={{ color: metricColors?.cpu }}
+      >
+        <b>CPU</b>
+        <div title={cpuFrequency}>{formatPercent(stats.cpuUsage)}</div>
+        {!visibility.temp && (
+          <div title={stats.cpuTempSource ?? undefined}>{cpuTemp}</div>
+        )}
+      </div>
+    ),
+    temp: visibility.temp && (
+      <div>
+        <b>Temp</b>
+        <div title={stats.cpuTempSource ?? undefined}>{cpuTemp}</div>
+      </div>
+    ),
+    // GPU 采集尚未实现，先占位
+    gpu: visibility.gpu && (
+      <div>
+        <b>GPU</b>
+        <div>--</div>
+      </div>
+    ),
+    mem: visibility.mem && (
+      <div
+        className={
+          alerts.has("memory")
+            ? "alert"
+            : alerts.has("swap")
+              ? "pressure-critical"
+              : stats.memPressure !== "normal"
+              ? `pressure-${stats.memPressure}`
+              : undefined
+        }
+        style={{ color: metricColors?.mem }}
+      >
+        <b>Mem</b>
+        <div>{formatPercent(stats.memUsage)}</div>
+        <div>
+          {formatGB(stats.memUsed, 1)}/{formatGB(stats.memTotal, 0)}
+        </div>
+      </div>
+    ),
+    net: visibility.net && (
+      <div
+        className={alerts.has("network") ? "alert" : undefined}
+        title={`peak ↑${peakUp} ↓${peakDown}`}
+        style={{ color: metricColors?.net }}
+      >
+        <b>Net</b>
+        <div>↑{formatSpeedCompact(stats.netUp, netUnit)}</div>
+        <div>↓{formatSpeedCompact(stats.netDown, netUnit)}</div>
+      </div>
+    ),
+    battery: visibility.battery && stats.battery && (
+      <div>
+        <b>Bat</b>
+        <div>{stats.battery.percentage.toFixed(0)}%</div>
+        <div>{stats.battery.state === "charging" ? "⚡" : "--"}</div>
+      </div>
+    ),
+  };
+
   return (
     <div
-      className={
-        layout === "horizontal" ? "layout-horizontal" : "layout-vertical"
-      }
-      style={{ color: textColor }}
+      className={[
+        layout === "horizontal" ? "layout-horizontal" : "layout-vertical",
+        clickThrough ? "click-through" : "",
+        paused ? "paused" : "",
+      ].join(" ")}
+      style={{ color: textColor, opacity, fontSize: `${fontSize}px` }}
       onMouseDown={handleMouseDown}
       onMouseUp={handleMouseUp}
       onContextMenu={(event) => event.preventDefault()}
     >
-      {visibility.cpu && (
-        <div>
-          <b>CPU</b>
-          <div>{formatPercent(stats.cpuUsage)}</div>
-          <div>
-            {stats.cpuTemp == null ? "--" : `${stats.cpuTemp.toFixed(1)}°C`}
-          </div>
-        </div>
-      )}
-      {visibility.mem && (
-        <div>
-          <b>Mem</b>
-          <div>{formatPercent(stats.memUsage)}</div>
-          <div>
-            {formatGB(stats.memUsed, 1)}/{formatGB(stats.memTotal, 0)}
-          </div>
-        </div>
-      )}
-      {visibility.net && (
-        <div>
-          <b>Net</b>
-          <div>↑{formatNetSpeed(stats.netUp)}/s</div>
-          <div>↓{formatNetSpeed(stats.netDown)}/s</div>
+      {metricOrder.map((item) => (
+        <Fragment key={item}>{metricViews[item]}</Fragment>
+      ))}
+      {fullDisks.size > 0 && (
+        <div className="alert" style={{ color: metricColors?.disk }}>
+          <b>Disk</b>
+          {[...fullDisks].map((mountPoint) => (
+            <div key={mountPoint}>{mountPoint}</div>
+          ))}
         </div>
       )}
     </div>
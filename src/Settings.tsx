@@ -0,0 +1,190 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import "./Settings.css";
+
+type Lang = "zh" | "en";
+
+type Layout = "horizontal" | "vertical";
+
+const LABELS: Record<Lang, Record<string, string>> = {
+  zh: {
+    layout: "布局",
+    horizontal: "水平",
+    vertical: "垂直",
+    textColor: "文字颜色",
+    opacity: "透明度",
+    fontSize: "文字大小",
+    margin: "边距",
+    lockToCorner: "拖动后吸附到角落",
+    language: "语言",
+  },
+  en: {
+    layout: "Layout",
+    horizontal: "Horizontal",
+    vertical: "Vertical",
+    textColor: "Text Color",
+    opacity: "Opacity",
+    fontSize: "Font Size",
+    margin: "Margin",
+    lockToCorner: "Snap to Corner After Drag",
+    language: "Language",
+  },
+};
+
+// 与后端 `state::MIN_FONT_SIZE`、`MAX_FONT_SIZE`、`MIN_OPACITY`、`MAX_MARGIN` 保持一致
+const MIN_FONT_SIZE = 8;
+const MAX_FONT_SIZE = 48;
+const MIN_OPACITY = 0.1;
+const MAX_MARGIN = 200;
+
+// 读取一项设置，并在托盘或悬浮窗修改时通过 `event` 同步
+function useSetting<T>(command: string, event?: string) {
+  const [value, setValue] = useState<T | null>(null);
+
+  useEffect(() => {
+    invoke<T>(command)
+      .then(setValue)
+      .catch((error) => {
+        console.error(`Failed to run ${command}`, error);
+      });
+  }, [command]);
+
+  useEffect(() => {
+    if (!event) {
+      return;
+    }
+    let unlisten: (() => void) | undefined;
+    listen<T>(event, (payload) => {
+      setValue(payload.payload);
+    })
+      .then((handler) => {
+        unlisten = handler;
+      })
+      .catch((error) => {
+        console.error(`Failed to listen for ${event}`, error);
+      });
+    return () => {
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, [event]);
+
+  return [value, setValue] as const;
+}
+
+const save = (command: string, args: Record<string, unknown>) =>
+  invoke(command, args).catch((error) => {
+    console.error(`Failed to run ${command}`, error);
+  });
+
+function Settings() {
+  const [lang, setLang] = useSetting<Lang>("get_language");
+  const [layout] = useSetting<Layout>("get_layout", "layout-changed");
+  const [textColor] = useSetting<string>(
+    "get_text_color",
+    "text-color-changed",
+  );
+  const [opacity] = useSetting<number>("get_opacity", "opacity-changed");
+  const [fontSize] = useSetting<number>("get_font_size", "font-size-changed");
+  const [margin, setMargin] = useSetting<number>("get_margin");
+  const [lockToCorner, setLockToCorner] =
+    useSetting<boolean>("get_lock_to_corner");
+
+  const t = LABELS[lang ?? "zh"];
+
+  return (
+    <main className="settings">
+      <label>
+        <span>{t.layout}</span>
+        <select
+          value={layout ?? "vertical"}
+          onChange={(event) =>
+            save("set_layout", { layout: event.target.value })
+          }
+        >
+          <option value="horizontal">{t.horizontal}</option>
+          <option value="vertical">{t.vertical}</option>
+        </select>
+      </label>
+      <label>
+        <span>{t.textColor}</span>
+        <input
+          type="color"
+          value={(textColor ?? "#ffffff").slice(0, 7)}
+          onChange={(event) =>
+            save("set_text_color", { color: event.target.value })
+          }
+        />
+      </label>
+      <label>
+        <span>{t.opacity}</span>
+        <input
+          type="range"
+          min={MIN_OPACITY}
+          max={1}
+          step={0.05}
+          value={opacity ?? 1}
+          onChange={(event) =>
+            save("set_opacity", { opacity: Number(event.target.value) })
+          }
+        />
+      </label>
+      <label>
+        <span>{t.fontSize}</span>
+        <input
+          type="number"
+          min={MIN_FONT_SIZE}
+          max={MAX_FONT_SIZE}
+          value={fontSize ?? 10}
+          onChange={(event) =>
+            save("set_font_size", { size: Number(event.target.value) })
+          }
+        />
+      </label>
+      <label>
+        <span>{t.margin}</span>
+        <input
+          type="number"
+          min={0}
+          max={MAX_MARGIN}
+          value={margin ?? 0}
+          onChange={(event) => {
+            const next = Number(event.target.value);
+            setMargin(next);
+            save("set_margin", { margin: next });
+          }}
+        />
+      </label>
+      <label>
+        <span>{t.lockToCorner}</span>
+        <input
+          type="checkbox"
+          checked={lockToCorner ?? true}
+          onChange={(event) => {
+            const next = event.target.checked;
+            setLockToCorner(next);
+            save("set_lock_to_corner", { locked: next });
+          }}
+        />
+      </label>
+      <label>
+        <span>{t.language}</span>
+        <select
+          value={lang ?? "zh"}
+          onChange={(event) => {
+            const next = event.target.value as Lang;
+            setLang(next);
+            save("set_language", { language: next });
+          }}
+        >
+          <option value="zh">中文</option>
+          <option value="en">English</option>
+        </select>
+      </label>
+    </main>
+  );
+}
+
+export default Settings;